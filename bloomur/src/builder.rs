@@ -0,0 +1,182 @@
+use core::f64::consts::LN_2;
+
+use super::{hasher::SimMurmur, BloomHasher, Filter};
+
+const SMALL_BLOCK: usize = 128;
+const MEDIUM_BLOCK: usize = 1024;
+const LARGE_BLOCK: usize = 8192;
+
+const SMALL_THRESHOLD: usize = 16 * 1024;
+const MEDIUM_THRESHOLD: usize = 1024 * 1024;
+
+/// Returns the optimal bits per key for the target false positive rate, `-ln(fp) / ln(2)^2`.
+#[inline]
+fn optimal_bits_per_key(fp: f64) -> usize {
+  use libm::{ceil, log};
+  ceil(-log(fp) / (LN_2 * LN_2)) as usize
+}
+
+#[derive(Debug, Clone)]
+enum Inner<S> {
+  Small(Filter<SMALL_BLOCK, S>),
+  Medium(Filter<MEDIUM_BLOCK, S>),
+  Large(Filter<LARGE_BLOCK, S>),
+}
+
+macro_rules! dispatch {
+  ($this:expr, |$f:ident| $expr:expr) => {
+    match $this {
+      Inner::Small($f) => $expr,
+      Inner::Medium($f) => $expr,
+      Inner::Large($f) => $expr,
+    }
+  };
+}
+
+/// A bloom filter builder which picks the block size at runtime.
+///
+/// Comparing to [`Filter`], which requires the number of hashes per block to be chosen at
+/// the type level, `FilterBuilder` chooses a suitable block size according to the expected
+/// number of entries.
+#[derive(Debug, Clone)]
+pub struct FilterBuilder<S = SimMurmur> {
+  inner: Inner<S>,
+}
+
+impl FilterBuilder {
+  /// Creates a new filter builder for the expected number of entries and the target false positive rate.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::FilterBuilder;
+  ///
+  /// let f = FilterBuilder::new(1_000_000, 0.01);
+  /// ```
+  #[inline]
+  pub fn new(num_entries: usize, fp: f64) -> Self {
+    Self::with_hasher(num_entries, fp, SimMurmur::new())
+  }
+}
+
+impl<S> FilterBuilder<S> {
+  /// Creates a new filter builder for the expected number of entries and the target false positive rate with the given hasher.
+  ///
+  /// The filter is sized with the optimal bits per key for `fp`, `-ln(fp) / ln(2)^2`.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{FilterBuilder, hasher::SimMurmur};
+  ///
+  /// let f = FilterBuilder::with_hasher(1_000_000, 0.01, SimMurmur::new());
+  /// ```
+  #[inline]
+  pub fn with_hasher(num_entries: usize, fp: f64, hasher: S) -> Self {
+    let bpk = optimal_bits_per_key(fp);
+    let inner = if num_entries <= SMALL_THRESHOLD {
+      Inner::Small(Filter::with_bits_per_key_and_hasher(bpk, hasher))
+    } else if num_entries <= MEDIUM_THRESHOLD {
+      Inner::Medium(Filter::with_bits_per_key_and_hasher(bpk, hasher))
+    } else {
+      Inner::Large(Filter::with_bits_per_key_and_hasher(bpk, hasher))
+    };
+
+    Self { inner }
+  }
+
+  /// Returns the number of hashes stored per block.
+  #[inline]
+  pub const fn block_size(&self) -> usize {
+    match self.inner {
+      Inner::Small(_) => SMALL_BLOCK,
+      Inner::Medium(_) => MEDIUM_BLOCK,
+      Inner::Large(_) => LARGE_BLOCK,
+    }
+  }
+}
+
+impl<S> FilterBuilder<S>
+where
+  S: BloomHasher,
+{
+  /// Adds a key to the filter.
+  #[inline]
+  pub fn insert(&mut self, key: &[u8]) {
+    dispatch!(&mut self.inner, |f| f.insert(key))
+  }
+
+  /// Returns the length of the final filter.
+  #[inline]
+  pub const fn filter_length(&self) -> usize {
+    dispatch!(&self.inner, |f| f.filter_length())
+  }
+
+  /// Finalize to the given buffer.
+  ///
+  /// See [`Filter::finalize_to`] for more details.
+  #[inline]
+  pub fn finalize_to(self, buf: &mut [u8]) -> Result<usize, usize> {
+    dispatch!(self.inner, |f| f.finalize_to(buf))
+  }
+
+  /// Finalizes the filter.
+  #[inline]
+  pub fn finalize(self) -> std::vec::Vec<u8> {
+    dispatch!(self.inner, |f| f.finalize())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::FrozenFilter;
+
+  fn false_positive_rate(num_entries: usize, fp: f64) {
+    let mut builder = FilterBuilder::new(num_entries, fp);
+    for i in 0..num_entries as u32 {
+      builder.insert(&i.to_le_bytes());
+    }
+
+    let filter = builder.finalize();
+    let frozen = FrozenFilter::new(filter.as_slice());
+    for i in 0..num_entries as u32 {
+      assert!(frozen.may_contain(&i.to_le_bytes()));
+    }
+
+    const PROBES: u32 = 10_000;
+    let mut false_positives = 0;
+    for i in 0..PROBES {
+      if frozen.may_contain(&(u32::MAX - i).to_le_bytes()) {
+        false_positives += 1;
+      }
+    }
+
+    let rate = false_positives as f64 / PROBES as f64;
+    assert!(
+      rate <= fp * 1.5,
+      "num_entries={num_entries}: false positive rate {rate} exceeds {}",
+      fp * 1.5
+    );
+  }
+
+  #[test]
+  fn block_size() {
+    assert_eq!(FilterBuilder::new(1_000, 0.01).block_size(), SMALL_BLOCK);
+    assert_eq!(FilterBuilder::new(100_000, 0.01).block_size(), MEDIUM_BLOCK);
+    assert_eq!(
+      FilterBuilder::new(10_000_000, 0.01).block_size(),
+      LARGE_BLOCK
+    );
+  }
+
+  #[test]
+  fn false_positive_rate_small() {
+    false_positive_rate(1_000, 0.01);
+  }
+
+  #[test]
+  fn false_positive_rate_medium() {
+    false_positive_rate(100_000, 0.01);
+  }
+}
@@ -1,6 +1,9 @@
 use smallvec::SmallVec;
 
-use super::{hasher::SimMurmur, BloomHasher};
+use super::{
+  hasher::{SimMurmur, TaggedHasher},
+  BloomHasher,
+};
 
 use core::f64::consts::LN_2;
 use std::vec::Vec;
@@ -32,6 +35,25 @@ pub fn bits_per_key(num_entries: usize, fp: f64) -> usize {
   ceil(LN_2 * size / num_entries as f64) as usize
 }
 
+/// Returns the largest bits per key whose filter for `num_entries` keys fits in `max_bytes`,
+/// including the 5-byte footer, clamped to at least `1`.
+#[inline]
+const fn budget_bits_per_key(num_entries: usize, max_bytes: usize) -> usize {
+  let mut n_lines = max_bytes.saturating_sub(5) / CACHE_LINE_SIZE;
+  // The number of lines is always rounded up to an odd number.
+  if n_lines % 2 == 0 {
+    n_lines = n_lines.saturating_sub(1);
+  }
+
+  let num_entries = if num_entries == 0 { 1 } else { num_entries };
+  let bpk = n_lines * CACHE_LINE_BITS / num_entries;
+  if bpk == 0 {
+    1
+  } else {
+    bpk
+  }
+}
+
 /// A bloom filter builder.
 #[derive(Debug, Clone)]
 pub struct Filter<const N: usize = 128, S = SimMurmur> {
@@ -44,6 +66,13 @@ pub struct Filter<const N: usize = 128, S = SimMurmur> {
   // We store the hashes in blocks.
   blocks: SmallVec<[Vec<u32>; 2]>,
 
+  // Up to this many keys, the filter is finalized as an exact set of hashes.
+  exact_threshold: usize,
+
+  // The hashes inserted so far, only when distinct tracking is enabled.
+  #[cfg(feature = "std")]
+  seen: Option<std::collections::HashSet<u32>>,
+
   hasher: S,
 }
 
@@ -65,6 +94,9 @@ impl<const N: usize> Filter<N> {
       num_hashes: 0,
       last_hash: 0,
       blocks: SmallVec::new_const(),
+      exact_threshold: 0,
+      #[cfg(feature = "std")]
+      seen: None,
       hasher: SimMurmur::new(),
     }
   }
@@ -85,9 +117,37 @@ impl<const N: usize> Filter<N> {
       num_hashes: 0,
       last_hash: 0,
       blocks: SmallVec::new_const(),
+      exact_threshold: 0,
+      #[cfg(feature = "std")]
+      seen: None,
       hasher: SimMurmur::new(),
     }
   }
+
+  /// Creates a new filter builder whose filter for `num_entries` keys fits in `max_bytes`.
+  ///
+  /// The largest bits per key which keeps [`filter_length`](Filter::filter_length) within
+  /// `max_bytes` is chosen, clamped to at least `1`, and returned along with the builder so
+  /// the effective false positive rate can be reported. If even `1` bit per key does not fit,
+  /// or more than `num_entries` keys are inserted, the filter can exceed the budget.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::Filter;
+  ///
+  /// let (mut f, bits_per_key) = Filter::<512>::with_budget(1000, 1024);
+  /// for i in 0..1000u32 {
+  ///   f.insert(&i.to_le_bytes());
+  /// }
+  /// assert!(f.filter_length() <= 1024);
+  /// assert_eq!(bits_per_key, f.bits_per_key());
+  /// ```
+  #[inline]
+  pub const fn with_budget(num_entries: usize, max_bytes: usize) -> (Self, usize) {
+    let bpk = budget_bits_per_key(num_entries, max_bytes);
+    (Self::with_bits_per_key(bpk), bpk)
+  }
 }
 
 impl<const N: usize, S> Filter<N, S> {
@@ -108,6 +168,9 @@ impl<const N: usize, S> Filter<N, S> {
       num_hashes: 0,
       last_hash: 0,
       blocks: SmallVec::new_const(),
+      exact_threshold: 0,
+      #[cfg(feature = "std")]
+      seen: None,
       hasher,
     }
   }
@@ -128,9 +191,101 @@ impl<const N: usize, S> Filter<N, S> {
       num_hashes: 0,
       last_hash: 0,
       blocks: SmallVec::new_const(),
+      exact_threshold: 0,
+      #[cfg(feature = "std")]
+      seen: None,
       hasher,
     }
   }
+
+  /// Creates a new filter builder whose filter for `num_entries` keys fits in `max_bytes`,
+  /// with the given hasher.
+  ///
+  /// See [`with_budget`](Filter::with_budget) for how the bits per key is chosen.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, hasher::SimMurmur};
+  ///
+  /// let (f, bits_per_key) =
+  ///   Filter::<512, SimMurmur>::with_budget_and_hasher(1000, 1024, SimMurmur::new());
+  /// assert_eq!(bits_per_key, 7);
+  /// ```
+  #[inline]
+  pub const fn with_budget_and_hasher(
+    num_entries: usize,
+    max_bytes: usize,
+    hasher: S,
+  ) -> (Self, usize) {
+    let bpk = budget_bits_per_key(num_entries, max_bytes);
+    (Self::with_bits_per_key_and_hasher(bpk, hasher), bpk)
+  }
+
+  /// Returns the number of bits per key.
+  #[inline]
+  pub const fn bits_per_key(&self) -> usize {
+    self.bits_per_key
+  }
+
+  /// Finalizes filters of at most `max_keys` keys as an exact set instead of a bit array.
+  ///
+  /// The exact set is the sorted list of the 4-byte key hashes, which
+  /// [`FrozenFilter`](crate::FrozenFilter) queries by binary search, so it has no false
+  /// positives beyond hash collisions. For a handful of keys it is also smaller than a single
+  /// cache line of bits. The threshold is `0`, i.e. disabled, by default.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, FrozenFilter};
+  ///
+  /// let mut f = Filter::<512>::with_bits_per_key(10).with_exact_threshold(16);
+  /// f.insert(b"hello");
+  /// f.insert(b"world");
+  /// assert_eq!(f.filter_length(), 2 * 4 + 5);
+  ///
+  /// let frozen = FrozenFilter::new(f.finalize());
+  /// assert!(frozen.may_contain(b"hello"));
+  /// assert!(!frozen.may_contain(b"foo"));
+  /// ```
+  #[inline]
+  pub fn with_exact_threshold(mut self, max_keys: usize) -> Self {
+    self.exact_threshold = max_keys;
+    self
+  }
+
+  /// Tracks the inserted hashes, so [`insert_checked`](Filter::insert_checked) can tell whether
+  /// a key is new.
+  ///
+  /// The blocked format does not retain which hashes are present, so this keeps a transient set
+  /// of every inserted hash, about 4 bytes per distinct key on top of the filter, until the
+  /// filter is finalized. Keys are compared by their 32-bit hash, so two keys whose hashes
+  /// collide count as one.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::Filter;
+  ///
+  /// let mut f = Filter::<512>::with_bits_per_key(10).with_distinct_tracking();
+  /// assert!(f.insert_checked(b"a"));
+  /// assert!(f.insert_checked(b"b"));
+  /// assert!(!f.insert_checked(b"a"));
+  /// ```
+  #[cfg(feature = "std")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+  #[inline]
+  pub fn with_distinct_tracking(mut self) -> Self {
+    self.seen.get_or_insert_with(Default::default);
+    self
+  }
+
+  /// Returns `true` if the filter is finalized as an exact set.
+  #[inline]
+  const fn is_exact(&self) -> bool {
+    self.num_hashes != 0 && self.num_hashes <= self.exact_threshold
+  }
 }
 
 impl<const N: usize, S> Filter<N, S>
@@ -139,11 +294,36 @@ where
 {
   /// Adds a key to the filter.
   pub fn insert(&mut self, key: &[u8]) {
+    self.insert_checked(key);
+  }
+
+  /// Adds a key to the filter, and returns `true` if the key was not present before.
+  ///
+  /// With [`with_distinct_tracking`](Filter::with_distinct_tracking), every previously inserted
+  /// key is detected, so counting the `true` returns counts the distinct keys. Otherwise, only a
+  /// key equal to the previously inserted one is detected, like [`insert`](Filter::insert) does.
+  pub fn insert_checked(&mut self, key: &[u8]) -> bool {
     let h = self.hasher.hash_one(key);
+
+    #[cfg(feature = "std")]
+    if let Some(seen) = &mut self.seen {
+      if !seen.insert(h) {
+        return false;
+      }
+
+      self.push_hash(h);
+      return true;
+    }
+
     if self.num_hashes != 0 && h == self.last_hash {
-      return;
+      return false;
     }
 
+    self.push_hash(h);
+    true
+  }
+
+  fn push_hash(&mut self, h: u32) {
     let ofs = self.num_hashes % N;
     if ofs == 0 {
       // Time for a new block
@@ -162,9 +342,18 @@ where
   /// Returns the length of the final filter.
   #[inline]
   pub const fn filter_length(&self) -> usize {
-    let n_lines = self.n_lines();
     // +5: 4 bytes for n_lines and 1 byte for n_probes
-    n_lines * CACHE_LINE_SIZE + 5
+    self.body_length() + 5
+  }
+
+  /// Returns the length of the filter without the footer.
+  #[inline]
+  const fn body_length(&self) -> usize {
+    if self.is_exact() {
+      self.num_hashes * 4
+    } else {
+      self.n_lines() * CACHE_LINE_SIZE
+    }
   }
 
   const fn n_lines(&self) -> usize {
@@ -202,52 +391,149 @@ where
   /// let written = f.finalize_to(&mut buf).unwrap();
   /// ```
   pub fn finalize_to(self, buf: &mut [u8]) -> Result<usize, usize> {
-    let n_lines = self.n_lines();
-    let n_bytes = n_lines * CACHE_LINE_SIZE;
+    let n_bytes = self.body_length();
     let written = n_bytes + 5;
     if buf.len() < written {
       return Err(written);
     }
 
-    self.finalize_in(n_lines, n_bytes, buf);
+    self.finalize_in(n_bytes, buf);
     Ok(written)
   }
 
   /// Finalizes the filter.
   pub fn finalize(self) -> std::vec::Vec<u8> {
-    let n_lines = self.n_lines();
-    let n_bytes = n_lines * CACHE_LINE_SIZE;
+    let n_bytes = self.body_length();
     // +5: 4 bytes for n_lines and 1 byte for n_probes
     let mut filter = std::vec![0; n_bytes + 5];
-    self.finalize_in(n_lines, n_bytes, &mut filter);
+    self.finalize_in(n_bytes, &mut filter);
     filter
   }
 
-  fn finalize_in(mut self, n_lines: usize, n_bytes: usize, filter: &mut [u8]) {
-    if n_lines != 0 {
-      let n_probes = calculate_probes(self.bits_per_key);
-      let num_blocks = self.blocks.len();
-      for (bidx, b) in self.blocks.iter_mut().enumerate() {
-        let mut length = N;
-        if bidx == num_blocks - 1 && self.num_hashes % N != 0 {
-          length = self.num_hashes % N;
-        }
+  /// Finalizes the filter, appending a 1-byte [`HasherTag`](crate::HasherTag) after the footer so that
+  /// [`FrozenFilter::open_tagged`](crate::FrozenFilter::open_tagged) can select the
+  /// matching hasher.
+  ///
+  /// The untagged format produced by [`finalize`](Filter::finalize) is unaffected.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, FrozenFilter};
+  ///
+  /// let mut f = Filter::<512>::with_bits_per_key(10);
+  /// f.insert(b"hello");
+  ///
+  /// let b = f.finalize_tagged();
+  /// let frozen = FrozenFilter::open_tagged(b.as_slice()).unwrap();
+  /// assert!(frozen.may_contain(b"hello"));
+  /// ```
+  pub fn finalize_tagged(self) -> std::vec::Vec<u8>
+  where
+    S: TaggedHasher,
+  {
+    let tag = self.hasher.tag();
+    let n_bytes = self.body_length();
+    // +6: 4 bytes for n_lines, 1 byte for n_probes and 1 byte for the hasher tag
+    let mut filter = std::vec![0; n_bytes + 6];
+    self.finalize_in(n_bytes, &mut filter);
+    filter[n_bytes + 5] = tag.as_u8();
+    filter
+  }
 
-        for h in &mut b[..length] {
-          let delta = h.rotate_left(15); // rotate right 17 bits
-          let b = (*h % n_lines as u32) * CACHE_LINE_BITS as u32;
+  /// Finalizes the filter into its body and the two footer fields, `(body, n_lines, n_probes)`.
+  ///
+  /// The body is the bit array without the 5-byte footer, so it can be embedded in a larger
+  /// block format, with the geometry stored elsewhere. For an exact set (see
+  /// [`with_exact_threshold`](Filter::with_exact_threshold)), `n_probes` is `0` and `n_lines`
+  /// is the number of hashes. Use
+  /// [`FrozenFilter::from_parts`](crate::FrozenFilter::from_parts) to query it.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, FrozenFilter, hasher::SimMurmur};
+  ///
+  /// let mut f = Filter::<512>::with_bits_per_key(10);
+  /// f.insert(b"hello");
+  ///
+  /// let (body, n_lines, n_probes) = f.finalize_parts();
+  /// let frozen = FrozenFilter::from_parts(body, n_lines, n_probes, SimMurmur::new());
+  /// assert!(frozen.may_contain(b"hello"));
+  /// ```
+  pub fn finalize_parts(mut self) -> (std::vec::Vec<u8>, u32, u8) {
+    let mut body = std::vec![0; self.body_length()];
+    let (n_lines, n_probes) = self.fill_body(&mut body);
+    (body, n_lines, n_probes)
+  }
 
-          for _ in 0..n_probes {
-            let bit_pos = b + (*h % CACHE_LINE_BITS as u32);
-            filter[(bit_pos / 8) as usize] |= 1 << (bit_pos % 8);
-            *h = h.wrapping_add(delta);
-          }
-        }
+  fn finalize_in(mut self, n_bytes: usize, filter: &mut [u8]) {
+    if self.num_hashes != 0 {
+      let (n_lines, n_probes) = self.fill_body(&mut filter[..n_bytes]);
+      filter[n_bytes] = n_probes;
+      filter[n_bytes + 1..n_bytes + 5].copy_from_slice(n_lines.to_le_bytes().as_slice());
+    }
+  }
+
+  /// Writes the body, returns the footer fields `(n_lines, n_probes)`.
+  ///
+  /// An exact set is tagged by `0` probes, with the number of hashes in place of `n_lines`.
+  /// A reader unaware of exact sets then probes nothing and reports every key as present,
+  /// which is still correct for a filter.
+  fn fill_body(&mut self, body: &mut [u8]) -> (u32, u8) {
+    if !self.is_exact() {
+      let n_lines = self.n_lines();
+      return (n_lines as u32, self.fill_bits(n_lines, body));
+    }
+
+    let mut hashes = self.hashes().collect::<Vec<_>>();
+    hashes.sort_unstable();
+    for (dst, h) in body.chunks_exact_mut(4).zip(&hashes) {
+      dst.copy_from_slice(&h.to_le_bytes());
+    }
+    (hashes.len() as u32, 0)
+  }
+
+  /// Returns the hashes of the inserted keys.
+  fn hashes(&self) -> impl Iterator<Item = u32> + '_ {
+    let num_blocks = self.blocks.len();
+    self.blocks.iter().enumerate().flat_map(move |(bidx, b)| {
+      let mut length = N;
+      if bidx == num_blocks - 1 && self.num_hashes % N != 0 {
+        length = self.num_hashes % N;
       }
+      b[..length].iter().copied()
+    })
+  }
 
-      filter[n_bytes] = n_probes as u8;
-      filter[n_bytes + 1..n_bytes + 5].copy_from_slice((n_lines as u32).to_le_bytes().as_slice());
+  /// Sets the bits of all inserted keys, returns the number of probes, or `0` if the
+  /// filter is empty.
+  fn fill_bits(&mut self, n_lines: usize, filter: &mut [u8]) -> u8 {
+    if n_lines == 0 {
+      return 0;
     }
+
+    let n_probes = calculate_probes(self.bits_per_key);
+    let num_blocks = self.blocks.len();
+    for (bidx, b) in self.blocks.iter_mut().enumerate() {
+      let mut length = N;
+      if bidx == num_blocks - 1 && self.num_hashes % N != 0 {
+        length = self.num_hashes % N;
+      }
+
+      for h in &mut b[..length] {
+        let delta = h.rotate_left(15); // rotate right 17 bits
+        let b = (*h % n_lines as u32) * CACHE_LINE_BITS as u32;
+
+        for _ in 0..n_probes {
+          let bit_pos = b + (*h % CACHE_LINE_BITS as u32);
+          filter[(bit_pos / 8) as usize] |= 1 << (bit_pos % 8);
+          *h = h.wrapping_add(delta);
+        }
+      }
+    }
+
+    n_probes as u8
   }
 }
 
@@ -259,7 +545,7 @@ mod tests {
   use crate::hasher::Xxh32;
 
   use super::*;
-  use crate::FrozenFilter;
+  use crate::{FrozenFilter, HasherMismatch, HasherTag};
 
   fn new_filter<'a, S: BloomHasher + Default>(
     bits_per_key: usize,
@@ -471,4 +757,235 @@ mod tests {
   fn bloom_filter_xxh3() {
     bloom_filter_in::<Xxh3>();
   }
+
+  #[test]
+  fn tagged_round_trip() {
+    let mut f = Filter::<512>::with_bits_per_key(10);
+    f.insert(b"hello");
+    f.insert(b"world");
+    let untagged_len = f.filter_length();
+
+    let b = f.finalize_tagged();
+    assert_eq!(b.len(), untagged_len + 1);
+    assert_eq!(*b.last().unwrap(), HasherTag::SimMurmur.as_u8());
+
+    let frozen = FrozenFilter::open_tagged(b.as_slice()).unwrap();
+    assert_eq!(frozen.hasher().tag(), HasherTag::SimMurmur);
+    assert!(frozen.may_contain(b"hello"));
+    assert!(frozen.may_contain(b"world"));
+    assert!(!frozen.may_contain(b"x"));
+
+    let frozen = FrozenFilter::open_tagged_with_hasher(b.as_slice(), SimMurmur::new()).unwrap();
+    assert!(frozen.may_contain(b"hello"));
+
+    assert_eq!(
+      FrozenFilter::open_tagged(<&[u8]>::default()).unwrap_err(),
+      HasherMismatch::Missing
+    );
+    assert_eq!(
+      FrozenFilter::open_tagged([0u8, 0, 0, 0, 0, 0xff].as_slice()).unwrap_err(),
+      HasherMismatch::Unknown(0xff)
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "xxhash3")]
+  fn tagged_hasher_mismatch() {
+    let mut f = Filter::<512>::with_bits_per_key(10);
+    f.insert(b"hello");
+    let b = f.finalize_tagged();
+
+    let err = FrozenFilter::open_tagged_with_hasher(b.as_slice(), Xxh3::new()).unwrap_err();
+    assert_eq!(
+      err,
+      HasherMismatch::Mismatch {
+        expected: HasherTag::Xxh3,
+        found: HasherTag::SimMurmur,
+      }
+    );
+  }
+
+  #[test]
+  fn parts_round_trip() {
+    let mut f = Filter::<512>::with_bits_per_key(10);
+    for i in 0..1000u32 {
+      f.insert(&i.to_le_bytes());
+    }
+    let whole = f.clone().finalize();
+    let (body, n_lines, n_probes) = f.finalize_parts();
+
+    // the body and the footer fields recombine into the finalized filter.
+    assert_eq!(body.len(), whole.len() - 5);
+    assert_eq!(body.as_slice(), &whole[..body.len()]);
+    assert_eq!(n_probes, whole[body.len()]);
+    assert_eq!(n_lines.to_le_bytes(), whole[body.len() + 1..]);
+
+    let frozen = FrozenFilter::from_parts(body.as_slice(), n_lines, n_probes, SimMurmur::new());
+    let whole = FrozenFilter::new(whole.as_slice());
+    for i in 0..1000u32 {
+      assert!(frozen.may_contain(&i.to_le_bytes()));
+    }
+    for i in 1_000_000..1_010_000u32 {
+      let key = i.to_le_bytes();
+      assert_eq!(frozen.may_contain(&key), whole.may_contain(&key));
+    }
+  }
+
+  #[test]
+  fn empty_parts() {
+    let (body, n_lines, n_probes) = Filter::<512>::with_bits_per_key(10).finalize_parts();
+    assert!(body.is_empty());
+    assert_eq!((n_lines, n_probes), (0, 0));
+
+    let frozen = FrozenFilter::from_parts(body, n_lines, n_probes, SimMurmur::new());
+    assert!(!frozen.may_contain(b"hello"));
+  }
+
+  #[test]
+  fn budget() {
+    let (mut f, bpk) = Filter::<512>::with_budget(1000, 1000);
+    assert_eq!(bpk, 7);
+    for i in 0..1000u32 {
+      f.insert(&i.to_le_bytes());
+    }
+    assert!(f.filter_length() <= 1000);
+
+    // one more bit per key does not fit.
+    let mut larger = Filter::<512>::with_bits_per_key(bpk + 1);
+    for i in 0..1000u32 {
+      larger.insert(&i.to_le_bytes());
+    }
+    assert!(larger.filter_length() > 1000);
+
+    let frozen = FrozenFilter::new(f.finalize());
+    for i in 0..1000u32 {
+      assert!(frozen.may_contain(&i.to_le_bytes()));
+    }
+
+    // a budget too tight for a single bit per key is clamped to 1.
+    let (_, bpk) = Filter::<512>::with_budget(1_000_000, 100);
+    assert_eq!(bpk, 1);
+    let (_, bpk) = Filter::<512>::with_budget(0, 0);
+    assert_eq!(bpk, 1);
+  }
+
+  #[test]
+  fn batch_queries() {
+    let mut f = Filter::<512>::with_bits_per_key(10);
+    for i in 0..100u32 {
+      f.insert(&i.to_le_bytes());
+    }
+    let frozen = FrozenFilter::new(f.finalize());
+
+    let present = [3u32, 50, 99].map(u32::to_le_bytes);
+    let absent = [2_000u32, 3_000, 4_000].map(u32::to_le_bytes);
+    let keys: [&[u8]; 6] = [
+      &absent[0],
+      &present[0],
+      &absent[1],
+      &present[1],
+      &present[2],
+      &absent[2],
+    ];
+
+    assert!(frozen.contains_any(&keys));
+    assert!(!frozen.contains_all(&keys));
+    assert!(frozen.contains_all(&keys[3..5]));
+    assert!(!frozen.contains_any(&[&absent[0], &absent[1], &absent[2]]));
+    assert!(!frozen.contains_any(&[]));
+    assert!(frozen.contains_all(&[]));
+
+    assert_eq!(frozen.find_possible(&keys).collect::<Vec<_>>(), [1, 3, 4]);
+  }
+
+  #[test]
+  fn exact_set() {
+    let keys: [&[u8]; 3] = [b"alpha", b"beta", b"gamma"];
+    let mut f = Filter::<512>::with_bits_per_key(10).with_exact_threshold(8);
+    for key in keys {
+      f.insert(key);
+    }
+    assert_eq!(f.filter_length(), 3 * 4 + 5);
+
+    let whole = f.clone().finalize();
+    assert_eq!(whole.len(), f.filter_length());
+    // exact sets are tagged by zero probes.
+    assert_eq!(whole[12], 0);
+
+    let frozen = FrozenFilter::new(whole.as_slice());
+    let (body, n_lines, n_probes) = f.finalize_parts();
+    assert_eq!((n_lines, n_probes), (3, 0));
+    let parts = FrozenFilter::from_parts(body.as_slice(), n_lines, n_probes, SimMurmur::new());
+
+    for key in keys {
+      assert!(frozen.may_contain(key));
+      assert!(parts.may_contain(key));
+    }
+    for i in 0..10_000u32 {
+      let key = i.to_le_bytes();
+      assert!(!frozen.may_contain(&key));
+      assert!(!parts.may_contain(&key));
+    }
+
+    // above the threshold, the bit array is used.
+    let mut f = Filter::<512>::with_bits_per_key(10).with_exact_threshold(2);
+    for key in keys {
+      f.insert(key);
+    }
+    assert_eq!(f.filter_length(), CACHE_LINE_SIZE + 5);
+    let frozen = FrozenFilter::new(f.finalize());
+    for key in keys {
+      assert!(frozen.may_contain(key));
+    }
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn insert_checked_counts_distinct_keys() {
+    let mut f = Filter::<64>::with_bits_per_key(10).with_distinct_tracking();
+    let mut distinct = 0;
+    for i in 0..1000u32 {
+      // every key is inserted at least 3 times, non-consecutively.
+      if f.insert_checked(&(i % 300).to_le_bytes()) {
+        distinct += 1;
+      }
+    }
+    assert_eq!(distinct, 300);
+
+    let frozen = FrozenFilter::new(f.finalize());
+    for i in 0..300u32 {
+      assert!(frozen.may_contain(&i.to_le_bytes()));
+    }
+
+    // without tracking, only consecutive duplicates are detected.
+    let mut f = Filter::<64>::with_bits_per_key(10);
+    assert!(f.insert_checked(b"a"));
+    assert!(!f.insert_checked(b"a"));
+    assert!(f.insert_checked(b"b"));
+    assert!(f.insert_checked(b"a"));
+  }
+
+  #[test]
+  fn all_may_contain_short_circuits() {
+    let mut f = Filter::<512>::with_bits_per_key(10);
+    for i in 0..100u32 {
+      f.insert(&i.to_le_bytes());
+    }
+    let frozen = FrozenFilter::new(f.finalize());
+
+    let present = [3u32, 50, 99].map(u32::to_le_bytes);
+    let absent = 3_000u32.to_le_bytes();
+    let keys: [&[u8]; 5] = [&present[0], &present[1], &absent, &present[2], &present[0]];
+
+    let mut pulled = 0;
+    let all = frozen.all_may_contain(keys.iter().map(|key| {
+      pulled += 1;
+      *key
+    }));
+    assert!(!all);
+    assert_eq!(pulled, 3);
+
+    assert!(frozen.all_may_contain(present.iter().map(|key| key.as_slice())));
+    assert!(frozen.all_may_contain(core::iter::empty()));
+  }
 }
@@ -1,10 +1,16 @@
-use super::{hasher::SimMurmur, BloomHasher};
+use super::{
+  hasher::{HasherTag, KnownHasher, SimMurmur, TaggedHasher},
+  BloomHasher,
+};
 
 /// A frozen filter.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct FrozenFilter<A, S = SimMurmur> {
   src: A,
   hasher: S,
+  tagged: bool,
+  // `(n_lines, n_probes)` when the footer is stored out of band, see `from_parts`.
+  footer: Option<(u32, u8)>,
 }
 
 impl<A> From<A> for FrozenFilter<A> {
@@ -13,6 +19,8 @@ impl<A> From<A> for FrozenFilter<A> {
     Self {
       src: a,
       hasher: SimMurmur::new(),
+      tagged: false,
+      footer: None,
     }
   }
 }
@@ -49,6 +57,8 @@ impl<A> FrozenFilter<A> {
     Self {
       src: a,
       hasher: SimMurmur::new(),
+      tagged: false,
+      footer: None,
     }
   }
 }
@@ -76,26 +86,196 @@ impl<A, S> FrozenFilter<A, S> {
   /// ```
   #[inline]
   pub const fn with_hasher(a: A, hasher: S) -> Self {
-    Self { src: a, hasher }
+    Self {
+      src: a,
+      hasher,
+      tagged: false,
+      footer: None,
+    }
+  }
+}
+
+impl<A, S> FrozenFilter<A, S> {
+  /// Creates a frozen filter from a body and the footer fields returned by
+  /// [`Filter::finalize_parts`](crate::Filter::finalize_parts).
+  ///
+  /// The body is the bit array without the footer, the geometry is supplied separately, so
+  /// the bits and the geometry can be stored in different places.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, FrozenFilter, hasher::SimMurmur};
+  ///
+  /// let mut filter = Filter::<512>::new(10_000, 0.01);
+  /// filter.insert(b"hello");
+  ///
+  /// let (body, n_lines, n_probes) = filter.finalize_parts();
+  /// let frozen = FrozenFilter::from_parts(body.as_slice(), n_lines, n_probes, SimMurmur::new());
+  /// assert!(frozen.may_contain(b"hello"));
+  /// assert!(!frozen.may_contain(b"foo"));
+  /// ```
+  #[inline]
+  pub const fn from_parts(body: A, n_lines: u32, n_probes: u8, hasher: S) -> Self {
+    Self {
+      src: body,
+      hasher,
+      tagged: false,
+      footer: Some((n_lines, n_probes)),
+    }
+  }
+}
+
+impl<A: AsRef<[u8]>> FrozenFilter<A, KnownHasher> {
+  /// Opens a filter produced by [`Filter::finalize_tagged`](crate::Filter::finalize_tagged),
+  /// selecting the hasher recorded in its footer.
+  ///
+  /// The selected hasher uses its default seed.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, FrozenFilter, HasherTag, TaggedHasher};
+  ///
+  /// let mut filter = Filter::<512>::new(10_000, 0.01);
+  /// filter.insert(b"hello");
+  ///
+  /// let frozen = FrozenFilter::open_tagged(filter.finalize_tagged()).unwrap();
+  /// assert_eq!(frozen.hasher().tag(), HasherTag::SimMurmur);
+  /// assert!(frozen.may_contain(b"hello"));
+  /// ```
+  pub fn open_tagged(a: A) -> Result<Self, HasherMismatch> {
+    let tag = read_tag(a.as_ref())?;
+    match KnownHasher::from_tag(tag) {
+      Some(hasher) => Ok(Self {
+        src: a,
+        hasher,
+        tagged: true,
+        footer: None,
+      }),
+      None => Err(HasherMismatch::Unsupported(tag)),
+    }
+  }
+}
+
+impl<A: AsRef<[u8]>, S: TaggedHasher> FrozenFilter<A, S> {
+  /// Opens a filter produced by [`Filter::finalize_tagged`](crate::Filter::finalize_tagged)
+  /// with the given hasher, returning an error if the filter was built with a different one.
+  pub fn open_tagged_with_hasher(a: A, hasher: S) -> Result<Self, HasherMismatch> {
+    let found = read_tag(a.as_ref())?;
+    let expected = hasher.tag();
+    if found != expected {
+      return Err(HasherMismatch::Mismatch { expected, found });
+    }
+
+    Ok(Self {
+      src: a,
+      hasher,
+      tagged: true,
+      footer: None,
+    })
+  }
+}
+
+impl<A, S> FrozenFilter<A, S> {
+  /// Returns the hasher used by this filter.
+  #[inline]
+  pub const fn hasher(&self) -> &S {
+    &self.hasher
   }
 }
 
+/// Binary searches the first `len` little-endian `u32` hashes of `set` for `h`.
+#[inline]
+fn contains_hash(set: &[u8], len: usize, h: u32) -> bool {
+  let (mut lo, mut hi) = (0, len);
+  while lo < hi {
+    let mid = lo + (hi - lo) / 2;
+    let idx = mid * 4;
+    let curr = u32::from_le_bytes([set[idx], set[idx + 1], set[idx + 2], set[idx + 3]]);
+    match curr.cmp(&h) {
+      core::cmp::Ordering::Less => lo = mid + 1,
+      core::cmp::Ordering::Greater => hi = mid,
+      core::cmp::Ordering::Equal => return true,
+    }
+  }
+  false
+}
+
+#[inline]
+fn read_tag(src: &[u8]) -> Result<HasherTag, HasherMismatch> {
+  match src.last() {
+    None => Err(HasherMismatch::Missing),
+    Some(&tag) => HasherTag::from_u8(tag).ok_or(HasherMismatch::Unknown(tag)),
+  }
+}
+
+/// An error returned when opening a tagged filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HasherMismatch {
+  /// The buffer is empty, so there is no hasher tag to read.
+  Missing,
+  /// The tag byte does not name a known hasher.
+  Unknown(u8),
+  /// The tag names a hasher whose feature is not enabled.
+  Unsupported(HasherTag),
+  /// The filter was built with a different hasher than the one requested.
+  Mismatch {
+    /// The tag of the requested hasher.
+    expected: HasherTag,
+    /// The tag recorded in the filter.
+    found: HasherTag,
+  },
+}
+
+impl core::fmt::Display for HasherMismatch {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Missing => write!(f, "missing hasher tag"),
+      Self::Unknown(tag) => write!(f, "unknown hasher tag: {tag}"),
+      Self::Unsupported(tag) => write!(f, "hasher {tag} is not enabled"),
+      Self::Mismatch { expected, found } => {
+        write!(f, "hasher mismatch: expected {expected}, found {found}")
+      }
+    }
+  }
+}
+
+impl core::error::Error for HasherMismatch {}
+
 impl<A: AsRef<[u8]>, S: BloomHasher> FrozenFilter<A, S> {
   /// Returns `true` if the filter may contain the key.
   #[inline]
   pub fn may_contain(&self, key: &[u8]) -> bool {
     let filter = self.src.as_ref();
-    let len = filter.len();
-    if len <= 5 {
-      return false;
-    }
+    let (n, n_lines, n_probes) = match self.footer {
+      Some((n_lines, n_probes)) => {
+        if filter.is_empty() || n_lines == 0 {
+          return false;
+        }
+        (filter.len(), n_lines, n_probes)
+      }
+      None => {
+        let len = filter.len() - self.tagged as usize;
+        if len <= 5 {
+          return false;
+        }
 
-    let n = len - 5;
-    let n_probes = filter[n];
-    let n_lines = u32::from_le_bytes([filter[n + 1], filter[n + 2], filter[n + 3], filter[n + 4]]);
-    let cache_line_bits = 8 * ((n as u32) / n_lines);
+        let n = len - 5;
+        let n_probes = filter[n];
+        let n_lines =
+          u32::from_le_bytes([filter[n + 1], filter[n + 2], filter[n + 3], filter[n + 4]]);
+        (n, n_lines, n_probes)
+      }
+    };
 
     let mut h = self.hasher.hash_one(key);
+    if n_probes == 0 {
+      // an exact set of `n_lines` sorted hashes, see `Filter::with_exact_threshold`.
+      return contains_hash(filter, (n_lines as usize).min(n / 4), h);
+    }
+
+    let cache_line_bits = 8 * ((n as u32) / n_lines);
     let delta = h.rotate_left(15);
     let b = (h % n_lines) * cache_line_bits;
 
@@ -111,4 +291,55 @@ impl<A: AsRef<[u8]>, S: BloomHasher> FrozenFilter<A, S> {
 
     true
   }
+
+  /// Returns `true` if the filter may contain any of the keys.
+  ///
+  /// Stops at the first key which may be present.
+  #[inline]
+  pub fn contains_any(&self, keys: &[&[u8]]) -> bool {
+    keys.iter().any(|key| self.may_contain(key))
+  }
+
+  /// Returns `true` if the filter may contain all of the keys.
+  ///
+  /// Stops at the first key which is definitely absent.
+  #[inline]
+  pub fn contains_all(&self, keys: &[&[u8]]) -> bool {
+    keys.iter().all(|key| self.may_contain(key))
+  }
+
+  /// Works like [`contains_all`](FrozenFilter::contains_all), but takes the keys lazily.
+  ///
+  /// Keys are pulled from the iterator one at a time, and no key after the first definitely
+  /// absent one is pulled, so this can short-circuit an expensive key source.
+  #[inline]
+  pub fn all_may_contain<'a>(&self, keys: impl IntoIterator<Item = &'a [u8]>) -> bool {
+    keys.into_iter().all(|key| self.may_contain(key))
+  }
+
+  /// Returns the indices of the keys which may be present in the filter.
+  ///
+  /// Useful for pruning a batch of point lookups before doing the expensive reads.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use bloomur::{Filter, FrozenFilter};
+  ///
+  /// let mut filter = Filter::<512>::new(10_000, 0.01);
+  /// filter.insert(b"hello");
+  /// filter.insert(b"world");
+  ///
+  /// let frozen = FrozenFilter::new(filter.finalize());
+  /// let keys: [&[u8]; 3] = [b"foo", b"hello", b"world"];
+  /// let found = frozen.find_possible(&keys).collect::<Vec<_>>();
+  /// assert_eq!(found, [1, 2]);
+  /// ```
+  #[inline]
+  pub fn find_possible<'a>(&'a self, keys: &'a [&'a [u8]]) -> impl Iterator<Item = usize> + 'a {
+    keys
+      .iter()
+      .enumerate()
+      .filter_map(|(idx, key)| self.may_contain(key).then_some(idx))
+  }
 }
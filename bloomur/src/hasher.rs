@@ -22,3 +22,141 @@ pub trait BloomHasher {
   /// Hashes the key and returns the hash value.
   fn hash_one(&self, src: &[u8]) -> u32;
 }
+
+/// The identity of a [`BloomHasher`] recorded in the footer of a tagged filter.
+///
+/// Tags are stable across releases and are defined regardless of which hasher
+/// features are enabled, so a filter written by one build can always be identified
+/// by another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum HasherTag {
+  /// [`SimMurmur`] hasher.
+  SimMurmur = 0,
+  /// `Xxh32` hasher.
+  Xxh32 = 1,
+  /// `Xxh3` hasher.
+  Xxh3 = 2,
+}
+
+impl HasherTag {
+  /// Returns the tag byte.
+  #[inline]
+  pub const fn as_u8(&self) -> u8 {
+    *self as u8
+  }
+
+  /// Returns the tag for the given byte, or `None` if the byte is not a known tag.
+  #[inline]
+  pub const fn from_u8(tag: u8) -> Option<Self> {
+    Some(match tag {
+      0 => Self::SimMurmur,
+      1 => Self::Xxh32,
+      2 => Self::Xxh3,
+      _ => return None,
+    })
+  }
+}
+
+impl core::fmt::Display for HasherTag {
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::SimMurmur => write!(f, "sim-murmur"),
+      Self::Xxh32 => write!(f, "xxh32"),
+      Self::Xxh3 => write!(f, "xxh3"),
+    }
+  }
+}
+
+/// A [`BloomHasher`] whose identity can be recorded in a tagged filter.
+///
+/// Only the algorithm is recorded, not the seed. A filter built with a custom seed
+/// must be opened with the same hasher instance.
+pub trait TaggedHasher: BloomHasher {
+  /// Returns the tag of this hasher.
+  fn tag(&self) -> HasherTag;
+}
+
+impl TaggedHasher for SimMurmur {
+  #[inline]
+  fn tag(&self) -> HasherTag {
+    HasherTag::SimMurmur
+  }
+}
+
+#[cfg(feature = "xxhash32")]
+impl TaggedHasher for Xxh32 {
+  #[inline]
+  fn tag(&self) -> HasherTag {
+    HasherTag::Xxh32
+  }
+}
+
+#[cfg(feature = "xxhash3")]
+impl TaggedHasher for Xxh3 {
+  #[inline]
+  fn tag(&self) -> HasherTag {
+    HasherTag::Xxh3
+  }
+}
+
+/// One of the hashers known to this crate, selected at runtime from a [`HasherTag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KnownHasher {
+  /// [`SimMurmur`] hasher.
+  SimMurmur(SimMurmur),
+  /// [`Xxh32`] hasher.
+  #[cfg(feature = "xxhash32")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "xxhash32")))]
+  Xxh32(Xxh32),
+  /// [`Xxh3`] hasher.
+  #[cfg(feature = "xxhash3")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "xxhash3")))]
+  Xxh3(Xxh3),
+}
+
+impl KnownHasher {
+  /// Returns the hasher for the given tag with its default seed, or `None` if the
+  /// hasher's feature is not enabled.
+  #[inline]
+  pub const fn from_tag(tag: HasherTag) -> Option<Self> {
+    match tag {
+      HasherTag::SimMurmur => Some(Self::SimMurmur(SimMurmur::new())),
+      #[cfg(feature = "xxhash32")]
+      HasherTag::Xxh32 => Some(Self::Xxh32(Xxh32::new())),
+      #[cfg(feature = "xxhash3")]
+      HasherTag::Xxh3 => Some(Self::Xxh3(Xxh3::new())),
+      #[allow(unreachable_patterns)]
+      _ => None,
+    }
+  }
+}
+
+impl BloomHasher for KnownHasher {
+  #[inline]
+  fn hash_one(&self, src: &[u8]) -> u32 {
+    match self {
+      Self::SimMurmur(h) => h.hash_one(src),
+      #[cfg(feature = "xxhash32")]
+      Self::Xxh32(h) => h.hash_one(src),
+      #[cfg(feature = "xxhash3")]
+      Self::Xxh3(h) => h.hash_one(src),
+    }
+  }
+}
+
+impl TaggedHasher for KnownHasher {
+  #[inline]
+  fn tag(&self) -> HasherTag {
+    match self {
+      Self::SimMurmur(_) => HasherTag::SimMurmur,
+      #[cfg(feature = "xxhash32")]
+      Self::Xxh32(_) => HasherTag::Xxh32,
+      #[cfg(feature = "xxhash3")]
+      Self::Xxh3(_) => HasherTag::Xxh3,
+    }
+  }
+}
@@ -20,13 +20,20 @@ mod filter;
 #[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
 pub use filter::{bits_per_key, Filter};
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+mod builder;
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+pub use builder::FilterBuilder;
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
 mod frozen_filter;
 #[cfg(any(feature = "std", feature = "alloc"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
-pub use frozen_filter::FrozenFilter;
+pub use frozen_filter::{FrozenFilter, HasherMismatch};
 
 /// Hashers for bloomfilter.
 pub mod hasher;
-pub use hasher::BloomHasher;
+pub use hasher::{BloomHasher, HasherTag, KnownHasher, TaggedHasher};
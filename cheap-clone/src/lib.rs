@@ -34,10 +34,13 @@ macro_rules! impl_cheap_clone_for_copy {
 /// Eg:
 /// - ✔ [`Arc<T>`](std::sync::Arc)
 /// - ✔ [`Rc<T>`](std::rc::Rc)
+/// - ✔ [`Weak<T>`](std::sync::Weak)
 /// - ✔ [`Bytes`](bytes1::Bytes)
 /// - ✗ [`Vec<T>`](std::vec::Vec)
 /// - ✔ [`SmolStr`](smol_str03::SmolStr)
 /// - ✔ [`FastStr`](faststr02::FastStr)
+/// - ✔ [`KString`](kstring2::KString)
+/// - ✔ [`EcoString`](ecow02::EcoString)
 /// - ✗ [`String`]
 pub trait CheapClone: Clone {
   /// Returns a copy of the value.
@@ -62,6 +65,14 @@ impl CheapClone for smol_str02::SmolStr {}
 #[cfg_attr(docsrs, doc(cfg(feature = "faststr02")))]
 impl CheapClone for faststr02::FastStr {}
 
+#[cfg(feature = "kstring2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kstring2")))]
+impl CheapClone for kstring2::KString {}
+
+#[cfg(feature = "ecow02")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ecow02")))]
+impl CheapClone for ecow02::EcoString {}
+
 #[cfg(feature = "triomphe01")]
 #[cfg_attr(docsrs, doc(cfg(feature = "triomphe01")))]
 impl<T> CheapClone for triomphe01::Arc<T> {}
@@ -72,6 +83,8 @@ mod a {
 
   impl<T: ?Sized> CheapClone for std::rc::Rc<T> {}
   impl<T: ?Sized> CheapClone for std::sync::Arc<T> {}
+  impl<T: ?Sized> CheapClone for std::rc::Weak<T> {}
+  impl<T: ?Sized> CheapClone for std::sync::Weak<T> {}
 }
 
 #[cfg(feature = "std")]
@@ -111,6 +124,48 @@ impl<T: CheapClone, E: CheapClone> CheapClone for Result<T, E> {
     }
   }
 }
+impl<T: CheapClone> CheapClone for core::ops::Bound<T> {
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    match self {
+      core::ops::Bound::Included(b) => core::ops::Bound::Included(b.cheap_clone()),
+      core::ops::Bound::Excluded(b) => core::ops::Bound::Excluded(b.cheap_clone()),
+      core::ops::Bound::Unbounded => core::ops::Bound::Unbounded,
+    }
+  }
+}
+impl<T: CheapClone> CheapClone for core::ops::Range<T> {
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    self.start.cheap_clone()..self.end.cheap_clone()
+  }
+}
+impl<T: CheapClone> CheapClone for core::ops::RangeFrom<T> {
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    self.start.cheap_clone()..
+  }
+}
+impl<T: CheapClone> CheapClone for core::ops::RangeTo<T> {
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    ..self.end.cheap_clone()
+  }
+}
+// `RangeInclusive` keeps an internal exhausted flag, so use `Clone` to preserve it.
+impl<T: CheapClone> CheapClone for core::ops::RangeInclusive<T> {}
+impl<T: CheapClone> CheapClone for core::ops::RangeToInclusive<T> {
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    ..=self.end.cheap_clone()
+  }
+}
+impl CheapClone for core::ops::RangeFull {
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    ..
+  }
+}
 #[cfg(feature = "either")]
 impl<L: CheapClone, R: CheapClone> CheapClone for either::Either<L, R> {
   #[inline]
@@ -207,3 +262,69 @@ impl_cheap_clone_for_tuple!(
 impl_cheap_clone_for_tuple!(
   0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23
 );
+
+#[cfg(test)]
+mod tests {
+  use super::CheapClone;
+  use core::ops::Bound;
+  use std::sync::Arc;
+
+  #[test]
+  fn bound() {
+    let bound = Bound::Included(Arc::<[u8]>::from(&b"x"[..]));
+    let cloned = bound.cheap_clone();
+    match (&bound, &cloned) {
+      (Bound::Included(a), Bound::Included(b)) => assert!(Arc::ptr_eq(a, b)),
+      _ => panic!("unexpected bound"),
+    }
+    assert_eq!(Bound::<u64>::Unbounded.cheap_clone(), Bound::Unbounded);
+  }
+
+  #[test]
+  fn ranges() {
+    let range = 1u64..10;
+    assert_eq!(range.cheap_clone(), range);
+    assert_eq!((1u64..).cheap_clone(), 1u64..);
+    assert_eq!((..10u64).cheap_clone(), ..10u64);
+    assert_eq!((1u64..=10).cheap_clone(), 1u64..=10);
+    assert_eq!((..=10u64).cheap_clone(), ..=10u64);
+    assert_eq!((..).cheap_clone(), ..);
+  }
+
+  #[test]
+  #[cfg(feature = "kstring2")]
+  fn kstring() {
+    let inline = kstring2::KString::from_static("key");
+    assert_eq!(inline.cheap_clone(), inline);
+
+    let heap = kstring2::KString::from_string("a key which is too long to be inlined".repeat(4));
+    assert_eq!(heap.cheap_clone(), heap);
+  }
+
+  #[test]
+  #[cfg(feature = "ecow02")]
+  fn eco_string() {
+    let inline = ecow02::EcoString::from("key");
+    assert_eq!(inline.cheap_clone(), inline);
+
+    let heap = ecow02::EcoString::from("a key which is too long to be inlined".repeat(4));
+    let cloned = heap.cheap_clone();
+    assert_eq!(cloned, heap);
+    assert_eq!(cloned.as_ptr(), heap.as_ptr());
+  }
+
+  #[test]
+  fn weak() {
+    let arc = Arc::new(7u64);
+    let weak = Arc::downgrade(&arc).cheap_clone();
+    assert!(Arc::ptr_eq(&weak.upgrade().unwrap(), &arc));
+
+    let rc = std::rc::Rc::new(7u64);
+    let weak = std::rc::Rc::downgrade(&rc).cheap_clone();
+    assert_eq!(*weak.upgrade().unwrap(), 7);
+
+    drop(arc);
+    drop(rc);
+    assert!(weak.upgrade().is_none());
+  }
+}
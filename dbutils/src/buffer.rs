@@ -2,19 +2,28 @@ use core::{
   array::TryFromSliceError,
   borrow::{Borrow, BorrowMut},
   marker::PhantomData,
-  mem,
+  mem::{self, MaybeUninit},
   ptr::{self, NonNull},
   slice,
 };
 
 use crate::{
   equivalent::{Comparable, Equivalent},
-  error::InsufficientBuffer,
-  types::{MaybeStructured, Type},
+  error::{IncompleteBuffer, InsufficientBuffer},
+  types::{MaybeStructured, Type, TypeRef},
 };
 
 use super::leb128::*;
 
+mod byte_order;
+pub use byte_order::{BigEndian, ByteOrder, LittleEndian};
+
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use owned::OwnedBuffer;
+
 /// Writing self to the [`VacantBuffer`] in bytes format.
 pub trait BufWriter {
   /// The error type.
@@ -235,6 +244,26 @@ macro_rules! impl_get {
   };
 }
 
+macro_rules! impl_get_put_with_order {
+  ($($ty:ident), +$(,)?) => {
+    $(
+      paste::paste! {
+        #[doc = "Decodes a `" $ty "` from the buffer in the byte order `E`."]
+        #[inline]
+        pub fn [< get_ $ty >]<E: ByteOrder>(&self) -> Result<$ty, TryFromSliceError> {
+          self.as_ref().try_into().map(E::[< $ty _from_bytes >])
+        }
+
+        #[doc = "Puts a `" $ty "` to the buffer in the byte order `E`."]
+        #[inline]
+        pub fn [< put_ $ty >]<E: ByteOrder>(&mut self, value: $ty) -> Result<(), $crate::error::InsufficientBuffer> {
+          self.put_slice(&E::[< $ty _to_bytes >](value)).map(|_| ())
+        }
+      }
+    )*
+  };
+}
+
 macro_rules! impl_put {
   ($($ty:ident), +$(,)?) => {
     $(
@@ -269,6 +298,98 @@ macro_rules! impl_put {
   };
 }
 
+/// An iterator over a sequence of [`Type`]s encoded by [`VacantBuffer::put_type_seq`].
+pub struct TypeSeqRef<'a, T: ?Sized> {
+  src: &'a [u8],
+  remaining: usize,
+  _m: PhantomData<T>,
+}
+
+impl<T: ?Sized> Clone for TypeSeqRef<'_, T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T: ?Sized> Copy for TypeSeqRef<'_, T> {}
+
+impl<T: ?Sized> core::fmt::Debug for TypeSeqRef<'_, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("TypeSeqRef")
+      .field("src", &self.src)
+      .field("remaining", &self.remaining)
+      .finish()
+  }
+}
+
+impl<'a, T: ?Sized> TypeSeqRef<'a, T> {
+  /// Decodes a sequence encoded by [`VacantBuffer::put_type_seq`] from the given bytes slice.
+  ///
+  /// The bounds of every element are validated eagerly, so the returned iterator
+  /// never fails.
+  ///
+  /// Returns the number of bytes the sequence occupies and an iterator over the elements.
+  ///
+  /// ## Safety
+  /// - the `src` must be produced by [`VacantBuffer::put_type_seq`] with elements of type `T`.
+  pub unsafe fn decode(src: &'a [u8]) -> Result<(usize, Self), DecodeVarintError> {
+    let (mut offset, count) = decode_u64_varint(src)?;
+    let start = offset;
+
+    for _ in 0..count {
+      let (read, len) = decode_u64_varint(&src[offset..])?;
+      offset += read;
+      let remaining = src.len() - offset;
+      if len > remaining as u64 {
+        return Err(DecodeVarintError::IncompleteBuffer(
+          IncompleteBuffer::with_information(len, remaining as u64),
+        ));
+      }
+      offset += len as usize;
+    }
+
+    Ok((
+      offset,
+      Self {
+        src: &src[start..offset],
+        remaining: count as usize,
+        _m: PhantomData,
+      },
+    ))
+  }
+}
+
+impl<'a, T> Iterator for TypeSeqRef<'a, T>
+where
+  T: Type + ?Sized,
+{
+  type Item = T::Ref<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    // Bounds have been validated in `decode`.
+    let (read, len) = decode_u64_varint(self.src).ok()?;
+    let end = read + len as usize;
+    let ent = &self.src[read..end];
+    self.src = &self.src[end..];
+    self.remaining -= 1;
+
+    // SAFETY: the caller of `decode` guarantees the bytes were encoded from `T`.
+    Some(unsafe { <T::Ref<'a> as TypeRef<'a>>::from_slice(ent) })
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl<T> ExactSizeIterator for TypeSeqRef<'_, T> where T: Type + ?Sized {}
+
 /// A vacant buffer in the WAL.
 #[must_use = "vacant buffer must be filled with bytes."]
 #[derive(Debug)]
@@ -301,6 +422,27 @@ impl<'a> From<&'a mut [u8]> for VacantBuffer<'a> {
   }
 }
 
+/// Writes as many bytes as fit into the remaining space.
+///
+/// Once the buffer is full, `write` returns `Ok(0)`, so [`write_all`](std::io::Write::write_all)
+/// and `write!` fail with [`ErrorKind::WriteZero`](std::io::ErrorKind::WriteZero) instead
+/// of panicking.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::io::Write for VacantBuffer<'_> {
+  #[inline]
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    let len = buf.len().min(self.remaining());
+    self.put_slice_unchecked(&buf[..len]);
+    Ok(len)
+  }
+
+  #[inline]
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
 impl<'a> VacantBuffer<'a> {
   /// Returns the slice of the vacant value. The lifetime is bound to the buffer.
   #[inline]
@@ -311,6 +453,54 @@ impl<'a> VacantBuffer<'a> {
 
     unsafe { slice::from_raw_parts(self.value.as_ptr(), self.len) }
   }
+
+  /// Reserves the next `n` bytes of the buffer and returns a sub-buffer aimed at the reserved region.
+  ///
+  /// Afterwards the length of `self` is advanced by `n`, so the following writes to `self` go
+  /// after the reserved region. This is useful for the reserve-then-backfill pattern, e.g.
+  /// reserving a header whose content depends on the body written after it.
+  ///
+  /// The reserved region is zero-filled, the same as the gap left by
+  /// [`set_len`](VacantBuffer::set_len).
+  ///
+  /// ## Safety
+  /// - The returned buffer is not tied to the borrow of `self`, so the caller must not access the
+  ///   reserved region through `self` (e.g. [`as_slice`](VacantBuffer::as_slice), `Deref` or
+  ///   `DerefMut`) while the returned buffer is still in use.
+  /// - The returned buffer must not outlive the memory `self` points to.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use dbutils::buffer::VacantBuffer;
+  ///
+  /// let mut data = [0u8; 16];
+  /// let mut buf = VacantBuffer::from(data.as_mut_slice());
+  /// let mut header = unsafe { buf.reserve(4).unwrap() };
+  /// let written = buf.put_slice(b"hello").unwrap();
+  /// header.put_u32_le(written as u32).unwrap();
+  ///
+  /// assert_eq!(buf.as_slice(), [5, 0, 0, 0, b'h', b'e', b'l', b'l', b'o']);
+  /// ```
+  pub unsafe fn reserve(&mut self, n: usize) -> Result<VacantBuffer<'a>, InsufficientBuffer> {
+    let remaining = self.cap - self.len;
+    if n > remaining {
+      return Err(InsufficientBuffer::with_information(
+        n as u64,
+        remaining as u64,
+      ));
+    }
+
+    if n == 0 {
+      return Ok(Self::dangling());
+    }
+
+    // SAFETY: the reserved region is within the capacity, and the caller guarantees it is not
+    // accessed through `self` while the reserved buffer is in use.
+    let reserved = VacantBuffer::new(n, self.value.add(self.len));
+    self.set_len(self.len + n);
+    Ok(reserved)
+  }
 }
 
 impl VacantBuffer<'_> {
@@ -328,6 +518,198 @@ impl VacantBuffer<'_> {
     self.len = self.cap;
   }
 
+  /// Fills the next `n` bytes with the given byte and advances the length by `n`.
+  ///
+  /// This is useful for writing padding, e.g. aligning a record, without allocating a buffer of pad bytes.
+  pub fn fill_n(&mut self, byte: u8, n: usize) -> Result<(), InsufficientBuffer> {
+    let remaining = self.cap - self.len;
+    if n > remaining {
+      return Err(InsufficientBuffer::with_information(
+        n as u64,
+        remaining as u64,
+      ));
+    }
+
+    if n == 0 {
+      return Ok(());
+    }
+
+    // SAFETY: the value's ptr is aligned and `n` is within the remaining space.
+    unsafe {
+      ptr::write_bytes(self.value.as_ptr().add(self.len), byte, n);
+    }
+    self.len += n;
+    Ok(())
+  }
+
+  /// Writes zero padding until the length is a multiple of `align`, and returns the number of
+  /// padding bytes written.
+  ///
+  /// The alignment is relative to the start of the buffer, so the buffer itself should start at
+  /// an offset aligned to `align`, e.g. the start of an mmap region. On error, nothing is
+  /// written.
+  ///
+  /// ## Panics
+  /// - If `align` is not a power of two.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use dbutils::buffer::VacantBuffer;
+  ///
+  /// let mut data = [0xffu8; 16];
+  /// let mut buf = VacantBuffer::from(data.as_mut_slice());
+  /// buf.put_slice(b"abc").unwrap();
+  /// assert_eq!(buf.align_to(8).unwrap(), 5);
+  /// assert_eq!(buf.as_slice(), b"abc\0\0\0\0\0");
+  /// assert_eq!(buf.align_to(8).unwrap(), 0);
+  /// ```
+  pub fn align_to(&mut self, align: usize) -> Result<usize, InsufficientBuffer> {
+    assert!(align.is_power_of_two(), "alignment must be a power of two");
+
+    let padding = self.len.wrapping_neg() & (align - 1);
+    let remaining = self.cap - self.len;
+    if padding > remaining {
+      return Err(InsufficientBuffer::with_information(
+        padding as u64,
+        remaining as u64,
+      ));
+    }
+
+    self.fill_n(0, padding)?;
+    Ok(padding)
+  }
+
+  /// Copies the written bytes in `src` to the position starting at `dest`, the two regions
+  /// may overlap.
+  ///
+  /// `src` must be within the written bytes, and the destination must start within the
+  /// written bytes and end within the capacity. If the destination ends after the written
+  /// bytes, the length grows to the end of the destination.
+  ///
+  /// This is useful for restructuring a partially built record in place, e.g. shifting the
+  /// body to make room for a larger header.
+  ///
+  /// ## Panics
+  /// - If `src` is not within [`len`](VacantBuffer::len).
+  /// - If `dest` is greater than [`len`](VacantBuffer::len), or `dest + src.len()` is greater
+  ///   than [`capacity`](VacantBuffer::capacity).
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use dbutils::buffer::VacantBuffer;
+  ///
+  /// let mut data = [0u8; 8];
+  /// let mut buf = VacantBuffer::from(data.as_mut_slice());
+  /// buf.put_slice(b"\x01hello").unwrap();
+  ///
+  /// // widen the header from 1 to 2 bytes.
+  /// buf.copy_within(1..6, 2);
+  /// assert_eq!(buf.as_slice(), [1, b'h', b'h', b'e', b'l', b'l', b'o']);
+  /// ```
+  #[track_caller]
+  pub fn copy_within(&mut self, src: core::ops::Range<usize>, dest: usize) {
+    let core::ops::Range { start, end } = src;
+    assert!(
+      start <= end,
+      "copy_within source range starts at {start} but ends at {end}"
+    );
+    assert!(
+      end <= self.len,
+      "copy_within source range end {end} out of range for buffer of length {}",
+      self.len
+    );
+    assert!(
+      dest <= self.len,
+      "copy_within destination {dest} out of range for buffer of length {}",
+      self.len
+    );
+
+    let count = end - start;
+    let dest_end = dest + count;
+    assert!(
+      dest_end <= self.cap,
+      "copy_within destination end {dest_end} out of range for buffer of capacity {}",
+      self.cap
+    );
+
+    if count == 0 {
+      return;
+    }
+
+    // SAFETY: both regions are within the capacity, and `ptr::copy` handles overlapping regions.
+    unsafe {
+      let ptr = self.value.as_ptr();
+      ptr::copy(ptr.add(start), ptr.add(dest), count);
+    }
+    self.len = self.len.max(dest_end);
+  }
+
+  /// Consumes the buffer, asserting that it has been fully filled, and returns
+  /// the number of bytes written.
+  ///
+  /// Call this at the end of an encode routine: a partially filled buffer
+  /// usually means the encoded length and the bytes actually written disagree.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if `remaining() != 0`.
+  #[inline]
+  #[track_caller]
+  pub fn expect_full(self) -> usize {
+    let remaining = self.cap - self.len;
+    assert!(
+      remaining == 0,
+      "vacant buffer is not fully filled with bytes (remaining {remaining})"
+    );
+    self.len
+  }
+
+  /// Returns the unfilled tail of the buffer as uninitialized memory.
+  ///
+  /// This is meant for APIs which write into `&mut [MaybeUninit<u8>]` and report how
+  /// many bytes they initialized (e.g. decompressors), so the tail does not need to be
+  /// zeroed first. After writing, call [`advance`](VacantBuffer::advance) with the number
+  /// of bytes written.
+  ///
+  /// ## Safety
+  /// - The caller must not write [`MaybeUninit::uninit`] (or any other uninitialized value)
+  ///   into the returned slice. The memory may be borrowed from an initialized `&mut [u8]`,
+  ///   which must stay initialized.
+  /// - The contents of the returned slice are unspecified and must not be assumed to be
+  ///   initialized until written.
+  #[inline]
+  pub unsafe fn uninit_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+    let remaining = self.cap - self.len;
+    if remaining == 0 {
+      return &mut [];
+    }
+
+    // SAFETY: the ptr is valid for `cap` bytes, and `MaybeUninit<u8>` has the same layout as `u8`.
+    slice::from_raw_parts_mut(
+      self.value.as_ptr().add(self.len) as *mut MaybeUninit<u8>,
+      remaining,
+    )
+  }
+
+  /// Marks the next `n` bytes of the unfilled tail as filled.
+  ///
+  /// ## Safety
+  /// - The first `n` bytes of [`uninit_mut`](VacantBuffer::uninit_mut) must have been initialized.
+  ///
+  /// ## Panics
+  /// - If `n` is greater than [`remaining`](VacantBuffer::remaining).
+  #[inline]
+  pub unsafe fn advance(&mut self, n: usize) {
+    let remaining = self.cap - self.len;
+    assert!(
+      n <= remaining,
+      "buffer does not have enough space (remaining {remaining}, want {n})"
+    );
+    self.len += n;
+  }
+
   /// Splits the buffer into two at the given index.
   ///
   /// Afterwards `self` has capacity `cap - at`, and the returned
@@ -472,6 +854,16 @@ impl VacantBuffer<'_> {
     Ok(len)
   }
 
+  /// Put bytes to the vacant value, and returns the written region of the buffer.
+  ///
+  /// This is useful when the just written bytes are needed afterwards, e.g. to compute
+  /// a checksum over them, without tracking the offset manually.
+  pub fn put_slice_ref(&mut self, bytes: &[u8]) -> Result<&[u8], InsufficientBuffer> {
+    let start = self.len;
+    self.put_slice(bytes)?;
+    Ok(&self.as_slice()[start..])
+  }
+
   /// Write bytes to the vacant value without bounds checking.
   ///
   /// # Panics
@@ -497,10 +889,38 @@ impl VacantBuffer<'_> {
     self.len += len;
   }
 
+  /// Encodes a sequence of [`Type`]s to the buffer.
+  ///
+  /// The sequence is encoded as the number of elements in LEB128 format, followed by
+  /// each element prefixed with its encoded length in LEB128 format. An empty sequence
+  /// is encoded as a single `0` byte.
+  ///
+  /// Returns the number of bytes written if successful.
+  ///
+  /// The encoded sequence can be decoded by [`TypeSeqRef::decode`].
+  pub fn put_type_seq<I, T>(&mut self, iter: I) -> Result<usize, T::Error>
+  where
+    I: IntoIterator<Item = T>,
+    I::IntoIter: ExactSizeIterator,
+    T: Type,
+    T::Error: From<InsufficientBuffer>,
+  {
+    let iter = iter.into_iter();
+    let mut written = self.put_u64_varint(iter.len() as u64)?;
+
+    for ent in iter {
+      written += self.put_u64_varint(ent.encoded_len() as u64)?;
+      written += ent.encode_to_buffer(self)?;
+    }
+
+    Ok(written)
+  }
+
   impl_get_varint!(u16, u32, u64, u128, i16, i32, i64, i128);
   impl_get!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
   impl_put_varint!(u16, u32, u64, u128, i16, i32, i64, i128);
   impl_put!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
+  impl_get_put_with_order!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
 
   /// Put a byte to the vacant value.
   pub fn put_u8(&mut self, value: u8) -> Result<(), InsufficientBuffer> {
@@ -717,3 +1137,299 @@ impl_ord!(
   const N impl <&VacantBuffer<'a>> <=> [u8; N],
   const N impl <&mut VacantBuffer<'a>> <=> [u8; N],
 );
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn roundtrip_with_order<E: ByteOrder>() {
+    let mut data = [0u8; 4];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_u32::<E>(0x0102_0304).unwrap();
+    assert_eq!(buf.get_u32::<E>().unwrap(), 0x0102_0304);
+    assert!(buf.put_u32::<E>(0).is_err());
+
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_i64::<E>(-42).unwrap();
+    assert_eq!(buf.get_i64::<E>().unwrap(), -42);
+
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_f64::<E>(1.5).unwrap();
+    assert_eq!(buf.get_f64::<E>().unwrap(), 1.5);
+  }
+
+  #[test]
+  fn byte_order() {
+    roundtrip_with_order::<LittleEndian>();
+    roundtrip_with_order::<BigEndian>();
+
+    let mut data = [0u8; 2];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_u16::<BigEndian>(0x0102).unwrap();
+    assert_eq!(buf.as_slice(), [1, 2]);
+    assert_eq!(buf.get_u16::<LittleEndian>().unwrap(), 0x0201);
+    assert_eq!(buf.get_u16_be().unwrap(), 0x0102);
+  }
+
+  #[test]
+  fn fill_n_padding() {
+    let mut data = [0u8; 16];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_slice(b"abc").unwrap();
+
+    let padding = buf.len().next_multiple_of(8) - buf.len();
+    buf.fill_n(0xff, padding).unwrap();
+    assert_eq!(buf.len(), 8);
+    assert_eq!(
+      buf.as_slice(),
+      [b'a', b'b', b'c', 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+
+    buf.fill_n(0xff, 0).unwrap();
+    assert_eq!(buf.len(), 8);
+    let err = buf.fill_n(0, buf.remaining() + 1).unwrap_err();
+    assert_eq!(err.required(), Some(9));
+    assert_eq!(err.remaining(), Some(8));
+    assert_eq!(buf.len(), 8);
+  }
+
+  #[test]
+  fn copy_within_overlapping() {
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_slice(b"abcdef").unwrap();
+
+    // forward move
+    buf.copy_within(0..4, 2);
+    assert_eq!(buf.as_slice(), b"ababcd");
+
+    // backward move
+    buf.copy_within(2..6, 0);
+    assert_eq!(buf.as_slice(), b"abcdcd");
+
+    // forward move past the written bytes grows the length
+    buf.copy_within(2..6, 4);
+    assert_eq!(buf.as_slice(), b"abcdcdcd");
+    assert_eq!(buf.len(), 8);
+
+    buf.copy_within(3..3, 8);
+    assert_eq!(buf.as_slice(), b"abcdcdcd");
+  }
+
+  #[test]
+  #[should_panic(expected = "source range end")]
+  fn copy_within_src_out_of_len() {
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_slice(b"abcd").unwrap();
+    buf.copy_within(2..5, 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "destination end")]
+  fn copy_within_dest_out_of_cap() {
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_slice(b"abcd").unwrap();
+    buf.copy_within(0..4, 4);
+    buf.copy_within(0..4, 5);
+  }
+
+  #[test]
+  fn expect_full() {
+    let mut data = [0u8; 4];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_u32_le(7).unwrap();
+    assert_eq!(buf.expect_full(), 4);
+  }
+
+  #[test]
+  #[should_panic(expected = "remaining 2")]
+  fn expect_full_panics_with_remaining() {
+    let mut data = [0u8; 4];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_u16_le(7).unwrap();
+    buf.expect_full();
+  }
+
+  #[test]
+  fn write_through_uninit() {
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_u8(1).unwrap();
+
+    let written = {
+      // SAFETY: only initialized bytes are written.
+      let tail = unsafe { buf.uninit_mut() };
+      assert_eq!(tail.len(), 7);
+      for (i, b) in tail.iter_mut().take(3).enumerate() {
+        b.write(i as u8 + 2);
+      }
+      3
+    };
+    // SAFETY: the first `written` bytes of the tail were initialized above.
+    unsafe { buf.advance(written) };
+
+    assert_eq!(buf.len(), 4);
+    assert_eq!(buf.as_slice(), [1, 2, 3, 4]);
+
+    buf.fill_n(0, buf.remaining()).unwrap();
+    assert!(unsafe { buf.uninit_mut() }.is_empty());
+  }
+
+  #[test]
+  fn reserve_then_backfill() {
+    let mut data = [0u8; 16];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let mut header = unsafe { buf.reserve(4).unwrap() };
+    assert_eq!(header.capacity(), 4);
+    assert_eq!(buf.len(), 4);
+
+    let body = b"layer0";
+    buf.put_slice(body).unwrap();
+    header.put_u32_le(body.len() as u32).unwrap();
+    assert_eq!(header.remaining(), 0);
+
+    assert_eq!(buf.len(), 4 + body.len());
+    assert_eq!(&buf.as_slice()[..4], (body.len() as u32).to_le_bytes());
+    assert_eq!(&buf.as_slice()[4..], body);
+
+    let err = unsafe { buf.reserve(buf.remaining() + 1) }.unwrap_err();
+    assert_eq!(err.required(), Some(7));
+    assert_eq!(err.remaining(), Some(6));
+
+    // the reserved region never exposes stale bytes.
+    let mut data = [0xffu8; 4];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    drop(unsafe { buf.reserve(2).unwrap() });
+    assert_eq!(buf.as_slice(), [0, 0]);
+  }
+
+  #[test]
+  fn type_seq_empty() {
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let src: &[u32] = &[];
+    let written = buf.put_type_seq(src).unwrap();
+    assert_eq!(written, 1);
+    assert_eq!(buf.as_slice(), [0]);
+
+    let (read, seq) = unsafe { TypeSeqRef::<u32>::decode(buf.as_slice()).unwrap() };
+    assert_eq!(read, written);
+    assert_eq!(seq.len(), 0);
+    assert_eq!(seq.count(), 0);
+  }
+
+  #[test]
+  fn type_seq_roundtrip() {
+    let mut data = [0u8; 64];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let src: &[u32] = &[0, 1, 128, u32::MAX / 2, u32::MAX];
+    let written = buf.put_type_seq(src).unwrap();
+    assert_eq!(written, 1 + src.len() * (1 + 4));
+    assert_eq!(written, buf.len());
+
+    let (read, seq) = unsafe { TypeSeqRef::<u32>::decode(buf.as_slice()).unwrap() };
+    assert_eq!(read, written);
+    assert_eq!(seq.len(), src.len());
+    assert_eq!(seq.collect::<std::vec::Vec<_>>(), src);
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn io_write() {
+    use std::io::Write;
+
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    write!(buf, "{}-ab", 12).unwrap();
+    buf.flush().unwrap();
+    assert_eq!(buf.as_slice(), b"12-ab");
+
+    assert_eq!(Write::write(&mut buf, b"cdef").unwrap(), 3);
+    assert_eq!(buf.as_slice(), b"12-abcde");
+    assert_eq!(Write::write(&mut buf, b"f").unwrap(), 0);
+
+    let mut data = [0u8; 4];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let err = write!(buf, "{}", 123456).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    assert_eq!(buf.as_slice(), b"1234");
+  }
+
+  #[test]
+  fn put_slice_ref() {
+    fn checksum(bytes: &[u8]) -> u32 {
+      bytes
+        .iter()
+        .fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(*b as u32))
+    }
+
+    let mut data = [0u8; 8];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+
+    let written = buf.put_slice_ref(b"abc").unwrap();
+    assert_eq!(written, b"abc");
+    assert_eq!(checksum(written), checksum(b"abc"));
+
+    let written = buf.put_slice_ref(b"defg").unwrap();
+    assert_eq!(written, b"defg");
+    assert_eq!(checksum(written), checksum(b"defg"));
+    assert_eq!(buf.as_slice(), b"abcdefg");
+
+    assert!(buf.put_slice_ref(b"hi").is_err());
+    assert_eq!(buf.len(), 7);
+  }
+
+  #[test]
+  fn align_to() {
+    for align in [4, 8] {
+      for start in 0..=16 {
+        let mut data = [0xffu8; 32];
+        let mut buf = VacantBuffer::from(data.as_mut_slice());
+        buf.fill_n(1, start).unwrap();
+
+        let padding = buf.align_to(align).unwrap();
+        assert!(padding < align);
+        assert_eq!(buf.len(), start + padding);
+        assert_eq!(buf.len() % align, 0);
+        assert!(buf.as_slice()[start..].iter().all(|b| *b == 0));
+        assert_eq!(buf.align_to(align).unwrap(), 0);
+      }
+    }
+
+    let mut data = [0u8; 6];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    buf.put_u8(1).unwrap();
+    assert!(buf.align_to(8).is_err());
+    assert_eq!(buf.len(), 1);
+    assert_eq!(buf.align_to(1).unwrap(), 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "alignment must be a power of two")]
+  fn align_to_non_power_of_two() {
+    let mut data = [0u8; 8];
+    VacantBuffer::from(data.as_mut_slice()).align_to(3).unwrap();
+  }
+
+  #[test]
+  fn type_seq_insufficient_buffer() {
+    let mut data = [0u8; 4];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let src: &[u32] = &[1];
+    assert!(buf.put_type_seq(src).is_err());
+  }
+
+  #[test]
+  fn type_seq_incomplete_buffer() {
+    let mut data = [0u8; 16];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let src: &[u32] = &[1, 2];
+    let written = buf.put_type_seq(src).unwrap();
+    let res = unsafe { TypeSeqRef::<u32>::decode(&buf.as_slice()[..written - 1]) };
+    assert!(matches!(res, Err(DecodeVarintError::IncompleteBuffer(_))));
+  }
+}
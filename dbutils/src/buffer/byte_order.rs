@@ -0,0 +1,68 @@
+mod sealed {
+  pub trait Sealed {}
+
+  impl Sealed for super::LittleEndian {}
+  impl Sealed for super::BigEndian {}
+}
+
+macro_rules! byte_order {
+  ($($ty:ident), +$(,)?) => {
+    /// A marker for the byte order used to encode numbers, which allows one code path
+    /// to handle an endianness chosen at runtime, e.g. from a file header.
+    ///
+    /// This trait is sealed, the implementors are [`LittleEndian`] and [`BigEndian`].
+    pub trait ByteOrder: sealed::Sealed + Copy + Default + core::fmt::Debug {
+      $(
+        paste::paste! {
+          #[doc = "Converts a `" $ty "` into bytes in this byte order."]
+          fn [< $ty _to_bytes >](value: $ty) -> [u8; core::mem::size_of::<$ty>()];
+
+          #[doc = "Converts bytes in this byte order into a `" $ty "`."]
+          fn [< $ty _from_bytes >](bytes: [u8; core::mem::size_of::<$ty>()]) -> $ty;
+        }
+      )*
+    }
+
+    impl ByteOrder for LittleEndian {
+      $(
+        paste::paste! {
+          #[inline]
+          fn [< $ty _to_bytes >](value: $ty) -> [u8; core::mem::size_of::<$ty>()] {
+            value.to_le_bytes()
+          }
+
+          #[inline]
+          fn [< $ty _from_bytes >](bytes: [u8; core::mem::size_of::<$ty>()]) -> $ty {
+            $ty::from_le_bytes(bytes)
+          }
+        }
+      )*
+    }
+
+    impl ByteOrder for BigEndian {
+      $(
+        paste::paste! {
+          #[inline]
+          fn [< $ty _to_bytes >](value: $ty) -> [u8; core::mem::size_of::<$ty>()] {
+            value.to_be_bytes()
+          }
+
+          #[inline]
+          fn [< $ty _from_bytes >](bytes: [u8; core::mem::size_of::<$ty>()]) -> $ty {
+            $ty::from_be_bytes(bytes)
+          }
+        }
+      )*
+    }
+  };
+}
+
+/// The little-endian byte order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LittleEndian;
+
+/// The big-endian byte order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigEndian;
+
+byte_order!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
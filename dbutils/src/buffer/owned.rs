@@ -0,0 +1,210 @@
+use std::vec::Vec;
+
+use crate::error::InsufficientBuffer;
+
+/// The capacity an empty [`OwnedBuffer`] grows to on its first growing write.
+const MIN_GROW_CAPACITY: usize = 8;
+
+/// An owned buffer backed by a `Vec<u8>`.
+///
+/// [`put_slice`](OwnedBuffer::put_slice) writes within the current capacity like
+/// [`VacantBuffer`](super::VacantBuffer) does, while [`put_slice_grow`](OwnedBuffer::put_slice_grow)
+/// grows the buffer instead of failing, for encoders which do not know the final size up front.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::buffer::OwnedBuffer;
+///
+/// let mut buf = OwnedBuffer::with_capacity(4);
+/// buf.put_slice(b"abcd").unwrap();
+/// assert!(buf.put_slice(b"e").is_err());
+///
+/// buf.put_slice_grow(b"efgh");
+/// assert_eq!(buf.as_slice(), b"abcdefgh");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedBuffer {
+  buf: Vec<u8>,
+}
+
+impl OwnedBuffer {
+  /// Creates a new empty buffer, without allocating.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { buf: Vec::new() }
+  }
+
+  /// Creates a new empty buffer with at least the given capacity.
+  #[inline]
+  pub fn with_capacity(cap: usize) -> Self {
+    Self {
+      buf: Vec::with_capacity(cap),
+    }
+  }
+
+  /// Returns the written bytes.
+  #[inline]
+  pub fn as_slice(&self) -> &[u8] {
+    &self.buf
+  }
+
+  /// Returns the number of written bytes.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.buf.len()
+  }
+
+  /// Returns `true` if no bytes have been written.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.buf.is_empty()
+  }
+
+  /// Returns the capacity of the buffer.
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.buf.capacity()
+  }
+
+  /// Returns the remaining capacity of the buffer.
+  #[inline]
+  pub fn remaining(&self) -> usize {
+    self.buf.capacity() - self.buf.len()
+  }
+
+  /// Put bytes to the buffer, without growing it.
+  ///
+  /// Returns the number of bytes written if successful.
+  pub fn put_slice(&mut self, bytes: &[u8]) -> Result<usize, InsufficientBuffer> {
+    let remaining = self.remaining();
+    if bytes.len() > remaining {
+      return Err(InsufficientBuffer::with_information(
+        bytes.len() as u64,
+        remaining as u64,
+      ));
+    }
+
+    self.buf.extend_from_slice(bytes);
+    Ok(bytes.len())
+  }
+
+  /// Put bytes to the buffer, growing it if the bytes do not fit.
+  ///
+  /// The capacity is at least doubled on each growth, so a sequence of writes costs amortized
+  /// constant time per byte. Returns the number of bytes written.
+  pub fn put_slice_grow(&mut self, bytes: &[u8]) -> usize {
+    self.grow(bytes.len());
+    self.buf.extend_from_slice(bytes);
+    bytes.len()
+  }
+
+  /// Put a byte to the buffer, growing it if the byte does not fit.
+  #[inline]
+  pub fn put_u8_grow(&mut self, byte: u8) {
+    self.grow(1);
+    self.buf.push(byte);
+  }
+
+  /// Clears the written bytes, keeping the capacity.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.buf.clear();
+  }
+
+  /// Consumes the buffer and returns the written bytes.
+  #[inline]
+  pub fn into_vec(self) -> Vec<u8> {
+    self.buf
+  }
+
+  #[inline]
+  fn grow(&mut self, additional: usize) {
+    if additional <= self.remaining() {
+      return;
+    }
+
+    let required = self
+      .buf
+      .len()
+      .checked_add(additional)
+      .expect("capacity overflow");
+    let cap = (self.buf.capacity() * 2)
+      .max(required)
+      .max(MIN_GROW_CAPACITY);
+    self.buf.reserve_exact(cap - self.buf.len());
+  }
+}
+
+impl From<Vec<u8>> for OwnedBuffer {
+  #[inline]
+  fn from(buf: Vec<u8>) -> Self {
+    Self { buf }
+  }
+}
+
+impl From<OwnedBuffer> for Vec<u8> {
+  #[inline]
+  fn from(buf: OwnedBuffer) -> Self {
+    buf.buf
+  }
+}
+
+impl AsRef<[u8]> for OwnedBuffer {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    &self.buf
+  }
+}
+
+impl core::ops::Deref for OwnedBuffer {
+  type Target = [u8];
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.buf
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn put_slice_grow() {
+    let mut buf = OwnedBuffer::with_capacity(4);
+    let initial = buf.capacity();
+    let mut expected = Vec::new();
+    let mut capacities = std::vec![initial];
+
+    for i in 0..200u8 {
+      let chunk = [i; 3];
+      assert_eq!(buf.put_slice_grow(&chunk), 3);
+      expected.extend_from_slice(&chunk);
+      if buf.capacity() != *capacities.last().unwrap() {
+        capacities.push(buf.capacity());
+      }
+    }
+
+    assert_eq!(buf.as_slice(), expected.as_slice());
+    // 600 bytes from a capacity of 4 need only a logarithmic number of growths.
+    assert!(capacities.len() <= 10, "{capacities:?}");
+    for pair in capacities.windows(2) {
+      assert!(pair[1] >= pair[0] * 2, "{capacities:?}");
+    }
+
+    let remaining = buf.remaining();
+    assert!(buf.put_slice(&std::vec![0; remaining + 1]).is_err());
+    assert_eq!(buf.len(), expected.len());
+  }
+
+  #[test]
+  fn grow_from_empty() {
+    let mut buf = OwnedBuffer::new();
+    buf.put_u8_grow(1);
+    assert!(buf.capacity() >= MIN_GROW_CAPACITY);
+    buf.put_slice_grow(&[2; 100]);
+    assert_eq!(buf.len(), 101);
+    assert_eq!(buf.into_vec()[..2], [1, 2]);
+  }
+}
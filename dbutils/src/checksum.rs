@@ -1,3 +1,21 @@
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use reader::ChecksumReader;
+
+mod adler32;
+pub use adler32::Adler32;
+
+mod truncate;
+pub use truncate::{fold32, Truncate32};
+
+#[cfg(feature = "xxhash3")]
+mod xxh3_128;
+#[cfg(feature = "xxhash3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xxhash3")))]
+pub use xxh3_128::XxHash3_128;
+
 /// A trait for creating instances of [`Checksumer`].
 ///
 /// A `BuildChecksumer` is typically used to create
@@ -243,3 +261,47 @@ const _: () = {
 
   impl super::CheapClone for XxHash3 {}
 };
+
+/// A checksumer shared by the tests, so they do not depend on any checksum feature.
+#[cfg(test)]
+mod test_checksumer {
+  use super::{BuildChecksumer, Checksumer};
+
+  /// FNV-1a, which is order-sensitive.
+  #[derive(Default, Clone)]
+  pub(super) struct Fnv(u64);
+
+  impl Checksumer for Fnv {
+    fn update(&mut self, buf: &[u8]) {
+      if self.0 == 0 {
+        self.0 = 0xcbf29ce484222325;
+      }
+      for b in buf {
+        self.0 ^= *b as u64;
+        self.0 = self.0.wrapping_mul(0x100000001b3);
+      }
+    }
+
+    fn reset(&mut self) {
+      self.0 = 0;
+    }
+
+    fn digest(&self) -> u64 {
+      self.0
+    }
+  }
+
+  impl BuildChecksumer for Fnv {
+    type Checksumer = Self;
+
+    fn build_checksumer(&self) -> Self::Checksumer {
+      Self::default()
+    }
+
+    fn checksum_one(&self, src: &[u8]) -> u64 {
+      let mut fnv = Self::default();
+      fnv.update(src);
+      fnv.digest()
+    }
+  }
+}
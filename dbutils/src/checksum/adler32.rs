@@ -0,0 +1,139 @@
+use super::{BuildChecksumer, Checksumer};
+
+/// The largest prime smaller than 65536.
+const MOD: u32 = 65521;
+
+/// The largest number of bytes which can be summed before `b` overflows a `u32`,
+/// see the zlib source.
+const NMAX: usize = 5552;
+
+/// Adler-32 checksumer, as used by zlib.
+///
+/// The checksum starts from the documented initial value of `1`, so the checksum of an
+/// empty input is `1`.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::checksum::{Adler32, BuildChecksumer};
+///
+/// assert_eq!(Adler32::new().checksum_one(b"Wikipedia"), 0x11E6_0398);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adler32 {
+  a: u32,
+  b: u32,
+}
+
+impl Default for Adler32 {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Adler32 {
+  /// Create a new Adler-32 checksumer.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { a: 1, b: 0 }
+  }
+
+  /// Returns the checksum as a `u32`.
+  #[inline]
+  pub const fn digest32(&self) -> u32 {
+    (self.b << 16) | self.a
+  }
+}
+
+impl Checksumer for Adler32 {
+  #[inline]
+  fn update(&mut self, buf: &[u8]) {
+    let Self { mut a, mut b } = *self;
+    for chunk in buf.chunks(NMAX) {
+      for byte in chunk {
+        a += *byte as u32;
+        b += a;
+      }
+      a %= MOD;
+      b %= MOD;
+    }
+    self.a = a;
+    self.b = b;
+  }
+
+  #[inline]
+  fn reset(&mut self) {
+    *self = Self::new();
+  }
+
+  #[inline]
+  fn digest(&self) -> u64 {
+    self.digest32() as u64
+  }
+}
+
+impl BuildChecksumer for Adler32 {
+  type Checksumer = Self;
+
+  #[inline]
+  fn build_checksumer(&self) -> Self::Checksumer {
+    Self::new()
+  }
+
+  #[inline]
+  fn checksum_one(&self, src: &[u8]) -> u64 {
+    let mut adler = Self::new();
+    adler.update(src);
+    adler.digest()
+  }
+}
+
+impl crate::CheapClone for Adler32 {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn vectors() {
+    let cases: [(&[u8], u32); 5] = [
+      (b"", 0x0000_0001),
+      (b"a", 0x0062_0062),
+      (b"abc", 0x024d_0127),
+      (b"Wikipedia", 0x11e6_0398),
+      (b"hello, world", 0x1d54_0489),
+    ];
+
+    for (data, expected) in cases {
+      assert_eq!(Adler32::new().checksum_one(data), expected as u64);
+    }
+
+    // long enough to reduce modulo 65521 several times.
+    let data = [0xffu8; 100_000];
+    assert_eq!(Adler32::new().checksum_one(&data), 0x149a_302c);
+  }
+
+  #[test]
+  fn chunked() {
+    let data = (0..20_000u32)
+      .map(|i| (i * 31) as u8)
+      .collect::<std::vec::Vec<_>>();
+    let expected = Adler32::new().checksum_one(&data);
+
+    for chunk_size in [1, 7, 5552, 5553, 12_345] {
+      let mut adler = Adler32::new().build_checksumer();
+      for chunk in data.chunks(chunk_size) {
+        adler.update(chunk);
+      }
+      assert_eq!(adler.digest(), expected);
+      assert_eq!(adler.digest32() as u64, expected);
+    }
+
+    let mut adler = Adler32::new();
+    adler.update(b"garbage");
+    adler.reset();
+    adler.update(b"Wikipedia");
+    assert_eq!(adler.digest32(), 0x11e6_0398);
+  }
+}
@@ -0,0 +1,159 @@
+use super::Checksumer;
+
+/// A reader which updates a [`Checksumer`] with every byte read from the
+/// underlying reader.
+///
+/// This allows a large value to be verified while it is streamed, without
+/// buffering it or making a second pass over it.
+///
+/// ## Example
+///
+/// ```rust
+/// # #[cfg(feature = "crc32")]
+/// # {
+/// use dbutils::checksum::{BuildChecksumer, ChecksumReader, Crc32};
+/// use std::io::Read;
+///
+/// let data = b"hello, world";
+/// let mut reader = ChecksumReader::new(&data[..], Crc32::new());
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+///
+/// assert_eq!(reader.finalize(), Crc32::new().checksum_one(data));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChecksumReader<R, C> {
+  reader: R,
+  checksumer: C,
+}
+
+impl<R, C> ChecksumReader<R, C> {
+  /// Creates a new `ChecksumReader` which feeds everything read from `reader`
+  /// into `checksumer`.
+  #[inline]
+  pub const fn new(reader: R, checksumer: C) -> Self {
+    Self { reader, checksumer }
+  }
+
+  /// Returns a reference to the underlying reader.
+  #[inline]
+  pub const fn get_ref(&self) -> &R {
+    &self.reader
+  }
+
+  /// Returns a reference to the checksumer.
+  #[inline]
+  pub const fn checksumer(&self) -> &C {
+    &self.checksumer
+  }
+
+  /// Consumes the `ChecksumReader`, returning the underlying reader and the checksumer.
+  #[inline]
+  pub fn into_parts(self) -> (R, C) {
+    (self.reader, self.checksumer)
+  }
+}
+
+impl<R, C: Checksumer> ChecksumReader<R, C> {
+  /// Consumes the `ChecksumReader` and returns the checksum of all bytes read so far.
+  #[inline]
+  pub fn finalize(self) -> u64 {
+    self.checksumer.digest()
+  }
+}
+
+impl<R: std::io::Read, C: Checksumer> std::io::Read for ChecksumReader<R, C> {
+  #[inline]
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    let n = self.reader.read(buf)?;
+    self.checksumer.update(&buf[..n]);
+    Ok(n)
+  }
+}
+
+#[cfg(feature = "future")]
+const _: () = {
+  use core::{
+    pin::Pin,
+    task::{Context, Poll},
+  };
+  use futures_io::AsyncRead;
+
+  impl<R, C> AsyncRead for ChecksumReader<R, C>
+  where
+    R: AsyncRead + Unpin,
+    C: Checksumer + Unpin,
+  {
+    fn poll_read(
+      self: Pin<&mut Self>,
+      cx: &mut Context<'_>,
+      buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+      let this = self.get_mut();
+      match Pin::new(&mut this.reader).poll_read(cx, buf) {
+        Poll::Ready(Ok(n)) => {
+          this.checksumer.update(&buf[..n]);
+          Poll::Ready(Ok(n))
+        }
+        other => other,
+      }
+    }
+  }
+};
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::checksum::test_checksumer::Fnv;
+  use std::io::{Cursor, Read};
+
+  #[test]
+  fn chunked_matches_one_shot() {
+    let data = (0..10_000u32).map(|i| (i * 31) as u8).collect::<Vec<_>>();
+
+    let mut one_shot = Fnv::default();
+    one_shot.update(&data);
+
+    let mut reader = ChecksumReader::new(Cursor::new(data.clone()), Fnv::default());
+    let mut chunk = [0u8; 333];
+    let mut read = Vec::new();
+    loop {
+      let n = reader.read(&mut chunk).unwrap();
+      if n == 0 {
+        break;
+      }
+      read.extend_from_slice(&chunk[..n]);
+    }
+
+    assert_eq!(read, data);
+    assert_eq!(reader.finalize(), one_shot.digest());
+  }
+
+  #[cfg(feature = "future")]
+  #[test]
+  fn async_chunked_matches_one_shot() {
+    use futures_lite::{future::block_on, AsyncReadExt};
+
+    let data = (0..10_000u32).map(|i| (i * 31) as u8).collect::<Vec<_>>();
+
+    let mut one_shot = Fnv::default();
+    one_shot.update(&data);
+
+    let mut reader = ChecksumReader::new(data.as_slice(), Fnv::default());
+    let mut chunk = [0u8; 333];
+    let mut read = Vec::new();
+    block_on(async {
+      loop {
+        let n = AsyncReadExt::read(&mut reader, &mut chunk).await.unwrap();
+        if n == 0 {
+          break;
+        }
+        read.extend_from_slice(&chunk[..n]);
+      }
+    });
+
+    assert_eq!(read, data);
+    assert_eq!(reader.finalize(), one_shot.digest());
+  }
+}
@@ -0,0 +1,143 @@
+use super::{BuildChecksumer, Checksumer};
+
+/// Folds a 64-bit checksum into 32 bits.
+///
+/// The folding function is `(h ^ (h >> 32)) as u32`, i.e. the high half is XORed into
+/// the low half, so every bit of the 64-bit checksum affects the result.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::checksum::fold32;
+///
+/// assert_eq!(fold32(0x0123_4567_89ab_cdef), 0x0123_4567 ^ 0x89ab_cdef);
+/// ```
+#[inline]
+pub const fn fold32(h: u64) -> u32 {
+  (h ^ (h >> 32)) as u32
+}
+
+/// An adapter which folds the 64-bit output of a [`Checksumer`] into 32 bits with [`fold32`].
+///
+/// This allows a strong 64-bit hasher (e.g. xxh3) to be stored in a 4-byte checksum slot.
+/// The folded checksum is returned by [`digest`](Checksumer::digest) and
+/// [`checksum_one`](BuildChecksumer::checksum_one) zero-extended to `u64`, and by
+/// [`digest32`](Truncate32::digest32) as a `u32`.
+///
+/// ## Example
+///
+/// ```rust
+/// # #[cfg(feature = "xxhash3")]
+/// # {
+/// use dbutils::checksum::{fold32, BuildChecksumer, Truncate32, XxHash3};
+///
+/// let data = b"hello, world";
+/// let truncated = Truncate32::new(XxHash3::new());
+/// assert_eq!(
+///   truncated.checksum_one(data),
+///   fold32(XxHash3::new().checksum_one(data)) as u64
+/// );
+/// # }
+/// ```
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Truncate32<C>(C);
+
+impl<C> Truncate32<C> {
+  /// Wraps a 64-bit checksumer.
+  #[inline]
+  pub const fn new(checksumer: C) -> Self {
+    Self(checksumer)
+  }
+
+  /// Returns a reference to the inner checksumer.
+  #[inline]
+  pub const fn inner(&self) -> &C {
+    &self.0
+  }
+
+  /// Consumes the adapter and returns the inner checksumer.
+  #[inline]
+  pub fn into_inner(self) -> C {
+    self.0
+  }
+}
+
+impl<C: Checksumer> Truncate32<C> {
+  /// Returns the folded checksum as a `u32`.
+  #[inline]
+  pub fn digest32(&self) -> u32 {
+    fold32(self.0.digest())
+  }
+}
+
+impl<C: Checksumer> Checksumer for Truncate32<C> {
+  #[inline]
+  fn update(&mut self, buf: &[u8]) {
+    self.0.update(buf)
+  }
+
+  #[inline]
+  fn reset(&mut self) {
+    self.0.reset()
+  }
+
+  #[inline]
+  fn digest(&self) -> u64 {
+    self.digest32() as u64
+  }
+
+  #[inline]
+  fn parallelizable(&self) -> bool {
+    self.0.parallelizable()
+  }
+}
+
+impl<C: BuildChecksumer> BuildChecksumer for Truncate32<C> {
+  type Checksumer = Truncate32<C::Checksumer>;
+
+  #[inline]
+  fn build_checksumer(&self) -> Self::Checksumer {
+    Truncate32(self.0.build_checksumer())
+  }
+
+  #[inline]
+  fn checksum_one(&self, src: &[u8]) -> u64 {
+    fold32(self.0.checksum_one(src)) as u64
+  }
+}
+
+impl<C: crate::CheapClone> crate::CheapClone for Truncate32<C> {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::checksum::test_checksumer::Fnv;
+
+  #[test]
+  fn fold() {
+    assert_eq!(fold32(0), 0);
+    assert_eq!(fold32(u64::MAX), 0);
+    assert_eq!(fold32(0xffff_ffff), 0xffff_ffff);
+    assert_eq!(fold32(0xffff_ffff_0000_0000), 0xffff_ffff);
+    assert_eq!(fold32(0x0123_4567_89ab_cdef), 0x8888_8888);
+  }
+
+  #[test]
+  fn stable() {
+    let data = b"hello, world";
+    assert_eq!(Fnv::default().checksum_one(data), 0x17a1_a4f2_67be_633d);
+
+    let truncated = Truncate32::new(Fnv::default());
+    assert_eq!(truncated.checksum_one(data), 0x701f_c7cf);
+
+    let mut checksumer = truncated.build_checksumer();
+    checksumer.update(&data[..5]);
+    checksumer.update(&data[5..]);
+    assert_eq!(checksumer.digest32(), 0x701f_c7cf);
+    assert_eq!(checksumer.digest(), 0x701f_c7cf);
+
+    checksumer.reset();
+    checksumer.update(data);
+    assert_eq!(checksumer.digest32(), 0x701f_c7cf);
+  }
+}
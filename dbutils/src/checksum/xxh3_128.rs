@@ -0,0 +1,159 @@
+use xxhash_rust::xxh3::{xxh3_128_with_seed, Xxh3};
+
+use crate::error::{IncompleteBuffer, InsufficientBuffer};
+
+/// XxHash3 128-bit checksumer, for large values where a 64-bit checksum is not strong enough.
+///
+/// [`Checksumer`](super::Checksumer) produces `u64` digests, so this type has its own
+/// [`update`](XxHash3_128::update) and [`digest`](XxHash3_128::digest) methods producing a
+/// `u128` instead. Use [`encode_digest`](XxHash3_128::encode_digest) and
+/// [`decode_digest`](XxHash3_128::decode_digest) to store the digest next to a value pointer,
+/// as [`ENCODED_LEN`](XxHash3_128::ENCODED_LEN) little-endian bytes.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::checksum::XxHash3_128;
+///
+/// let mut hasher = XxHash3_128::new();
+/// hasher.update(b"hello, ");
+/// hasher.update(b"world");
+/// assert_eq!(hasher.digest(), XxHash3_128::new().checksum_one(b"hello, world"));
+///
+/// let mut buf = [0; XxHash3_128::ENCODED_LEN];
+/// XxHash3_128::encode_digest(hasher.digest(), &mut buf).unwrap();
+/// assert_eq!(XxHash3_128::decode_digest(&buf).unwrap(), hasher.digest());
+/// ```
+#[derive(Default, Clone)]
+pub struct XxHash3_128 {
+  seed: u64,
+  hasher: Xxh3,
+}
+
+impl XxHash3_128 {
+  /// The length of an encoded digest.
+  pub const ENCODED_LEN: usize = 16;
+
+  /// Create a new XxHash3_128 with seed 0.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      seed: 0,
+      hasher: Xxh3::new(),
+    }
+  }
+
+  /// Create a new XxHash3_128 with a seed.
+  #[inline]
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      hasher: Xxh3::with_seed(seed),
+    }
+  }
+
+  /// Adds chunk of data to checksum.
+  #[inline]
+  pub fn update(&mut self, buf: &[u8]) {
+    self.hasher.update(buf)
+  }
+
+  /// Resets state to initial state.
+  #[inline]
+  pub fn reset(&mut self) {
+    self.hasher.reset()
+  }
+
+  /// Finalize hashing.
+  #[inline]
+  pub fn digest(&self) -> u128 {
+    self.hasher.digest128()
+  }
+
+  /// Calculates the checksum of a byte slice, with the seed of this checksumer.
+  #[inline]
+  pub fn checksum_one(&self, src: &[u8]) -> u128 {
+    xxh3_128_with_seed(src, self.seed)
+  }
+
+  /// Encodes a digest into the buffer.
+  ///
+  /// Returns the number of bytes written, which is [`ENCODED_LEN`](XxHash3_128::ENCODED_LEN).
+  #[inline]
+  pub fn encode_digest(digest: u128, buf: &mut [u8]) -> Result<usize, InsufficientBuffer> {
+    if buf.len() < Self::ENCODED_LEN {
+      return Err(InsufficientBuffer::with_information(
+        Self::ENCODED_LEN as u64,
+        buf.len() as u64,
+      ));
+    }
+
+    buf[..Self::ENCODED_LEN].copy_from_slice(&digest.to_le_bytes());
+    Ok(Self::ENCODED_LEN)
+  }
+
+  /// Decodes a digest from the first [`ENCODED_LEN`](XxHash3_128::ENCODED_LEN) bytes of the
+  /// buffer.
+  #[inline]
+  pub fn decode_digest(src: &[u8]) -> Result<u128, IncompleteBuffer> {
+    match src.get(..Self::ENCODED_LEN) {
+      Some(bytes) => Ok(u128::from_le_bytes(bytes.try_into().unwrap())),
+      None => Err(IncompleteBuffer::with_information(
+        Self::ENCODED_LEN as u64,
+        src.len() as u64,
+      )),
+    }
+  }
+}
+
+impl crate::CheapClone for XxHash3_128 {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn vectors() {
+    let vectors: [(&[u8], u128); 3] = [
+      (b"", 0x99aa06d3014798d86001c324468d497f),
+      (b"a", 0xa96faf705af16834e6c632b61e964e1f),
+      (b"abc", 0x06b05ab6733a618578af5f94892f3950),
+    ];
+
+    for (src, expected) in vectors {
+      assert_eq!(XxHash3_128::new().checksum_one(src), expected);
+
+      let mut hasher = XxHash3_128::new();
+      hasher.update(src);
+      assert_eq!(hasher.digest(), expected);
+    }
+  }
+
+  #[test]
+  fn chunked() {
+    let data = (0..100_000u32)
+      .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+      .collect::<std::vec::Vec<_>>();
+
+    for seed in [0, 42] {
+      let one_shot = XxHash3_128::with_seed(seed).checksum_one(&data);
+      for chunk_size in [1, 7, 64, 240, 4096] {
+        let mut hasher = XxHash3_128::with_seed(seed);
+        for chunk in data.chunks(chunk_size) {
+          hasher.update(chunk);
+        }
+        assert_eq!(hasher.digest(), one_shot, "seed {seed}, chunk {chunk_size}");
+
+        hasher.reset();
+        hasher.update(&data);
+        assert_eq!(hasher.digest(), one_shot);
+      }
+    }
+
+    let mut buf = [0; XxHash3_128::ENCODED_LEN + 1];
+    assert!(XxHash3_128::encode_digest(1, &mut buf[..8]).is_err());
+    assert_eq!(XxHash3_128::encode_digest(u128::MAX - 1, &mut buf), Ok(16));
+    assert_eq!(XxHash3_128::decode_digest(&buf), Ok(u128::MAX - 1));
+    assert!(XxHash3_128::decode_digest(&buf[..15]).is_err());
+  }
+}
@@ -1 +1,4 @@
 pub use equivalent_flipped::*;
+
+mod versioned;
+pub use versioned::{VersionedKey, VersionedQuery};
@@ -0,0 +1,173 @@
+use core::cmp;
+
+use super::{Comparable, Equivalent};
+
+/// A key paired with a version, ordered by `key` ascending and then by
+/// `version` descending.
+///
+/// This is the usual layout for MVCC storage: all versions of a key are
+/// adjacent, and the newest version of a key comes first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VersionedKey<K> {
+  key: K,
+  version: u64,
+}
+
+impl<K> VersionedKey<K> {
+  /// Creates a new versioned key.
+  #[inline]
+  pub const fn new(key: K, version: u64) -> Self {
+    Self { key, version }
+  }
+
+  /// Returns the key.
+  #[inline]
+  pub const fn key(&self) -> &K {
+    &self.key
+  }
+
+  /// Returns the version.
+  #[inline]
+  pub const fn version(&self) -> u64 {
+    self.version
+  }
+
+  /// Consumes the versioned key and returns the key and the version.
+  #[inline]
+  pub fn into_components(self) -> (K, u64) {
+    (self.key, self.version)
+  }
+}
+
+impl<K> PartialOrd for VersionedKey<K>
+where
+  K: PartialOrd,
+{
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+    self
+      .key
+      .partial_cmp(&other.key)
+      .map(|o| o.then_with(|| other.version.cmp(&self.version)))
+  }
+}
+
+impl<K> Ord for VersionedKey<K>
+where
+  K: Ord,
+{
+  #[inline]
+  fn cmp(&self, other: &Self) -> cmp::Ordering {
+    self
+      .key
+      .cmp(&other.key)
+      .then_with(|| other.version.cmp(&self.version))
+  }
+}
+
+/// A borrowed query used to look up a [`VersionedKey`] without constructing an owned key.
+#[derive(Debug)]
+pub struct VersionedQuery<'a, Q: ?Sized> {
+  query: &'a Q,
+  version: u64,
+}
+
+impl<Q: ?Sized> Clone for VersionedQuery<'_, Q> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<Q: ?Sized> Copy for VersionedQuery<'_, Q> {}
+
+impl<'a, Q: ?Sized> VersionedQuery<'a, Q> {
+  /// Creates a new versioned query.
+  #[inline]
+  pub const fn new(query: &'a Q, version: u64) -> Self {
+    Self { query, version }
+  }
+
+  /// Returns the query.
+  #[inline]
+  pub const fn query(&self) -> &'a Q {
+    self.query
+  }
+
+  /// Returns the version.
+  #[inline]
+  pub const fn version(&self) -> u64 {
+    self.version
+  }
+}
+
+impl<Q, K> Equivalent<VersionedQuery<'_, Q>> for VersionedKey<K>
+where
+  K: Equivalent<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn equivalent(&self, key: &VersionedQuery<'_, Q>) -> bool {
+    Equivalent::equivalent(&self.key, key.query) && key.version == self.version
+  }
+}
+
+impl<Q, K> Comparable<VersionedQuery<'_, Q>> for VersionedKey<K>
+where
+  K: Comparable<Q>,
+  Q: ?Sized,
+{
+  #[inline]
+  fn compare(&self, key: &VersionedQuery<'_, Q>) -> cmp::Ordering {
+    Comparable::compare(&self.key, key.query).then_with(|| key.version.cmp(&self.version))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ordering() {
+    let a1 = VersionedKey::new(1u32, 1);
+    let a2 = VersionedKey::new(1u32, 2);
+    let b1 = VersionedKey::new(2u32, 1);
+
+    // newer versions of the same key sort first
+    assert!(a2 < a1);
+    assert!(a1 < b1);
+    assert!(a2 < b1);
+    assert_eq!(a1.cmp(&VersionedKey::new(1, 1)), cmp::Ordering::Equal);
+    assert_eq!(a1.partial_cmp(&a2), Some(cmp::Ordering::Greater));
+
+    let mut keys = [b1, a1, a2];
+    keys.sort();
+    assert_eq!(keys, [a2, a1, b1]);
+  }
+
+  #[test]
+  fn query() {
+    let key = VersionedKey::new(std::string::String::from("a"), 2);
+
+    assert!(key.equivalent(&VersionedQuery::new("a", 2)));
+    assert!(!key.equivalent(&VersionedQuery::new("a", 1)));
+    assert!(!key.equivalent(&VersionedQuery::new("b", 2)));
+
+    assert_eq!(
+      key.compare(&VersionedQuery::new("a", 2)),
+      cmp::Ordering::Equal
+    );
+    assert_eq!(
+      key.compare(&VersionedQuery::new("a", 1)),
+      cmp::Ordering::Less
+    );
+    assert_eq!(
+      key.compare(&VersionedQuery::new("a", 3)),
+      cmp::Ordering::Greater
+    );
+    assert_eq!(
+      key.compare(&VersionedQuery::new("b", 0)),
+      cmp::Ordering::Less
+    );
+  }
+}
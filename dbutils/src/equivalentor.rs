@@ -1,12 +1,21 @@
 mod ascend;
 pub use ascend::*;
 
+mod ascii;
+pub use ascii::*;
+
 mod bytes;
 pub use bytes::*;
 
+mod concat;
+pub use concat::*;
+
 mod descend;
 pub use descend::*;
 
+mod func;
+pub use func::*;
+
 mod reverse;
 pub use reverse::*;
 
@@ -186,6 +186,11 @@ where
   fn compare(&self, a: &A, b: &A) -> cmp::Ordering {
     <Ascend as StaticComparator<A>>::compare(a, b)
   }
+
+  #[inline]
+  fn is_ascend(&self) -> bool {
+    true
+  }
 }
 
 impl<Q, A> QueryComparator<A, Q> for Ascend
@@ -0,0 +1,185 @@
+use core::cmp;
+
+use cheap_clone::CheapClone;
+
+use super::{
+  Comparator, Equivalentor, QueryComparator, QueryEquivalentor, StaticBytesComparator,
+  StaticBytesEquivalentor, StaticComparator, StaticEquivalentor, StaticQueryComparator,
+  StaticQueryEquivalentor,
+};
+
+/// A comparator that compares bytes lexicographically, ignoring ASCII case.
+///
+/// Every byte is mapped with [`u8::to_ascii_lowercase`] before comparing, so non-ASCII
+/// bytes are compared verbatim. The comparator works for any type which can be viewed
+/// as bytes, e.g. `[u8]`, `&[u8]`, `str` or `Vec<u8>`.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::equivalentor::{AsciiCaseInsensitive, Comparator, Equivalentor};
+///
+/// let cmp = AsciiCaseInsensitive::new();
+/// assert!(cmp.equivalent("ABC", "abc"));
+/// assert!(cmp.compare("abc", "ABD").is_lt());
+/// ```
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AsciiCaseInsensitive;
+
+impl AsciiCaseInsensitive {
+  /// Create a new AsciiCaseInsensitive.
+  #[inline]
+  pub const fn new() -> Self {
+    Self
+  }
+}
+
+impl CheapClone for AsciiCaseInsensitive {}
+
+#[inline]
+fn compare_ignore_ascii_case(a: &[u8], b: &[u8]) -> cmp::Ordering {
+  a.iter()
+    .map(u8::to_ascii_lowercase)
+    .cmp(b.iter().map(u8::to_ascii_lowercase))
+}
+
+impl StaticBytesEquivalentor for AsciiCaseInsensitive {
+  #[inline]
+  fn equivalent(a: &[u8], b: &[u8]) -> bool {
+    a.eq_ignore_ascii_case(b)
+  }
+}
+
+impl StaticBytesComparator for AsciiCaseInsensitive {
+  #[inline]
+  fn compare(a: &[u8], b: &[u8]) -> cmp::Ordering {
+    compare_ignore_ascii_case(a, b)
+  }
+}
+
+impl<A> StaticEquivalentor<A> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn equivalent(a: &A, b: &A) -> bool {
+    a.as_ref().eq_ignore_ascii_case(b.as_ref())
+  }
+}
+
+impl<A, Q> StaticQueryEquivalentor<A, Q> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+  Q: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn query_equivalent(a: &A, b: &Q) -> bool {
+    a.as_ref().eq_ignore_ascii_case(b.as_ref())
+  }
+}
+
+impl<A> StaticComparator<A> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn compare(a: &A, b: &A) -> cmp::Ordering {
+    compare_ignore_ascii_case(a.as_ref(), b.as_ref())
+  }
+}
+
+impl<A, Q> StaticQueryComparator<A, Q> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+  Q: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn query_compare(a: &A, b: &Q) -> cmp::Ordering {
+    compare_ignore_ascii_case(a.as_ref(), b.as_ref())
+  }
+}
+
+impl<A> Equivalentor<A> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn equivalent(&self, a: &A, b: &A) -> bool {
+    <Self as StaticEquivalentor<A>>::equivalent(a, b)
+  }
+}
+
+impl<A, Q> QueryEquivalentor<A, Q> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+  Q: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn query_equivalent(&self, a: &A, b: &Q) -> bool {
+    <Self as StaticQueryEquivalentor<A, Q>>::query_equivalent(a, b)
+  }
+}
+
+impl<A> Comparator<A> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn compare(&self, a: &A, b: &A) -> cmp::Ordering {
+    <Self as StaticComparator<A>>::compare(a, b)
+  }
+}
+
+impl<A, Q> QueryComparator<A, Q> for AsciiCaseInsensitive
+where
+  A: AsRef<[u8]> + ?Sized,
+  Q: AsRef<[u8]> + ?Sized,
+{
+  #[inline]
+  fn query_compare(&self, a: &A, b: &Q) -> cmp::Ordering {
+    <Self as StaticQueryComparator<A, Q>>::query_compare(a, b)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn ignore_case() {
+    let cmp = AsciiCaseInsensitive::new();
+    assert!(cmp.equivalent(b"ABC".as_slice(), b"abc".as_slice()));
+    assert!(cmp.compare(b"ABC".as_slice(), b"abc".as_slice()).is_eq());
+    assert!(cmp.query_equivalent("aBc", "AbC"));
+    assert!(cmp.query_compare("aBc", "AbC").is_eq());
+    assert!(<AsciiCaseInsensitive as StaticBytesEquivalentor>::equivalent(b"Abc", b"aBC"));
+    assert!(!cmp.equivalent("abc", "abd"));
+  }
+
+  #[test]
+  fn lexicographic() {
+    let cmp = AsciiCaseInsensitive::new();
+    assert!(cmp.compare("abc", "ABD").is_lt());
+    assert!(cmp.compare("ABD", "abc").is_gt());
+    assert!(cmp.compare("ab", "ABC").is_lt());
+    assert!(cmp.compare("", "a").is_lt());
+    assert!(<AsciiCaseInsensitive as StaticBytesComparator>::compare(b"B", b"a").is_gt());
+
+    let mut keys = ["b", "C", "a", "B", "A", "c"];
+    keys.sort_by(|a, b| cmp.compare(*a, *b));
+    assert!(keys.windows(2).all(|w| cmp.compare(w[0], w[1]).is_le()));
+    assert!(keys[..2].iter().all(|k| cmp.equivalent(*k, "a")));
+  }
+
+  #[test]
+  fn non_ascii() {
+    let cmp = AsciiCaseInsensitive::new();
+    // non-ASCII bytes are compared verbatim.
+    assert!(!cmp.equivalent("É", "é"));
+    assert!(cmp.equivalent([0xC3u8, b'A'].as_slice(), [0xC3u8, b'a'].as_slice()));
+    assert!(cmp
+      .compare([0x80u8].as_slice(), [0x7Fu8].as_slice())
+      .is_gt());
+    assert!(cmp.compare([0xFFu8].as_slice(), b"z".as_slice()).is_gt());
+  }
+}
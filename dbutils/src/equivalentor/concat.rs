@@ -0,0 +1,194 @@
+use core::cmp;
+
+use cheap_clone::CheapClone;
+
+use super::{Comparator, Equivalentor};
+
+/// Splits an encoded composite key into `N` segments, for [`Concat2`] and [`Concat3`].
+///
+/// Implemented by [`Offsets`] and by closures returning `[&[u8]; N]`.
+pub trait Split<const N: usize> {
+  /// Splits the key into its segments.
+  fn split<'a>(&self, key: &'a [u8]) -> [&'a [u8]; N];
+}
+
+impl<F, const N: usize> Split<N> for F
+where
+  F: Fn(&[u8]) -> [&[u8]; N],
+{
+  #[inline]
+  fn split<'a>(&self, key: &'a [u8]) -> [&'a [u8]; N] {
+    self(key)
+  }
+}
+
+/// Splits a key at fixed offsets, e.g. `Offsets([4])` splits a key into the first 4 bytes
+/// and the rest.
+///
+/// The offsets must be ascending. Offsets past the end of a key are clamped to its length, so
+/// the trailing segments of a short key are empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Offsets<const M: usize>(pub [usize; M]);
+
+impl<const M: usize> CheapClone for Offsets<M> {}
+
+impl Split<2> for Offsets<1> {
+  #[inline]
+  fn split<'a>(&self, key: &'a [u8]) -> [&'a [u8]; 2] {
+    let (a, b) = key.split_at(self.0[0].min(key.len()));
+    [a, b]
+  }
+}
+
+impl Split<3> for Offsets<2> {
+  #[inline]
+  fn split<'a>(&self, key: &'a [u8]) -> [&'a [u8]; 3] {
+    let (a, rest) = key.split_at(self.0[0].min(key.len()));
+    let (b, c) = rest.split_at(self.0[1].saturating_sub(self.0[0]).min(rest.len()));
+    [a, b, c]
+  }
+}
+
+macro_rules! concat_comparator {
+  ($(#[$meta:meta])* $name:ident<$n:literal> { $($field:ident: $c:ident = $idx:literal),+ $(,)? }) => {
+    $(#[$meta])*
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct $name<S, $($c),+> {
+      splitter: S,
+      $($field: $c,)+
+    }
+
+    impl<S, $($c),+> $name<S, $($c),+> {
+      #[doc = concat!("Creates a new `", stringify!($name), "` from a splitter and the comparators of the segments, in order.")]
+      #[inline]
+      pub const fn new(splitter: S, $($field: $c),+) -> Self {
+        Self { splitter, $($field),+ }
+      }
+    }
+
+    impl<F, $($c),+> $name<F, $($c),+>
+    where
+      F: Fn(&[u8]) -> [&[u8]; $n],
+    {
+      #[doc = concat!("Creates a new `", stringify!($name), "` from a splitter closure and the comparators of the segments, in order.")]
+      ///
+      /// Unlike `new`, this pins the signature of the closure, so the segments can borrow from
+      /// the key without annotating lifetimes.
+      #[inline]
+      pub const fn from_fn(splitter: F, $($field: $c),+) -> Self {
+        Self { splitter, $($field),+ }
+      }
+    }
+
+    impl<S: CheapClone, $($c: CheapClone),+> CheapClone for $name<S, $($c),+> {}
+
+    impl<S, $($c),+> Equivalentor<[u8]> for $name<S, $($c),+>
+    where
+      S: Split<$n>,
+      $($c: Equivalentor<[u8]>,)+
+    {
+      #[inline]
+      fn equivalent(&self, a: &[u8], b: &[u8]) -> bool {
+        let (a, b) = (self.splitter.split(a), self.splitter.split(b));
+        $(self.$field.equivalent(a[$idx], b[$idx]))&&+
+      }
+    }
+
+    impl<S, $($c),+> Comparator<[u8]> for $name<S, $($c),+>
+    where
+      S: Split<$n>,
+      $($c: Comparator<[u8]>,)+
+    {
+      #[inline]
+      fn compare(&self, a: &[u8], b: &[u8]) -> cmp::Ordering {
+        let (a, b) = (self.splitter.split(a), self.splitter.split(b));
+        cmp::Ordering::Equal
+          $(.then_with(|| self.$field.compare(a[$idx], b[$idx])))+
+      }
+    }
+  };
+}
+
+concat_comparator!(
+  /// A comparator for composite keys of two concatenated fields, which compares the fields
+  /// one by one with their own comparators and returns the first non-equal result.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use dbutils::equivalentor::{Ascend, Comparator, Concat2, Descend, Offsets};
+  ///
+  /// // (u32 ascending, u32 descending), big-endian encoded.
+  /// let cmp = Concat2::new(Offsets([4]), Ascend, Descend);
+  /// let key = |a: u32, b: u32| [a.to_be_bytes(), b.to_be_bytes()].concat();
+  ///
+  /// assert!(cmp.compare(&key(1, 9)[..], &key(2, 0)[..]).is_lt());
+  /// assert!(cmp.compare(&key(1, 9)[..], &key(1, 0)[..]).is_lt());
+  /// ```
+  Concat2<2> { first: C1 = 0, second: C2 = 1 }
+);
+
+concat_comparator!(
+  /// A comparator for composite keys of three concatenated fields, which compares the fields
+  /// one by one with their own comparators and returns the first non-equal result.
+  ///
+  /// See [`Concat2`].
+  Concat3<3> { first: C1 = 0, second: C2 = 1, third: C3 = 2 }
+);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::equivalentor::{Ascend, Descend};
+  use std::vec::Vec;
+
+  fn key(a: u32, b: u32) -> Vec<u8> {
+    [a.to_be_bytes(), b.to_be_bytes()].concat()
+  }
+
+  fn compare<C: Comparator<[u8]>>(cmp: &C, a: &[u8], b: &[u8]) -> cmp::Ordering {
+    cmp.compare(a, b)
+  }
+
+  #[test]
+  fn asc_desc_composite() {
+    let cmp = Concat2::new(Offsets([4]), Ascend, Descend);
+
+    let mut keys = [key(2, 1), key(1, 1), key(1, 3), key(2, 5), key(1, 2)];
+    keys.sort_by(|a, b| compare(&cmp, a, b));
+    assert_eq!(
+      keys,
+      [key(1, 3), key(1, 2), key(1, 1), key(2, 5), key(2, 1)]
+    );
+
+    assert!(cmp.equivalent(&key(7, 7)[..], &key(7, 7)[..]));
+    assert!(!cmp.equivalent(&key(7, 7)[..], &key(7, 8)[..]));
+
+    let closure = Concat2::from_fn(
+      |k: &[u8]| {
+        let (a, b) = k.split_at(4);
+        [a, b]
+      },
+      Ascend,
+      Descend,
+    );
+    for (a, b) in keys.iter().zip(keys.iter().skip(1)) {
+      assert_eq!(compare(&closure, a, b), compare(&cmp, a, b));
+      assert!(compare(&closure, a, b).is_lt());
+    }
+  }
+
+  #[test]
+  fn three_fields() {
+    let cmp = Concat3::new(Offsets([1, 3]), Descend, Ascend, Descend);
+
+    assert!(compare(&cmp, b"\x02ab1", b"\x01ab1").is_lt());
+    assert!(compare(&cmp, b"\x01aa9", b"\x01ab1").is_lt());
+    assert!(compare(&cmp, b"\x01ab9", b"\x01ab1").is_lt());
+    assert!(compare(&cmp, b"\x01ab", b"\x01ab1").is_gt());
+    assert!(cmp.equivalent(&b"\x01ab1"[..], b"\x01ab1"));
+
+    // offsets past the end of a key yield empty segments.
+    assert_eq!(Offsets([1, 3]).split(b"\x01"), [&b"\x01"[..], b"", b""]);
+  }
+}
@@ -0,0 +1,85 @@
+use core::cmp;
+
+use cheap_clone::CheapClone;
+
+use super::{Comparator, Equivalentor};
+
+/// Any comparator that orders items by the wrapped closure.
+///
+/// Equality is derived from the closure, two items are equivalent if the closure returns [`Ordering::Equal`](cmp::Ordering::Equal).
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::equivalentor::{Comparator, ComparatorFn};
+///
+/// let cmp = ComparatorFn(|a: &u32, b: &u32| a.cmp(b).reverse());
+/// assert!(cmp.compare(&1, &2).is_gt());
+/// ```
+#[derive(Clone, Copy)]
+pub struct ComparatorFn<F>(pub F);
+
+impl<F> ComparatorFn<F> {
+  /// Create a new `ComparatorFn` from the given closure.
+  #[inline]
+  pub const fn new(f: F) -> Self {
+    Self(f)
+  }
+}
+
+impl<F: CheapClone> CheapClone for ComparatorFn<F> {}
+
+impl<F> core::fmt::Debug for ComparatorFn<F> {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("ComparatorFn").finish()
+  }
+}
+
+impl<A, F> Equivalentor<A> for ComparatorFn<F>
+where
+  A: ?Sized,
+  F: Fn(&A, &A) -> cmp::Ordering,
+{
+  #[inline]
+  fn equivalent(&self, a: &A, b: &A) -> bool {
+    (self.0)(a, b).is_eq()
+  }
+}
+
+impl<A, F> Comparator<A> for ComparatorFn<F>
+where
+  A: ?Sized,
+  F: Fn(&A, &A) -> cmp::Ordering,
+{
+  #[inline]
+  fn compare(&self, a: &A, b: &A) -> cmp::Ordering {
+    (self.0)(a, b)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn low_byte_order() {
+    let cmp = ComparatorFn(|a: &u32, b: &u32| (a & 0xFF).cmp(&(b & 0xFF)));
+
+    assert!(cmp.compare(&0x100, &0x01).is_lt());
+    assert!(cmp.compare(&0x1FF, &0x02).is_gt());
+    assert!(cmp.equivalent(&0x101, &0x201));
+    assert!(!cmp.equivalent(&0x101, &0x102));
+
+    let mut nums = [0x103u32, 0x2, 0x201, 0x300];
+    nums.sort_by(|a, b| cmp.compare(a, b));
+    assert_eq!(nums, [0x300, 0x201, 0x2, 0x103]);
+  }
+
+  #[test]
+  fn send_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let cmp = ComparatorFn(|a: &u32, b: &u32| a.cmp(b));
+    assert_send_sync(&cmp);
+  }
+}
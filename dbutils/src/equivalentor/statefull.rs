@@ -93,6 +93,16 @@ where
 pub trait Comparator<T: ?Sized>: Equivalentor<T> {
   /// Compare `a` to `b` and return their ordering.
   fn compare(&self, a: &T, b: &T) -> cmp::Ordering;
+
+  /// Returns `true` if the comparator orders items by their [`Ord`] implementation, like
+  /// [`Ascend`](super::Ascend).
+  ///
+  /// Callers may then replace [`compare`](Comparator::compare) with a cheaper comparison which
+  /// agrees with [`Ord`], e.g. comparing the encoded bytes of the items.
+  #[inline]
+  fn is_ascend(&self) -> bool {
+    false
+  }
 }
 
 impl<T, C> Comparator<T> for &C
@@ -103,6 +113,11 @@ where
   fn compare(&self, a: &T, b: &T) -> cmp::Ordering {
     C::compare(self, a, b)
   }
+
+  #[inline]
+  fn is_ascend(&self) -> bool {
+    C::is_ascend(self)
+  }
 }
 
 /// `RangeComparator` is implemented as an extention to [`Comparator`] to
@@ -430,6 +445,11 @@ const _: () = {
           {
             (**self).compare(a, b)
           }
+
+          #[inline]
+          fn is_ascend(&self) -> bool {
+            (**self).is_ascend()
+          }
         }
 
         impl<'a, T, C> TypeRefComparator<'a, T> for $ty
@@ -123,3 +123,222 @@ impl core::fmt::Display for IncompleteBuffer {
 }
 
 impl core::error::Error for IncompleteBuffer {}
+
+/// Returned when decoding a scalar enum generated by [`impl_scalar_enum_type!`](crate::impl_scalar_enum_type) fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeEnumError {
+  /// The buffer did not contain a valid LEB128 encoded discriminant.
+  Varint(crate::leb128::DecodeVarintError),
+  /// The decoded discriminant does not match any variant of the enum.
+  UnknownDiscriminant(u64),
+}
+
+impl From<crate::leb128::DecodeVarintError> for DecodeEnumError {
+  #[inline]
+  fn from(e: crate::leb128::DecodeVarintError) -> Self {
+    Self::Varint(e)
+  }
+}
+
+impl core::fmt::Display for DecodeEnumError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Varint(_) => write!(f, "failed to decode the enum discriminant"),
+      Self::UnknownDiscriminant(d) => write!(f, "unknown enum discriminant: {d}"),
+    }
+  }
+}
+
+impl core::error::Error for DecodeEnumError {
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::Varint(e) => Some(e),
+      Self::UnknownDiscriminant(_) => None,
+    }
+  }
+}
+
+/// A unified error for decoding records, so a decoder can use `?` across the varint,
+/// buffer and fixed-width decoding helpers of this crate.
+#[derive(Debug, Clone)]
+pub enum DecodeError {
+  /// A LEB128 encoded value could not be decoded.
+  Varint(crate::leb128::DecodeVarintError),
+  /// The buffer did not contain enough bytes to decode a value.
+  IncompleteBuffer(IncompleteBuffer),
+  /// The buffer did not have enough space to hold a value.
+  InsufficientBuffer(InsufficientBuffer),
+  /// A slice did not have the length of the fixed-width value being decoded.
+  TryFromSlice(core::array::TryFromSliceError),
+  /// A scalar enum could not be decoded.
+  Enum(DecodeEnumError),
+}
+
+impl From<crate::leb128::DecodeVarintError> for DecodeError {
+  #[inline]
+  fn from(e: crate::leb128::DecodeVarintError) -> Self {
+    Self::Varint(e)
+  }
+}
+
+impl From<IncompleteBuffer> for DecodeError {
+  #[inline]
+  fn from(e: IncompleteBuffer) -> Self {
+    Self::IncompleteBuffer(e)
+  }
+}
+
+impl From<InsufficientBuffer> for DecodeError {
+  #[inline]
+  fn from(e: InsufficientBuffer) -> Self {
+    Self::InsufficientBuffer(e)
+  }
+}
+
+impl From<core::array::TryFromSliceError> for DecodeError {
+  #[inline]
+  fn from(e: core::array::TryFromSliceError) -> Self {
+    Self::TryFromSlice(e)
+  }
+}
+
+impl From<DecodeEnumError> for DecodeError {
+  #[inline]
+  fn from(e: DecodeEnumError) -> Self {
+    Self::Enum(e)
+  }
+}
+
+impl core::fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Varint(_) => write!(f, "failed to decode a varint"),
+      Self::IncompleteBuffer(_) => write!(f, "failed to read from the buffer"),
+      Self::InsufficientBuffer(_) => write!(f, "failed to write to the buffer"),
+      Self::TryFromSlice(_) => write!(f, "failed to decode a fixed-width value"),
+      Self::Enum(_) => write!(f, "failed to decode an enum"),
+    }
+  }
+}
+
+impl core::error::Error for DecodeError {
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::Varint(e) => Some(e),
+      Self::IncompleteBuffer(e) => Some(e),
+      Self::InsufficientBuffer(e) => Some(e),
+      Self::TryFromSlice(e) => Some(e),
+      Self::Enum(e) => Some(e),
+    }
+  }
+}
+
+/// Returned when encoding a [`Path`](std::path::Path) fails.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodePathError {
+  /// The buffer did not have enough space to hold the path.
+  InsufficientBuffer(InsufficientBuffer),
+  /// The path is not valid UTF-8, on platforms whose paths cannot be encoded as raw bytes.
+  NonUtf8,
+}
+
+#[cfg(feature = "std")]
+impl From<InsufficientBuffer> for EncodePathError {
+  #[inline]
+  fn from(e: InsufficientBuffer) -> Self {
+    Self::InsufficientBuffer(e)
+  }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for EncodePathError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::InsufficientBuffer(_) => write!(f, "failed to encode the path"),
+      Self::NonUtf8 => write!(f, "path is not valid UTF-8"),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for EncodePathError {
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::InsufficientBuffer(e) => Some(e),
+      Self::NonUtf8 => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::leb128::{decode_u64_varint, encode_u64_varint, DecodeVarintError};
+
+  // record layout: varint key length | key | u32 le value
+  fn decode_record(buf: &[u8]) -> Result<(&[u8], u32), DecodeError> {
+    let (read, len) = decode_u64_varint(buf)?;
+    let rest = &buf[read..];
+    let len = len as usize;
+    if rest.len() < len {
+      return Err(IncompleteBuffer::with_information(len as u64, rest.len() as u64).into());
+    }
+    let (key, rest) = rest.split_at(len);
+    let value = <[u8; 4]>::try_from(rest)?;
+    Ok((key, u32::from_le_bytes(value)))
+  }
+
+  fn encode_record(key: &[u8], value: u32, buf: &mut [u8]) -> Result<usize, DecodeError> {
+    let written = encode_u64_varint(key.len() as u64, buf)?;
+    let end = written + key.len() + 4;
+    if buf.len() < end {
+      return Err(InsufficientBuffer::with_information(end as u64, buf.len() as u64).into());
+    }
+    buf[written..written + key.len()].copy_from_slice(key);
+    buf[written + key.len()..end].copy_from_slice(&value.to_le_bytes());
+    Ok(end)
+  }
+
+  #[test]
+  fn decode_error_conversions() {
+    let mut buf = [0u8; 16];
+    let len = encode_record(b"key", 7, &mut buf).unwrap();
+    assert_eq!(decode_record(&buf[..len]).unwrap(), (&b"key"[..], 7));
+
+    // truncated varint
+    assert!(matches!(
+      decode_record(&[0x80]),
+      Err(DecodeError::Varint(DecodeVarintError::IncompleteBuffer(_)))
+    ));
+    // overlong varint
+    assert!(matches!(
+      decode_record(&[0xff; 11]),
+      Err(DecodeError::Varint(DecodeVarintError::Overflow))
+    ));
+    // key shorter than its length prefix
+    let err = decode_record(&[5, b'k']).unwrap_err();
+    assert!(matches!(err, DecodeError::IncompleteBuffer(ref e) if e.required() == Some(5)));
+    // value is not 4 bytes
+    assert!(matches!(
+      decode_record(&buf[..len - 1]),
+      Err(DecodeError::TryFromSlice(_))
+    ));
+    // encoding into a buffer too small
+    assert!(matches!(
+      encode_record(b"key", 7, &mut buf[..4]),
+      Err(DecodeError::InsufficientBuffer(_))
+    ));
+    assert!(matches!(
+      encode_record(b"key", 7, &mut []),
+      Err(DecodeError::InsufficientBuffer(_))
+    ));
+
+    // the inner error is reported through the source, not repeated in the message.
+    let err = DecodeError::from(DecodeEnumError::UnknownDiscriminant(9));
+    assert_eq!(std::format!("{err}"), "failed to decode an enum");
+    let source = core::error::Error::source(&err).unwrap();
+    assert_eq!(std::format!("{source}"), "unknown enum discriminant: 9");
+  }
+}
@@ -79,17 +79,10 @@ macro_rules! encode_varint {
 /// The returned value will be between 1 and 19, inclusive.
 #[inline]
 pub const fn encoded_u128_varint_len(value: u128) -> usize {
-  // Each byte in LEB128 encoding can hold 7 bits of data
-  // We want to find how many groups of 7 bits are needed
-  // Special case for 0 and small numbers
-  if value < 128 {
-    return 1;
-  }
-
-  // Calculate position of highest set bit
-  let highest_bit = 128 - value.leading_zeros();
-  // Convert to number of LEB128 bytes needed
-  // Each byte holds 7 bits, but we need to round up
+  // Each byte in LEB128 encoding can hold 7 bits of data, so round the number of
+  // significant bits up to a multiple of 7. (value | 1) has at least one significant
+  // bit, so 0 takes 1 byte without a branch.
+  let highest_bit = 128 - (value | 1).leading_zeros();
   ((highest_bit + 6) / 7) as usize
 }
 
@@ -262,6 +255,9 @@ pub const fn decode_u64_varint(buf: &[u8]) -> Result<(usize, u64), DecodeVarintE
 ///
 /// * Returns [`DecodeVarintError`] if the buffer did not contain a valid LEB128 encoding
 ///   or the decode buffer did not contain enough bytes to decode a value.
+///
+/// Values written by [`encode_u64_varint`] decode here as well, and a value greater than
+/// [`u32::MAX`] returns [`DecodeVarintError::Overflow`] instead of being truncated.
 pub const fn decode_u32_varint(buf: &[u8]) -> Result<(usize, u32), DecodeVarintError> {
   decode_varint!(|buf| u32::MAX_U32_LEB128)
 }
@@ -350,12 +346,246 @@ pub fn decode_i128_varint(buf: &[u8]) -> Result<(usize, i128), DecodeVarintError
   Ok((bytes_read, value))
 }
 
+/// Decodes all of the LEB128 encoded `u32`s in `buf` and appends them to `out`.
+///
+/// Runs of single byte varints are decoded eight bytes at a time, and multi-byte varints
+/// fall back to [`decode_u32_varint`], so the result is exactly the same as decoding `buf`
+/// one varint after another.
+///
+/// # Returns
+///
+/// * Returns the bytes readed, which is always `buf.len()`, if successful.
+///
+/// * Returns [`DecodeVarintError`] if the buffer did not contain a valid LEB128 encoding
+///   or the last varint was incomplete. The values decoded before the error are kept in `out`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+pub fn decode_u32_varints_batch(
+  buf: &[u8],
+  out: &mut ::std::vec::Vec<u32>,
+) -> Result<usize, DecodeVarintError> {
+  const CONTINUATION_BITS: u64 = 0x8080_8080_8080_8080;
+
+  let mut offset = 0;
+  while offset < buf.len() {
+    if let Some(chunk) = buf.get(offset..offset + 8) {
+      let word = u64::from_le_bytes(chunk.try_into().unwrap());
+      // none of the 8 bytes has the continuation bit, so each byte is a varint.
+      if word & CONTINUATION_BITS == 0 {
+        out.extend(chunk.iter().map(|b| *b as u32));
+        offset += 8;
+        continue;
+      }
+    }
+
+    let (read, value) = decode_u32_varint(&buf[offset..])?;
+    out.push(value);
+    offset += read;
+  }
+
+  Ok(offset)
+}
+
+/// The maximum number of bytes a group of four `u32`s takes in group varint format.
+pub const MAX_GROUP_VARINT_U32_LEN: usize = 1 + 4 * 4;
+
+#[inline]
+const fn group_varint_byte_len(value: u32) -> usize {
+  4 - ((value | 1).leading_zeros() / 8) as usize
+}
+
+/// Returns the number of bytes the group of four `u32`s takes in group varint format.
+#[inline]
+pub const fn encoded_group_varint_u32_len(values: &[u32; 4]) -> usize {
+  1 + group_varint_byte_len(values[0])
+    + group_varint_byte_len(values[1])
+    + group_varint_byte_len(values[2])
+    + group_varint_byte_len(values[3])
+}
+
+/// Encodes a group of four `u32`s into group varint format, and writes it to the buffer.
+///
+/// The format is a control byte followed by each value in little-endian, using only as many
+/// bytes as the value needs (1 to 4). Each value has 2 bits in the control byte holding its
+/// byte length minus one, starting from the least significant bits for the first value.
+///
+/// Returns the number of bytes written.
+pub fn encode_group_varint_u32(
+  values: &[u32; 4],
+  buf: &mut [u8],
+) -> Result<usize, InsufficientBuffer> {
+  let len = encoded_group_varint_u32_len(values);
+  if buf.len() < len {
+    return Err(InsufficientBuffer::with_information(
+      len as u64,
+      buf.len() as u64,
+    ));
+  }
+
+  let mut control = 0u8;
+  let mut offset = 1;
+  for (i, value) in values.iter().enumerate() {
+    let n = group_varint_byte_len(*value);
+    control |= ((n - 1) as u8) << (i * 2);
+    buf[offset..offset + n].copy_from_slice(&value.to_le_bytes()[..n]);
+    offset += n;
+  }
+  buf[0] = control;
+
+  Ok(len)
+}
+
+/// Decodes a group of four `u32`s from group varint format.
+///
+/// # Returns
+///
+/// * Returns the bytes readed and the decoded values if successful.
+///
+/// * Returns [`DecodeVarintError::IncompleteBuffer`] if the buffer did not contain the whole group.
+pub fn decode_group_varint_u32(buf: &[u8]) -> Result<(usize, [u32; 4]), DecodeVarintError> {
+  let Some(&control) = buf.first() else {
+    return Err(DecodeVarintError::IncompleteBuffer(
+      IncompleteBuffer::with_information(1, 0),
+    ));
+  };
+
+  let len = 1
+    + (control & 0b11) as usize
+    + ((control >> 2) & 0b11) as usize
+    + ((control >> 4) & 0b11) as usize
+    + ((control >> 6) & 0b11) as usize
+    + 4;
+  if buf.len() < len {
+    return Err(DecodeVarintError::IncompleteBuffer(
+      IncompleteBuffer::with_information(len as u64, buf.len() as u64),
+    ));
+  }
+
+  let mut values = [0u32; 4];
+  let mut offset = 1;
+  for (i, value) in values.iter_mut().enumerate() {
+    let n = ((control >> (i * 2)) & 0b11) as usize + 1;
+    let mut bytes = [0u8; 4];
+    bytes[..n].copy_from_slice(&buf[offset..offset + n]);
+    *value = u32::from_le_bytes(bytes);
+    offset += n;
+  }
+
+  Ok((len, values))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
   use rstest::*;
 
+  /// Counts the bytes written by the LEB128 encoding loop.
+  fn reference_varint_len(mut value: u128) -> usize {
+    let mut len = 1;
+    while value >= 0x80 {
+      value >>= 7;
+      len += 1;
+    }
+    len
+  }
+
+  #[test]
+  fn test_encoded_varint_len_boundaries() {
+    let mut values = std::vec![0u128, u64::MAX as u128, u128::MAX];
+    for shift in 0..128 {
+      let pow = 1u128 << shift;
+      values.extend([pow - 1, pow, pow + 1]);
+    }
+    // every length boundary, 2^(7k) - 1 and 2^(7k)
+    for k in 1..=18 {
+      values.extend([(1u128 << (7 * k)) - 1, 1u128 << (7 * k)]);
+    }
+
+    for value in values {
+      let expected = reference_varint_len(value);
+      assert_eq!(encoded_u128_varint_len(value), expected, "u128 {value}");
+
+      if let Ok(value) = u64::try_from(value) {
+        assert_eq!(encoded_u64_varint_len(value), expected, "u64 {value}");
+        let mut buf = [0u8; MAX_U64_LEB128];
+        assert_eq!(encode_u64_varint(value, &mut buf).unwrap(), expected);
+      }
+
+      if let Ok(value) = u32::try_from(value) {
+        assert_eq!(encoded_u32_varint_len(value), expected, "u32 {value}");
+      }
+    }
+  }
+
+  #[test]
+  fn test_group_varint_known_vectors() {
+    let cases: &[([u32; 4], &[u8])] = &[
+      ([0, 0, 0, 0], &[0x00, 0, 0, 0, 0]),
+      (
+        [1, 256, 65536, 16777216],
+        &[0xe4, 1, 0, 1, 0, 0, 1, 0, 0, 0, 1],
+      ),
+      (
+        [0x01020304, 0xff, 0x0100, 0],
+        &[0x13, 4, 3, 2, 1, 0xff, 0, 1, 0],
+      ),
+      ([u32::MAX; 4], &[0xff; MAX_GROUP_VARINT_U32_LEN]),
+    ];
+
+    for (values, encoded) in cases {
+      let mut buf = [0u8; MAX_GROUP_VARINT_U32_LEN];
+      let written = encode_group_varint_u32(values, &mut buf).unwrap();
+      assert_eq!(written, encoded_group_varint_u32_len(values));
+      assert_eq!(&buf[..written], *encoded);
+      assert_eq!(
+        decode_group_varint_u32(encoded).unwrap(),
+        (encoded.len(), *values)
+      );
+    }
+  }
+
+  #[test]
+  fn test_group_varint_roundtrip_mixed_widths() {
+    let widths = [
+      0u32,
+      0x7f,
+      0xff,
+      0x100,
+      0xffff,
+      0x10000,
+      0xff_ffff,
+      0x100_0000,
+      u32::MAX,
+    ];
+    let mut buf = [0u8; MAX_GROUP_VARINT_U32_LEN * 2];
+    for a in widths {
+      for b in widths {
+        let values = [a, b, b, a];
+        let written = encode_group_varint_u32(&values, &mut buf).unwrap();
+        // a trailing group must not affect decoding of the first one
+        encode_group_varint_u32(&[u32::MAX; 4], &mut buf[written..]).unwrap();
+        assert_eq!(decode_group_varint_u32(&buf).unwrap(), (written, values));
+      }
+    }
+  }
+
+  #[test]
+  fn test_group_varint_errors() {
+    let values = [1, 256, 65536, 16777216];
+    let mut buf = [0u8; 10];
+    assert!(encode_group_varint_u32(&values, &mut buf).is_err());
+
+    let mut buf = [0u8; MAX_GROUP_VARINT_U32_LEN];
+    let written = encode_group_varint_u32(&values, &mut buf).unwrap();
+    for len in 0..written {
+      assert!(matches!(
+        decode_group_varint_u32(&buf[..len]),
+        Err(DecodeVarintError::IncompleteBuffer(_))
+      ));
+    }
+  }
+
   fn check(value: u64, encoded: &[u8]) {
     let mut expected = [0u8; 16];
 
@@ -444,6 +674,65 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_decode_error_as_dyn_error() {
+    fn source_of(err: &dyn core::error::Error) -> Option<&(dyn core::error::Error + 'static)> {
+      err.source()
+    }
+
+    // the incomplete buffer is displayed as is, so it is not reported as the source again.
+    let err = decode_u64_varint(&[0x80]).unwrap_err();
+    assert_eq!(
+      std::format!("{err}"),
+      std::format!("{}", IncompleteBuffer::new())
+    );
+    assert!(source_of(&err).is_none());
+
+    let err = crate::error::DecodeEnumError::from(err);
+    let source = source_of(&err).expect("varint error has a source");
+    assert!(source.downcast_ref::<DecodeVarintError>().is_some());
+
+    let err: &dyn core::error::Error = &InsufficientBuffer::new();
+    assert!(err.downcast_ref::<InsufficientBuffer>().is_some());
+  }
+
+  #[test]
+  fn test_decode_u32_varints_batch() {
+    let mut state = 0x2545_f491_u32;
+    let values = (0..10_000)
+      .map(|i| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        // mostly small values with some large ones
+        if i % 16 == 0 {
+          state
+        } else {
+          state & 0x7f
+        }
+      })
+      .collect::<std::vec::Vec<_>>();
+
+    let mut buffer = std::vec![0u8; values.len() * MAX_U32_LEB128];
+    let mut encoded_len = 0;
+    for value in &values {
+      encoded_len += encode_u32_varint(*value, &mut buffer[encoded_len..]).unwrap();
+    }
+
+    let mut decoded = std::vec::Vec::new();
+    let read = decode_u32_varints_batch(&buffer[..encoded_len], &mut decoded).unwrap();
+    assert_eq!(read, encoded_len);
+    assert_eq!(decoded, values);
+
+    // the trailing varint is incomplete
+    let mut decoded = std::vec::Vec::new();
+    assert!(matches!(
+      decode_u32_varints_batch(&[1, 2, 0x80], &mut decoded),
+      Err(DecodeVarintError::IncompleteBuffer(_))
+    ));
+    assert_eq!(decoded, [1, 2]);
+  }
+
   #[test]
   fn test_decode_overflow_error() {
     let buffer = [0x80u8; 11]; // More than 10 bytes
@@ -465,6 +754,28 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_decode_u32_varint_from_u64() {
+    let mut buffer = [0u8; MAX_U64_LEB128];
+
+    let len = encode_u64_varint(u32::MAX as u64, &mut buffer).unwrap();
+    assert_eq!(
+      decode_u32_varint(&buffer[..len]).unwrap(),
+      (len, u32::MAX)
+    );
+
+    let len = encode_u64_varint(u32::MAX as u64 + 1, &mut buffer).unwrap();
+    assert!(matches!(
+      decode_u32_varint(&buffer[..len]),
+      Err(DecodeVarintError::Overflow)
+    ));
+
+    assert!(matches!(
+      decode_u32_varint(&buffer[..len - 1]),
+      Err(DecodeVarintError::IncompleteBuffer(_))
+    ));
+  }
+
   // Helper function for zig-zag encoding and decoding
   fn test_zigzag_encode_decode<T>(value: T)
   where
@@ -739,6 +1050,35 @@ mod fuzzy_tests {
     value == decoded && encoded_len == bytes_read
   }
 
+  #[quickcheck]
+  fn fuzzy_u32_batch(values: std::vec::Vec<u32>, small: std::vec::Vec<u8>) -> bool {
+    // interleave runs of single byte varints to exercise the fast path.
+    let values = values
+      .into_iter()
+      .zip(small.chunks(9))
+      .flat_map(|(v, run)| core::iter::once(v).chain(run.iter().map(|b| (*b & 0x7f) as u32)))
+      .collect::<std::vec::Vec<_>>();
+
+    let mut buffer = std::vec![0u8; values.len() * MAX_U32_LEB128];
+    let mut encoded_len = 0;
+    for value in &values {
+      encoded_len += encode_u32_varint(*value, &mut buffer[encoded_len..]).unwrap();
+    }
+    let buffer = &buffer[..encoded_len];
+
+    let mut scalar = std::vec::Vec::with_capacity(values.len());
+    let mut offset = 0;
+    while offset < buffer.len() {
+      let (read, value) = decode_u32_varint(&buffer[offset..]).unwrap();
+      scalar.push(value);
+      offset += read;
+    }
+
+    let mut batch = std::vec::Vec::new();
+    let read = decode_u32_varints_batch(buffer, &mut batch).unwrap();
+    read == encoded_len && batch == scalar && batch == values
+  }
+
   #[quickcheck]
   fn fuzzy_u64(value: u64) -> bool {
     let mut buffer = [0u8; 16];
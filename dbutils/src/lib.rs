@@ -161,6 +161,144 @@ macro_rules! builder {
   };
 }
 
+/// A macro to implement [`Type`](crate::types::Type) and [`TypeRef`](crate::types::TypeRef) for C-like enums.
+///
+/// The discriminant is encoded as a single LEB128 varint. Decoding an unknown discriminant through
+/// the generated `TryFrom<&[u8]>` implementation returns
+/// [`DecodeEnumError::UnknownDiscriminant`](crate::error::DecodeEnumError::UnknownDiscriminant),
+/// or the error type given after the enum name, which must implement `From<DecodeEnumError>`
+/// and `Debug`.
+///
+/// `PartialOrd` and `Ord` are implemented by discriminant, so the enum can be used as a key, e.g.
+/// with the [`Ascend`](crate::equivalentor::Ascend) comparator. The enum must implement `Copy`,
+/// `Debug` and `Eq`.
+///
+/// ## Panics
+///
+/// The generated [`TypeRef::from_slice`](crate::types::TypeRef::from_slice) panics if the bytes do
+/// not contain a known discriminant, which can only happen if its safety contract is violated.
+/// Use `TryFrom<&[u8]>` to decode untrusted bytes.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::{error::DecodeEnumError, impl_scalar_enum_type, types::Type};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Kind {
+///   Put,
+///   Delete,
+/// }
+///
+/// impl_scalar_enum_type!(Kind {
+///   Put = 0,
+///   Delete = 1,
+/// });
+///
+/// let encoded = Kind::Delete.encode_into_vec().unwrap();
+/// assert_eq!(Kind::try_from(encoded.as_slice()).unwrap(), Kind::Delete);
+/// assert!(Kind::Put < Kind::Delete);
+///
+/// #[derive(Debug)]
+/// struct StatusError(DecodeEnumError);
+///
+/// impl From<DecodeEnumError> for StatusError {
+///   fn from(e: DecodeEnumError) -> Self {
+///     Self(e)
+///   }
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Status {
+///   Active,
+///   Closed,
+/// }
+///
+/// impl_scalar_enum_type!(Status(StatusError) {
+///   Active = 1,
+///   Closed = 2,
+/// });
+///
+/// assert!(matches!(
+///   Status::try_from([3u8].as_slice()),
+///   Err(StatusError(DecodeEnumError::UnknownDiscriminant(3)))
+/// ));
+/// ```
+#[macro_export]
+macro_rules! impl_scalar_enum_type {
+  ($name:ident { $($variant:ident = $discriminant:literal),+ $(,)? }) => {
+    $crate::impl_scalar_enum_type!($name($crate::error::DecodeEnumError) { $($variant = $discriminant),+ });
+  };
+  ($name:ident($err:ty) { $($variant:ident = $discriminant:literal),+ $(,)? }) => {
+    impl $name {
+      #[inline]
+      const fn __scalar_enum_discriminant(&self) -> u64 {
+        match self {
+          $(Self::$variant => $discriminant,)+
+        }
+      }
+    }
+
+    impl $crate::types::Type for $name {
+      type Ref<'a> = Self;
+
+      type Error = $crate::error::InsufficientBuffer;
+
+      #[inline]
+      fn encoded_len(&self) -> usize {
+        $crate::leb128::encoded_u64_varint_len(self.__scalar_enum_discriminant())
+      }
+
+      #[inline]
+      fn encode_to_buffer(&self, buf: &mut $crate::buffer::VacantBuffer<'_>) -> ::core::result::Result<usize, Self::Error> {
+        buf.put_u64_varint(self.__scalar_enum_discriminant())
+      }
+    }
+
+    impl $crate::types::TypeRef<'_> for $name {
+      #[inline]
+      unsafe fn from_slice(buf: &[u8]) -> Self {
+        match <Self as ::core::convert::TryFrom<&[u8]>>::try_from(buf) {
+          ::core::result::Result::Ok(val) => val,
+          ::core::result::Result::Err(e) => ::core::panic!("failed to decode `{}`: {:?}", ::core::stringify!($name), e),
+        }
+      }
+    }
+
+    impl ::core::convert::TryFrom<&[u8]> for $name {
+      type Error = $err;
+
+      #[inline]
+      fn try_from(buf: &[u8]) -> ::core::result::Result<Self, Self::Error> {
+        let discriminant = match $crate::leb128::decode_u64_varint(buf) {
+          ::core::result::Result::Ok((_, discriminant)) => discriminant,
+          ::core::result::Result::Err(e) => {
+            return ::core::result::Result::Err(::core::convert::From::from($crate::error::DecodeEnumError::Varint(e)));
+          }
+        };
+        match discriminant {
+          $($discriminant => ::core::result::Result::Ok(Self::$variant),)+
+          d => ::core::result::Result::Err(::core::convert::From::from($crate::error::DecodeEnumError::UnknownDiscriminant(d))),
+        }
+      }
+    }
+
+    impl ::core::cmp::PartialOrd for $name {
+      #[inline]
+      fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+        ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+      }
+    }
+
+    impl ::core::cmp::Ord for $name {
+      #[inline]
+      fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+        self.__scalar_enum_discriminant().cmp(&other.__scalar_enum_discriminant())
+      }
+    }
+  };
+}
+
 /// Abort the process.
 #[inline(never)]
 #[cold]
@@ -78,8 +78,10 @@ impl<T: Type> Type for &T {
   }
 }
 
+/// `Reverse<T>` is encoded the same as `T`, but its reference type is also wrapped in
+/// [`Reverse`], so decoded keys are ordered in descending order.
 impl<T: Type> Type for Reverse<T> {
-  type Ref<'a> = T::Ref<'a>;
+  type Ref<'a> = Reverse<T::Ref<'a>>;
   type Error = T::Error;
 
   #[inline]
@@ -120,6 +122,18 @@ pub trait TypeRef<'a>: core::fmt::Debug + Copy + Sized {
   }
 }
 
+impl<'a, T: TypeRef<'a>> TypeRef<'a> for Reverse<T> {
+  #[inline]
+  unsafe fn from_slice(src: &'a [u8]) -> Self {
+    Reverse(T::from_slice(src))
+  }
+
+  #[inline]
+  fn as_raw(&self) -> Option<&'a [u8]> {
+    self.0.as_raw()
+  }
+}
+
 /// A wrapper around a generic type that can be used to construct for insertion.
 #[repr(transparent)]
 #[derive(Debug)]
@@ -302,3 +316,144 @@ impl<'a, T: 'a + ?Sized> From<&'a T> for MaybeStructured<'a, T> {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::DecodeEnumError;
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  enum Kind {
+    Put,
+    Delete,
+    RangeDelete,
+  }
+
+  crate::impl_scalar_enum_type!(Kind {
+    Put = 0,
+    Delete = 1,
+    RangeDelete = 200,
+  });
+
+  #[test]
+  fn scalar_enum_roundtrip() {
+    for kind in [Kind::Put, Kind::Delete, Kind::RangeDelete] {
+      let mut buf = [0u8; 10];
+      let len = kind.encode(&mut buf).unwrap();
+      assert_eq!(len, kind.encoded_len());
+      assert_eq!(Kind::try_from(&buf[..len]).unwrap(), kind);
+      assert_eq!(
+        unsafe { <Kind as TypeRef<'_>>::from_slice(&buf[..len]) },
+        kind
+      );
+    }
+
+    assert_eq!(Kind::RangeDelete.encoded_len(), 2);
+  }
+
+  #[test]
+  fn reverse_ref_order() {
+    let encoded = [Reverse(1u64), Reverse(2u64)].map(|key| key.encode_into_vec().unwrap());
+
+    let keys = encoded
+      .iter()
+      .map(|buf| unsafe { <<Reverse<u64> as Type>::Ref<'_> as TypeRef<'_>>::from_slice(buf) })
+      .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(
+      keys.into_iter().collect::<std::vec::Vec<_>>(),
+      [Reverse(2), Reverse(1)]
+    );
+
+    let (small, large) = (MaybeStructured::<Reverse<u64>>::from(&Reverse(1)), unsafe {
+      MaybeStructured::<Reverse<u64>>::from_slice(&encoded[1])
+    });
+    assert!(large < small);
+  }
+
+  #[test]
+  fn scalar_enum_unknown_discriminant() {
+    assert_eq!(
+      Kind::try_from([3u8].as_slice()).unwrap_err(),
+      DecodeEnumError::UnknownDiscriminant(3)
+    );
+    let empty: &[u8] = &[];
+    assert!(matches!(
+      Kind::try_from(empty).unwrap_err(),
+      DecodeEnumError::Varint(_)
+    ));
+  }
+
+  #[derive(Debug, PartialEq, Eq)]
+  struct StatusError(DecodeEnumError);
+
+  impl From<DecodeEnumError> for StatusError {
+    fn from(e: DecodeEnumError) -> Self {
+      Self(e)
+    }
+  }
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  enum Status {
+    Closed,
+    Active,
+  }
+
+  crate::impl_scalar_enum_type!(Status(StatusError) {
+    Closed = 2,
+    Active = 1,
+  });
+
+  #[test]
+  fn scalar_enum_custom_error() {
+    let encoded = Status::Closed.encode_into_vec().unwrap();
+    assert_eq!(
+      Status::try_from(encoded.as_slice()).unwrap(),
+      Status::Closed
+    );
+    assert_eq!(
+      Status::try_from([0u8].as_slice()).unwrap_err(),
+      StatusError(DecodeEnumError::UnknownDiscriminant(0))
+    );
+  }
+
+  #[test]
+  fn scalar_enum_key_order() {
+    use crate::equivalentor::{Ascend, TypeRefComparator};
+
+    // ordered by discriminant, not by declaration.
+    assert!(Status::Active < Status::Closed);
+    assert!(Kind::Delete < Kind::RangeDelete);
+
+    let encoded = Kind::RangeDelete.encode_into_vec().unwrap();
+    let decoded = unsafe { <Kind as TypeRef<'_>>::from_slice(&encoded) };
+    assert_eq!(
+      Ascend.compare_ref(&Kind::Put, &decoded),
+      cmp::Ordering::Less
+    );
+    assert_eq!(
+      TypeRefComparator::<Kind>::compare_refs(&Ascend, &decoded, &Kind::RangeDelete),
+      cmp::Ordering::Equal
+    );
+  }
+
+  #[test]
+  fn compare_same_raw_bytes() {
+    let buf = *b"abcabd";
+    let key = unsafe { SliceRef::from_slice(&buf[..3]) };
+    assert_eq!(buf[..3].compare(&key), cmp::Ordering::Equal);
+    // same length, different address and contents
+    assert_eq!(buf[3..].compare(&key), cmp::Ordering::Greater);
+    // same address, different length
+    assert_eq!(buf[..2].compare(&key), cmp::Ordering::Less);
+    assert_eq!(buf[..4].compare(&key), cmp::Ordering::Greater);
+    // equal contents at a different address
+    assert_eq!(b"abc".as_slice().compare(&key), cmp::Ordering::Equal);
+
+    let s = "abcabd";
+    let key = unsafe { Str::from_slice(&s.as_bytes()[..3]) };
+    assert_eq!(s[..3].compare(&key), cmp::Ordering::Equal);
+    assert_eq!(s[3..].compare(&key), cmp::Ordering::Greater);
+    assert_eq!(s[..2].compare(&key), cmp::Ordering::Less);
+    assert_eq!("abc".compare(&key), cmp::Ordering::Equal);
+  }
+}
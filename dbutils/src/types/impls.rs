@@ -28,11 +28,22 @@ macro_rules! impl_cmp {
 
 mod bytes;
 pub use bytes::*;
+#[cfg(feature = "alloc")]
+mod cow;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use cow::CowRef;
 mod string;
 pub use string::Str;
+mod seq;
+pub use seq::TypeSeq;
+mod varint;
+pub use varint::Varint;
 
 #[cfg(feature = "std")]
 mod net;
+#[cfg(feature = "std")]
+mod path;
 
 impl Type for () {
   type Ref<'a> = ();
@@ -139,6 +139,10 @@ impl Equivalent<SliceRef<'_>> for [u8] {
 impl Comparable<SliceRef<'_>> for [u8] {
   #[inline]
   fn compare(&self, key: &SliceRef<'_>) -> cmp::Ordering {
+    // comparing a key to itself is common when navigating, skip the byte comparison.
+    if core::ptr::eq(self, key.0) {
+      return cmp::Ordering::Equal;
+    }
     self.cmp(key.0)
   }
 }
@@ -0,0 +1,210 @@
+use core::{borrow::Borrow, cmp::Ordering};
+use std::vec::Vec;
+
+use either::Either;
+
+use super::SliceRef;
+
+/// Decoded bytes which either borrow the source buffer or own a copy of it.
+///
+/// [`TypeRef`](super::TypeRef)s must be `Copy`, so they can only borrow. `CowRef` is the
+/// companion for decoders which sometimes need the bytes to outlive the buffer: choose
+/// [`borrowed`](CowRef::borrowed) or [`owned`](CowRef::owned) at decode time, or decode a
+/// [`SliceRef`] as usual and convert it. The borrowed path never allocates, and
+/// [`into_owned`](CowRef::into_owned) only copies if the bytes are still borrowed.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::types::CowRef;
+///
+/// let buf = std::vec![1, 2, 3];
+/// let borrowed = CowRef::decode(&buf, false);
+/// assert!(borrowed.is_borrowed());
+///
+/// // detach the lifetime from `buf`, which is free since the bytes are already owned.
+/// let owned = CowRef::decode(&buf, true).into_static();
+/// drop(buf);
+/// assert_eq!(owned.into_owned(), [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CowRef<'a> {
+  data: Either<&'a [u8], Vec<u8>>,
+}
+
+impl<'a> CowRef<'a> {
+  /// Creates a `CowRef` which borrows the bytes.
+  #[inline]
+  pub const fn borrowed(src: &'a [u8]) -> Self {
+    Self {
+      data: Either::Left(src),
+    }
+  }
+
+  /// Creates a `CowRef` which owns a copy of the bytes.
+  #[inline]
+  pub fn owned(src: &[u8]) -> Self {
+    Self {
+      data: Either::Right(src.to_vec()),
+    }
+  }
+
+  /// Decodes the bytes, copying them if `owned` is `true` and borrowing them otherwise.
+  #[inline]
+  pub fn decode(src: &'a [u8], owned: bool) -> Self {
+    if owned {
+      Self::owned(src)
+    } else {
+      Self::borrowed(src)
+    }
+  }
+
+  /// Returns `true` if the bytes are borrowed.
+  #[inline]
+  pub const fn is_borrowed(&self) -> bool {
+    matches!(self.data, Either::Left(_))
+  }
+
+  /// Returns the bytes.
+  #[inline]
+  pub fn as_bytes(&self) -> &[u8] {
+    match &self.data {
+      Either::Left(src) => src,
+      Either::Right(src) => src,
+    }
+  }
+
+  /// Returns the underlying bytes, either borrowed or owned.
+  #[inline]
+  pub fn into_inner(self) -> Either<&'a [u8], Vec<u8>> {
+    self.data
+  }
+
+  /// Returns the owned bytes, copying them only if they are borrowed.
+  #[inline]
+  pub fn into_owned(self) -> Vec<u8> {
+    match self.data {
+      Either::Left(src) => src.to_vec(),
+      Either::Right(src) => src,
+    }
+  }
+
+  /// Returns a `CowRef` which owns its bytes, so it no longer borrows the source buffer.
+  #[inline]
+  pub fn into_static(self) -> CowRef<'static> {
+    CowRef {
+      data: Either::Right(self.into_owned()),
+    }
+  }
+}
+
+impl<'a> From<&'a [u8]> for CowRef<'a> {
+  #[inline]
+  fn from(src: &'a [u8]) -> Self {
+    Self::borrowed(src)
+  }
+}
+
+impl<'a> From<SliceRef<'a>> for CowRef<'a> {
+  #[inline]
+  fn from(src: SliceRef<'a>) -> Self {
+    Self::borrowed(src.as_bytes())
+  }
+}
+
+impl From<Vec<u8>> for CowRef<'_> {
+  #[inline]
+  fn from(src: Vec<u8>) -> Self {
+    Self {
+      data: Either::Right(src),
+    }
+  }
+}
+
+impl AsRef<[u8]> for CowRef<'_> {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl Borrow<[u8]> for CowRef<'_> {
+  #[inline]
+  fn borrow(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl core::ops::Deref for CowRef<'_> {
+  type Target = [u8];
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    self.as_bytes()
+  }
+}
+
+impl PartialEq for CowRef<'_> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.as_bytes() == other.as_bytes()
+  }
+}
+
+impl Eq for CowRef<'_> {}
+
+impl PartialOrd for CowRef<'_> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for CowRef<'_> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.as_bytes().cmp(other.as_bytes())
+  }
+}
+
+impl core::hash::Hash for CowRef<'_> {
+  #[inline]
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.as_bytes().hash(state)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::{Type, TypeRef};
+
+  #[test]
+  fn borrow_and_own() {
+    let value = std::vec![7u8, 8, 9];
+    let encoded = value.encode_into_vec().unwrap();
+
+    let borrowed = CowRef::decode(&encoded, false);
+    assert!(borrowed.is_borrowed());
+    assert!(core::ptr::eq(borrowed.as_bytes(), encoded.as_slice()));
+
+    let owned = CowRef::decode(&encoded, true);
+    assert!(!owned.is_borrowed());
+    assert!(!core::ptr::eq(owned.as_bytes(), encoded.as_slice()));
+    assert_eq!(borrowed, owned);
+    let vec = owned.into_owned();
+
+    // the decoded ref converts without copying, and owns only on demand.
+    let slice = unsafe { <SliceRef<'_> as TypeRef<'_>>::from_slice(&encoded) };
+    let cow = CowRef::from(slice);
+    assert!(cow.is_borrowed());
+    let detached = cow.into_static();
+    drop(encoded);
+    assert!(!detached.is_borrowed());
+    assert_eq!(detached.as_bytes(), value.as_slice());
+
+    let ptr = vec.as_ptr();
+    let back = CowRef::from(vec).into_owned();
+    assert_eq!(back.as_ptr(), ptr);
+  }
+}
@@ -1,4 +1,4 @@
-use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
 use super::{InsufficientBuffer, Type, TypeRef, VacantBuffer};
 
@@ -7,6 +7,11 @@ const SOCKET_V4_ENCODED_LEN: usize = 6;
 const IPV6_ENCODED_LEN: usize = 16;
 const IPV4_ENCODED_LEN: usize = 4;
 
+// The tags keep IPv4 before IPv6 when the encoded bytes are compared, matching `IpAddr`'s `Ord`.
+const IPV4_TAG: u8 = 0;
+const IPV6_TAG: u8 = 1;
+const PORT_ENCODED_LEN: usize = 2;
+
 impl Type for Ipv4Addr {
   type Ref<'a> = Self;
 
@@ -112,3 +117,130 @@ impl TypeRef<'_> for SocketAddrV6 {
     SocketAddrV6::new(Ipv6Addr::from(octets), port, 0, 0)
   }
 }
+
+impl Type for IpAddr {
+  type Ref<'a> = Self;
+
+  type Error = InsufficientBuffer;
+
+  #[inline]
+  fn encoded_len(&self) -> usize {
+    1 + match self {
+      Self::V4(_) => IPV4_ENCODED_LEN,
+      Self::V6(_) => IPV6_ENCODED_LEN,
+    }
+  }
+
+  #[inline]
+  fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+    match self {
+      Self::V4(addr) => {
+        buf.put_u8(IPV4_TAG)?;
+        buf.put_slice(addr.octets().as_ref())?;
+        Ok(1 + IPV4_ENCODED_LEN)
+      }
+      Self::V6(addr) => {
+        buf.put_u8(IPV6_TAG)?;
+        buf.put_slice(addr.octets().as_ref())?;
+        Ok(1 + IPV6_ENCODED_LEN)
+      }
+    }
+  }
+}
+
+impl TypeRef<'_> for IpAddr {
+  #[inline]
+  unsafe fn from_slice(buf: &[u8]) -> Self {
+    match buf[0] {
+      IPV4_TAG => IpAddr::V4(Ipv4Addr::from_slice(&buf[1..])),
+      IPV6_TAG => IpAddr::V6(Ipv6Addr::from_slice(&buf[1..])),
+      tag => panic!("invalid ip address tag: {tag}"),
+    }
+  }
+}
+
+/// The address is encoded as [`IpAddr`], followed by the port in big-endian, so the
+/// encoded bytes sort by address and then by port.
+impl Type for SocketAddr {
+  type Ref<'a> = Self;
+
+  type Error = InsufficientBuffer;
+
+  #[inline]
+  fn encoded_len(&self) -> usize {
+    self.ip().encoded_len() + PORT_ENCODED_LEN
+  }
+
+  #[inline]
+  fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+    let written = self.ip().encode_to_buffer(buf)?;
+    buf.put_u16_be(self.port())?;
+    Ok(written + PORT_ENCODED_LEN)
+  }
+}
+
+impl TypeRef<'_> for SocketAddr {
+  #[inline]
+  unsafe fn from_slice(buf: &[u8]) -> Self {
+    let ip = IpAddr::from_slice(buf);
+    let ofs = ip.encoded_len();
+    let port = u16::from_be_bytes([buf[ofs], buf[ofs + 1]]);
+    SocketAddr::new(ip, port)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn roundtrip<T>(value: T)
+  where
+    T: for<'a> Type<Ref<'a> = T> + for<'a> TypeRef<'a> + PartialEq + core::fmt::Debug,
+    T::Error: core::fmt::Debug,
+  {
+    let mut buf = [0u8; 32];
+    let len = value.encoded_len();
+    assert_eq!(value.encode(&mut buf[..len]).unwrap(), len);
+    assert_eq!(unsafe { T::from_slice(&buf[..len]) }, value);
+  }
+
+  fn encoded<T>(value: &T) -> std::vec::Vec<u8>
+  where
+    T: Type,
+    T::Error: core::fmt::Debug,
+  {
+    let mut buf = std::vec![0; value.encoded_len()];
+    value.encode(&mut buf).unwrap();
+    buf
+  }
+
+  #[test]
+  fn net_roundtrip() {
+    let v4 = Ipv4Addr::new(10, 0, 0, 1);
+    let v6 = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+
+    roundtrip(v4);
+    roundtrip(v6);
+    roundtrip(IpAddr::V4(v4));
+    roundtrip(IpAddr::V6(v6));
+    roundtrip(SocketAddr::new(IpAddr::V4(v4), 8080));
+    roundtrip(SocketAddr::new(IpAddr::V6(v6), 443));
+  }
+
+  #[test]
+  fn socket_addr_sorts_by_address_then_port() {
+    let mut addrs = std::vec![
+      SocketAddr::new(Ipv4Addr::new(10, 0, 0, 2).into(), 1),
+      SocketAddr::new(Ipv4Addr::new(10, 0, 0, 1).into(), 256),
+      SocketAddr::new(Ipv4Addr::new(10, 0, 0, 1).into(), 255),
+      SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 0),
+      SocketAddr::new(Ipv4Addr::new(192, 168, 0, 1).into(), 80),
+      SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 65535),
+    ];
+
+    let mut by_bytes = addrs.clone();
+    by_bytes.sort_by_key(encoded);
+    addrs.sort();
+    assert_eq!(by_bytes, addrs);
+  }
+}
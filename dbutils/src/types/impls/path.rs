@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::EncodePathError;
+
+use super::{Type, TypeRef, VacantBuffer};
+
+/// Returns the encoded bytes of the path.
+#[inline]
+fn path_bytes(path: &Path) -> Result<&[u8], EncodePathError> {
+  #[cfg(unix)]
+  {
+    use std::os::unix::ffi::OsStrExt;
+
+    Ok(path.as_os_str().as_bytes())
+  }
+
+  #[cfg(not(unix))]
+  {
+    path
+      .to_str()
+      .map(str::as_bytes)
+      .ok_or(EncodePathError::NonUtf8)
+  }
+}
+
+/// Returns the path encoded in the bytes.
+#[inline]
+fn path_from_bytes(src: &[u8]) -> &Path {
+  #[cfg(unix)]
+  {
+    use std::os::unix::ffi::OsStrExt;
+
+    Path::new(std::ffi::OsStr::from_bytes(src))
+  }
+
+  #[cfg(not(unix))]
+  {
+    Path::new(core::str::from_utf8(src).unwrap())
+  }
+}
+
+/// Paths are encoded as the bytes of their `OsStr`.
+///
+/// On Unix, a path is an arbitrary byte string, so every path is encoded losslessly, including
+/// paths which are not valid UTF-8. Elsewhere, e.g. on Windows where paths are potentially
+/// ill-formed UTF-16, there is no portable byte representation, so a path is encoded as UTF-8
+/// and encoding a non-UTF-8 path fails with
+/// [`EncodePathError::NonUtf8`](crate::error::EncodePathError::NonUtf8).
+///
+/// The encoded bytes are not ordered like [`Path`]: `Path` compares by components, so e.g.
+/// `a/b` sorts before `a.b`, and `a//b` equals `a/b`. The reference type is `&Path`, so keys
+/// compared after decoding follow the `Path` order.
+impl Type for Path {
+  type Ref<'a> = &'a Path;
+  type Error = EncodePathError;
+
+  // a path which cannot be encoded has no encoded bytes, `encode` reports the error.
+  #[inline]
+  fn encoded_len(&self) -> usize {
+    path_bytes(self).map_or(0, <[u8]>::len)
+  }
+
+  #[inline]
+  fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+    buf.put_slice(path_bytes(self)?).map_err(Into::into)
+  }
+
+  #[inline]
+  fn as_encoded(&self) -> Option<&[u8]> {
+    path_bytes(self).ok()
+  }
+}
+
+impl Type for PathBuf {
+  type Ref<'a> = &'a Path;
+  type Error = EncodePathError;
+
+  #[inline]
+  fn encoded_len(&self) -> usize {
+    self.as_path().encoded_len()
+  }
+
+  #[inline]
+  fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+    self.as_path().encode_to_buffer(buf)
+  }
+
+  #[inline]
+  fn as_encoded(&self) -> Option<&[u8]> {
+    self.as_path().as_encoded()
+  }
+}
+
+impl<'a> TypeRef<'a> for &'a Path {
+  #[inline]
+  unsafe fn from_slice(src: &'a [u8]) -> Self {
+    path_from_bytes(src)
+  }
+
+  #[inline]
+  fn as_raw(&self) -> Option<&'a [u8]> {
+    path_bytes(self).ok()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn path_roundtrip() {
+    let path = Path::new("/var/lib/db/000042.sst");
+    let encoded = path.encode_into_vec().unwrap();
+    assert_eq!(encoded, b"/var/lib/db/000042.sst");
+    assert_eq!(path.as_encoded(), Some(encoded.as_slice()));
+    assert_eq!(unsafe { <&Path>::from_slice(&encoded) }, path);
+
+    let buf = PathBuf::from("meta/ünïcode/MANIFEST");
+    let encoded = buf.encode_into_vec().unwrap();
+    assert_eq!(encoded.len(), buf.encoded_len());
+    let decoded = unsafe { <&Path>::from_slice(&encoded) };
+    assert_eq!(decoded, buf);
+    assert_eq!(decoded.as_raw(), Some(encoded.as_slice()));
+
+    let mut small = [0u8; 4];
+    let err = path.encode(&mut small).unwrap_err();
+    assert!(matches!(err, EncodePathError::InsufficientBuffer(_)));
+    assert_eq!(std::format!("{err}"), "failed to encode the path");
+    assert!(core::error::Error::source(&err).is_some());
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn non_utf8_path_roundtrip() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = Path::new(std::ffi::OsStr::from_bytes(b"dir/\xff\xfe.log"));
+    let encoded = path.encode_into_vec().unwrap();
+    assert_eq!(encoded, b"dir/\xff\xfe.log");
+    assert_eq!(unsafe { <&Path>::from_slice(&encoded) }, path);
+  }
+}
@@ -0,0 +1,167 @@
+use crate::{buffer::TypeSeqRef, leb128::encoded_u64_varint_len};
+
+use super::*;
+
+/// A wrapper which encodes a sequence of structured elements as a single [`Type`].
+///
+/// `Vec<u8>` and friends are already encoded as raw bytes, so a sequence of arbitrary
+/// elements has to be wrapped to be distinguished from them.
+///
+/// The sequence is encoded in the same format as [`VacantBuffer::put_type_seq`], the number
+/// of elements in LEB128 format, followed by each element prefixed with its encoded length
+/// in LEB128 format. The reference type is a [`TypeSeqRef`] iterating over the elements.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::{
+///   buffer::TypeSeqRef,
+///   types::{Type, TypeRef, TypeSeq},
+/// };
+///
+/// let seq = TypeSeq::new(vec![1u32, 2, 3]);
+/// let encoded = seq.encode_into_vec().unwrap();
+///
+/// let decoded = unsafe { TypeSeqRef::<u32>::from_slice(&encoded) };
+/// assert_eq!(decoded.collect::<Vec<_>>(), [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct TypeSeq<C>(C);
+
+impl<C> TypeSeq<C> {
+  /// Wraps a sequence.
+  #[inline]
+  pub const fn new(seq: C) -> Self {
+    Self(seq)
+  }
+
+  /// Returns a reference to the inner sequence.
+  #[inline]
+  pub const fn as_inner(&self) -> &C {
+    &self.0
+  }
+
+  /// Consumes the wrapper and returns the inner sequence.
+  #[inline]
+  pub fn into_inner(self) -> C {
+    self.0
+  }
+}
+
+impl<C> From<C> for TypeSeq<C> {
+  #[inline]
+  fn from(seq: C) -> Self {
+    Self(seq)
+  }
+}
+
+#[inline]
+fn encoded_seq_len<T: Type>(seq: &[T]) -> usize {
+  seq
+    .iter()
+    .fold(encoded_u64_varint_len(seq.len() as u64), |acc, ent| {
+      let len = ent.encoded_len();
+      acc + encoded_u64_varint_len(len as u64) + len
+    })
+}
+
+macro_rules! impls {
+  ($( $(#[cfg($cfg:meta)])? $ty:ty),+ $(,)?) => {
+    $(
+      $(#[cfg($cfg)])?
+      impl<T> Type for TypeSeq<$ty>
+      where
+        T: Type,
+        T::Error: From<InsufficientBuffer>,
+      {
+        type Ref<'a> = TypeSeqRef<'a, T>;
+        type Error = T::Error;
+
+        #[inline]
+        fn encoded_len(&self) -> usize {
+          encoded_seq_len(&self.0)
+        }
+
+        #[inline]
+        fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+          buf.put_type_seq(self.0.iter())
+        }
+      }
+    )*
+  };
+}
+
+impls! {
+  &[T],
+  #[cfg(feature = "alloc")]
+  ::std::vec::Vec<T>,
+  #[cfg(feature = "alloc")]
+  ::std::boxed::Box<[T]>,
+}
+
+impl<'a, T> TypeRef<'a> for TypeSeqRef<'a, T>
+where
+  T: Type + ?Sized,
+{
+  #[inline]
+  unsafe fn from_slice(src: &'a [u8]) -> Self {
+    Self::decode(src).unwrap().1
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn vec_u32_roundtrip() {
+    let seq = TypeSeq::new(vec![0u32, 1, 128, u32::MAX]);
+    let encoded = seq.encode_into_vec().unwrap();
+    assert_eq!(encoded.len(), seq.encoded_len());
+
+    let decoded = unsafe { TypeSeqRef::<u32>::from_slice(&encoded) };
+    assert_eq!(decoded.len(), 4);
+    assert_eq!(decoded.collect::<Vec<_>>(), seq.into_inner());
+  }
+
+  #[test]
+  fn vec_str_roundtrip() {
+    let seq = TypeSeq::new(vec!["", "a", "layer0"]);
+    let encoded = seq.encode_into_vec().unwrap();
+    assert_eq!(encoded.len(), seq.encoded_len());
+
+    let decoded = unsafe { TypeSeqRef::<&str>::from_slice(&encoded) };
+    assert_eq!(
+      decoded.map(|s| s.as_str()).collect::<Vec<_>>(),
+      seq.into_inner()
+    );
+  }
+
+  #[test]
+  fn boxed_slice_roundtrip() {
+    let seq: TypeSeq<Box<[u64]>> = TypeSeq::new(vec![7, 8].into_boxed_slice());
+    let encoded = seq.encode_into_vec().unwrap();
+
+    let decoded = unsafe { TypeSeqRef::<u64>::from_slice(&encoded) };
+    assert_eq!(decoded.collect::<Vec<_>>(), [7, 8]);
+  }
+
+  #[test]
+  fn empty() {
+    let seq = TypeSeq::new(Vec::<u32>::new());
+    assert_eq!(seq.encoded_len(), 1);
+    let encoded = seq.encode_into_vec().unwrap();
+    assert_eq!(encoded, [0]);
+
+    let decoded = unsafe { TypeSeqRef::<u32>::from_slice(&encoded) };
+    assert_eq!(decoded.count(), 0);
+  }
+
+  #[test]
+  fn insufficient_buffer() {
+    let seq = TypeSeq::new(&[1u32, 2][..]);
+    let mut buf = [0; 4];
+    assert!(seq.encode(&mut buf).is_err());
+  }
+}
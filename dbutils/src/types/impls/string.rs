@@ -175,6 +175,10 @@ impl Equivalent<Str<'_>> for str {
 impl Comparable<Str<'_>> for str {
   #[inline]
   fn compare(&self, key: &Str<'_>) -> cmp::Ordering {
+    // comparing a key to itself is common when navigating, skip the byte comparison.
+    if core::ptr::eq(self, key.0) {
+      return cmp::Ordering::Equal;
+    }
     self.cmp(key.0)
   }
 }
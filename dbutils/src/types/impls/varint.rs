@@ -0,0 +1,145 @@
+use crate::leb128::*;
+
+use super::*;
+
+/// A wrapper which encodes an integer as a [`Type`] in LEB128 format.
+///
+/// The plain integer types are encoded in a fixed size, which is wasteful for values that are
+/// usually small, e.g. counters or lengths. `Varint` encodes the integer in LEB128 format,
+/// signed integers are ZigZag encoded first, so a small value takes a single byte.
+///
+/// The encoded bytes do not preserve the order of the integers, so `Varint` is meant to be
+/// used for values, not for keys compared in the encoded form.
+///
+/// ## Example
+///
+/// ```rust
+/// use dbutils::types::{Type, TypeRef, Varint};
+///
+/// let encoded = Varint::new(42u64).encode_into_vec().unwrap();
+/// assert_eq!(encoded.len(), 1);
+///
+/// let decoded = unsafe { Varint::<u64>::from_slice(&encoded) };
+/// assert_eq!(decoded.into_inner(), 42);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Varint<T>(T);
+
+impl<T> Varint<T> {
+  /// Wraps an integer.
+  #[inline]
+  pub const fn new(val: T) -> Self {
+    Self(val)
+  }
+
+  /// Returns a reference to the inner integer.
+  #[inline]
+  pub const fn as_inner(&self) -> &T {
+    &self.0
+  }
+
+  /// Consumes the wrapper and returns the inner integer.
+  #[inline]
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T> From<T> for Varint<T> {
+  #[inline]
+  fn from(val: T) -> Self {
+    Self(val)
+  }
+}
+
+macro_rules! impl_varint {
+  ($($ty:ident), +$(,)?) => {
+    $(
+      paste::paste! {
+        impl Type for Varint<$ty> {
+          type Ref<'a> = Self;
+          type Error = InsufficientBuffer;
+
+          #[inline]
+          fn encoded_len(&self) -> usize {
+            [< encoded_ $ty _varint_len >](self.0)
+          }
+
+          #[inline]
+          fn encode(&self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            [< encode_ $ty _varint >](self.0, buf)
+          }
+
+          #[inline]
+          fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+            buf.[< put_ $ty _varint >](self.0)
+          }
+        }
+
+        impl TypeRef<'_> for Varint<$ty> {
+          #[inline]
+          unsafe fn from_slice(src: &[u8]) -> Self {
+            Self([< decode_ $ty _varint >](src).unwrap().1)
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_varint!(u16, u32, u64, u128, i16, i32, i64, i128);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  macro_rules! roundtrip {
+    ($($ty:ident), +$(,)?) => {
+      $(
+        paste::paste! {
+          proptest::proptest! {
+            #[test]
+            fn [< varint_ $ty _roundtrip >](x in $ty::MIN..=$ty::MAX) {
+              let val = Varint::new(x);
+              let encoded = val.encode_into_vec().unwrap();
+              proptest::prop_assert_eq!(encoded.len(), val.encoded_len());
+
+              let decoded = unsafe { Varint::<$ty>::from_slice(&encoded) };
+              proptest::prop_assert_eq!(decoded, val);
+            }
+          }
+        }
+      )*
+    };
+  }
+
+  roundtrip!(u16, u32, u64, u128, i16, i32, i64, i128);
+
+  #[test]
+  fn small_values_take_one_byte() {
+    assert_eq!(Varint::new(0u64).encoded_len(), 1);
+    assert_eq!(Varint::new(127u64).encoded_len(), 1);
+    assert_eq!(Varint::new(128u64).encoded_len(), 2);
+    assert_eq!(Varint::new(-1i64).encoded_len(), 1);
+    assert_eq!(Varint::new(63i32).encoded_len(), 1);
+    assert_eq!(Varint::new(u64::MAX).encoded_len(), 10);
+
+    let mut buf = [0u8; 1];
+    assert_eq!(Varint::new(7u32).encode(&mut buf), Ok(1));
+    assert_eq!(buf, [7]);
+    assert!(Varint::new(128u32).encode(&mut buf).is_err());
+  }
+
+  #[test]
+  fn encode_to_buffer() {
+    let mut data = [0u8; 16];
+    let mut buf = VacantBuffer::from(data.as_mut_slice());
+    let written = Varint::new(300u64).encode_to_buffer(&mut buf).unwrap();
+    assert_eq!(written, 2);
+    assert_eq!(buf.as_slice(), [0xac, 0x02]);
+
+    let decoded = unsafe { Varint::<u64>::from_slice(buf.as_slice()) };
+    assert_eq!(decoded.into_inner(), 300);
+  }
+}
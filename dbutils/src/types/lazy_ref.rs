@@ -53,6 +53,12 @@ where
   pub const fn raw(&self) -> Option<&'a [u8]> {
     self.raw
   }
+
+  /// Returns `true` if the reference value has been decoded (or was given when constructing).
+  #[inline]
+  pub fn is_decoded(&self) -> bool {
+    self.val.get().is_some()
+  }
 }
 
 impl<'a, T> LazyRef<'a, T>
@@ -60,6 +66,9 @@ where
   T: Type + ?Sized,
 {
   /// Returns the reference value.
+  ///
+  /// The raw byte slice is decoded on the first call and the result is cached,
+  /// so the following calls will not decode again.
   pub fn get(&self) -> &T::Ref<'a> {
     self.val.get_or_init(|| unsafe {
       <T::Ref<'_> as TypeRef<'_>>::from_slice(
@@ -178,3 +187,64 @@ where
     self.get()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use core::sync::atomic::{AtomicUsize, Ordering};
+
+  use super::*;
+  use crate::{buffer::VacantBuffer, error::InsufficientBuffer};
+
+  static DECODED: AtomicUsize = AtomicUsize::new(0);
+
+  #[derive(Debug)]
+  struct Counted;
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  struct CountedRef(u8);
+
+  impl Type for Counted {
+    type Ref<'a> = CountedRef;
+    type Error = InsufficientBuffer;
+
+    fn encoded_len(&self) -> usize {
+      1
+    }
+
+    fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
+      buf.put_u8(1).map(|_| 1)
+    }
+  }
+
+  impl TypeRef<'_> for CountedRef {
+    unsafe fn from_slice(src: &[u8]) -> Self {
+      DECODED.fetch_add(1, Ordering::SeqCst);
+      CountedRef(src[0])
+    }
+  }
+
+  #[test]
+  fn decode_at_most_once() {
+    let raw = [7u8];
+    let val = unsafe { LazyRef::<Counted>::from_raw(&raw) };
+    assert!(!val.is_decoded());
+    assert_eq!(val.raw(), Some(raw.as_slice()));
+
+    for _ in 0..3 {
+      assert_eq!(*val.get(), CountedRef(7));
+      assert_eq!(*val, CountedRef(7));
+    }
+    assert!(val.is_decoded());
+    assert_eq!(DECODED.load(Ordering::SeqCst), 1);
+
+    // the decoded value is cloned along with the lazy reference.
+    let cloned = val.clone();
+    assert!(cloned.is_decoded());
+    assert_eq!(*cloned.get(), CountedRef(7));
+    assert_eq!(DECODED.load(Ordering::SeqCst), 1);
+
+    let val = LazyRef::<Counted>::new(CountedRef(1));
+    assert!(val.is_decoded());
+    assert_eq!(val.raw(), None);
+  }
+}
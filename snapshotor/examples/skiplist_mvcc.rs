@@ -4,11 +4,11 @@ use core::{
   ops::{Bound, RangeBounds},
   sync::atomic::{AtomicU64, Ordering},
 };
-use crossbeam_skiplist::{
+use crossbeam_skiplist::{equivalent as skiplist_equivalent, SkipMap as CSkipMap};
+use dbutils::{
   equivalent::{Comparable, Equivalent},
-  SkipMap as CSkipMap,
+  state::{Active, MaybeTombstone},
 };
-use dbutils::state::{Active, MaybeTombstone};
 
 /// Errors for multiple version `SkipMap`s
 #[derive(Debug, Clone)]
@@ -563,7 +563,7 @@ impl<'a, Q: ?Sized, K: ?Sized> Query<'a, Q, K> {
     }
   }
 }
-impl<Q, K> Equivalent<Query<'_, Q, K>> for Key<K>
+impl<Q, K> skiplist_equivalent::Equivalent<Query<'_, Q, K>> for Key<K>
 where
   K: Equivalent<Q>,
   Q: ?Sized,
@@ -573,7 +573,7 @@ where
     Equivalent::equivalent(&self.key, key.query) && key.version == self.version
   }
 }
-impl<Q, K> Comparable<Query<'_, Q, K>> for Key<K>
+impl<Q, K> skiplist_equivalent::Comparable<Query<'_, Q, K>> for Key<K>
 where
   K: Comparable<Q>,
   Q: ?Sized,
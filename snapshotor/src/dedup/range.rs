@@ -3,11 +3,12 @@ use core::{
   ops::{Bound, RangeBounds},
 };
 
-use dbutils::equivalentor::{Comparator, QueryComparator, QueryRangeComparator};
+use dbutils::equivalentor::QueryComparator;
 
 use crate::{
-  next_back_dedup, next_dedup, sealed::SealedRange, Builder, Cursor, DoubleEndedCursor, Entry,
-  Seekable, Validator,
+  compare_keys, consume, exhausted, key_bytes_pair, next_back_dedup, next_dedup, same_key,
+  sealed::SealedRange, Builder, Cursor, DoubleEndedCursor, Entry, KeyValidator, Seekable,
+  Validator, VersionedValidator,
 };
 
 struct RangeKeyValidator<'a, C, R, Q, E, V>
@@ -21,8 +22,11 @@ where
   key_validator: &'a V,
   range: &'a R,
   comparator: &'a C,
-  last: Option<&'a E::Key>,
-  _e: PhantomData<E>,
+  /// The entry yielded last from the end being advanced.
+  last: Option<&'a E>,
+  /// The current head and tail, which are known to be in the range.
+  head: Option<&'a E>,
+  tail: Option<&'a E>,
   _q: PhantomData<Q>,
 }
 
@@ -39,20 +43,23 @@ where
     key_validator: &'a V,
     range: &'a R,
     comparator: &'a C,
-    last: Option<&'a E::Key>,
+    last: Option<&'a E>,
+    head: Option<&'a E>,
+    tail: Option<&'a E>,
   ) -> Self {
     Self {
       key_validator,
       range,
       comparator,
       last,
-      _e: PhantomData,
+      head,
+      tail,
       _q: PhantomData,
     }
   }
 }
 
-impl<C, R, Q, E, V> Validator<E::Key> for RangeKeyValidator<'_, C, R, Q, E, V>
+impl<C, R, Q, E, V> KeyValidator<E> for RangeKeyValidator<'_, C, R, Q, E, V>
 where
   C: QueryComparator<E::Key, Q>,
   R: RangeBounds<Q>,
@@ -61,15 +68,31 @@ where
   V: Validator<E::Key>,
 {
   #[inline]
-  fn validate(&self, key: &E::Key) -> bool {
-    let same = if let Some(last) = self.last {
-      self.comparator.equivalent(key, last)
-    } else {
-      false
+  fn validate_key(&self, ent: &E) -> bool {
+    let same = match self.last {
+      Some(last) => same_key(self.comparator, ent, last),
+      None => false,
     };
-    !same
-      && self.key_validator.validate(key)
-      && self.comparator.query_compare_contains(self.range, key)
+    if same || !self.key_validator.validate(ent.key()) {
+      return false;
+    }
+
+    // a key between the head and the tail is in the range, as both of them are.
+    let above_start = match self
+      .head
+      .and_then(|h| key_bytes_pair(self.comparator, ent, h))
+    {
+      Some((a, h)) if a >= h => true,
+      _ => above_lower_bound_compare(self.comparator, &self.range.start_bound(), ent.key()),
+    };
+    let below_end = match self
+      .tail
+      .and_then(|t| key_bytes_pair(self.comparator, ent, t))
+    {
+      Some((a, t)) if a <= t => true,
+      _ => below_upper_bound_compare(self.comparator, &self.range.end_bound(), ent.key()),
+    };
+    above_start && below_end
   }
 }
 
@@ -106,6 +129,9 @@ where
       head: None,
       tail: None,
       query_version: version,
+      remaining: builder.limit,
+      head_done: false,
+      tail_done: false,
       range,
       _q: PhantomData,
     }
@@ -129,6 +155,9 @@ where
   tail: Option<E>,
   head: Option<E>,
   query_version: E::Version,
+  remaining: Option<usize>,
+  head_done: bool,
+  tail_done: bool,
   range: R,
   _q: PhantomData<Q>,
 }
@@ -158,17 +187,44 @@ where
     self.tail.as_ref()
   }
 
+  /// Returns `true` if the iterator has been driven from either end.
+  ///
+  /// The underlying iterator is not touched until the first call to `next` or `next_back`,
+  /// so an iterator which has not started can still be inspected without executing the scan.
+  #[inline]
+  pub const fn is_started(&self) -> bool {
+    self.head.is_some() || self.tail.is_some() || self.head_done || self.tail_done
+  }
+
   /// Returns the range.
   #[inline]
   pub const fn range(&self) -> &R {
     &self.range
   }
+
+  /// Returns the range, the same as [`range`](Self::range).
+  ///
+  /// The name matches the `bounds` accessor of other scan iterators, for generic code.
+  #[inline]
+  pub const fn bounds(&self) -> &R {
+    &self.range
+  }
+
+  /// Returns the start and end bounds the iterator seeks with.
+  ///
+  /// The start bound is used to seek the head via [`Seekable::lower_bound`], and the end bound
+  /// is used to seek the tail via [`Seekable::upper_bound`]. Calling this does not drive the
+  /// iterator.
+  #[inline]
+  pub fn effective_bounds(&self) -> (Bound<&Q>, Bound<&Q>) {
+    (self.range.start_bound(), self.range.end_bound())
+  }
 }
 
 impl<R, Q, S, E, C, K, V> Iterator for Range<R, Q, S, E, C, K, V>
 where
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   S: Seekable<Q, Entry = E>,
   E: Cursor + Clone,
   C: QueryComparator<E::Key, Q>,
@@ -178,6 +234,10 @@ where
   type Item = E;
 
   fn next(&mut self) -> Option<Self::Item> {
+    if self.head_done || exhausted(&self.remaining) {
+      return None;
+    }
+
     let next_head = match self.head.as_ref() {
       Some(head) => head.next(),
       None => self.seeker.lower_bound(self.range.start_bound()),
@@ -187,7 +247,9 @@ where
       &self.key_validator,
       &self.range,
       &self.comparator,
-      self.head.as_ref().map(|h| h.key()),
+      self.head.as_ref(),
+      self.head.as_ref(),
+      self.tail.as_ref(),
     );
 
     self.head = next_dedup(
@@ -201,10 +263,11 @@ where
     if let Some(ref h) = self.head {
       match &self.tail {
         Some(t) => {
-          let bound = Bound::Excluded(t.key());
-          if !below_upper_bound(&self.comparator, &bound, h.key()) {
+          if compare_keys(&self.comparator, t, h).is_le() {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
         None => {
@@ -212,19 +275,25 @@ where
           if !below_upper_bound_compare(&self.comparator, &bound, h.key()) {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
       }
     }
 
-    self.head.clone()
+    if self.head.is_none() {
+      self.head_done = true;
+    }
+
+    consume(&mut self.remaining, self.head.clone())
   }
 }
 
 impl<R, Q, S, E, C, K, V> DoubleEndedIterator for Range<R, Q, S, E, C, K, V>
 where
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   S: Seekable<Q, Entry = E>,
   E: Entry + DoubleEndedCursor + Clone,
   C: QueryComparator<E::Key, Q>,
@@ -232,6 +301,10 @@ where
   R: RangeBounds<Q>,
 {
   fn next_back(&mut self) -> Option<Self::Item> {
+    if self.tail_done || exhausted(&self.remaining) {
+      return None;
+    }
+
     let next_tail = match self.tail.as_ref() {
       Some(tail) => tail.next_back(),
       None => self.seeker.upper_bound(self.range.end_bound()),
@@ -241,7 +314,9 @@ where
       &self.key_validator,
       &self.range,
       &self.comparator,
-      self.tail.as_ref().map(|t| t.key()),
+      self.tail.as_ref(),
+      self.head.as_ref(),
+      self.tail.as_ref(),
     );
     self.tail = next_back_dedup(
       next_tail,
@@ -254,10 +329,11 @@ where
     if let Some(ref t) = self.tail {
       match &self.head {
         Some(h) => {
-          let bound = Bound::Excluded(h.key());
-          if !above_lower_bound(&self.comparator, &bound, t.key()) {
+          if compare_keys(&self.comparator, h, t).is_ge() {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
         None => {
@@ -265,12 +341,18 @@ where
           if !above_lower_bound_compare(&self.comparator, &bound, t.key()) {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
       }
     }
 
-    self.tail.clone()
+    if self.tail.is_none() {
+      self.tail_done = true;
+    }
+
+    consume(&mut self.remaining, self.tail.clone())
   }
 }
 
@@ -288,19 +370,6 @@ where
   }
 }
 
-/// Helper function to check if a value is above a lower bound
-fn above_lower_bound<C, K>(cmp: &C, bound: &Bound<&K>, other: &K) -> bool
-where
-  C: Comparator<K>,
-  K: ?Sized,
-{
-  match *bound {
-    Bound::Unbounded => true,
-    Bound::Included(key) => cmp.compare(key, other).is_le(),
-    Bound::Excluded(key) => cmp.compare(key, other).is_lt(),
-  }
-}
-
 /// Helper function to check if a value is below an upper bound
 fn below_upper_bound_compare<C, V, T>(cmp: &C, bound: &Bound<&T>, other: &V) -> bool
 where
@@ -314,16 +383,3 @@ where
     Bound::Excluded(key) => cmp.query_compare(other, key).is_lt(),
   }
 }
-
-/// Helper function to check if a value is below an upper bound
-fn below_upper_bound<C, K>(cmp: &C, bound: &Bound<&K>, other: &K) -> bool
-where
-  C: Comparator<K>,
-  K: ?Sized,
-{
-  match *bound {
-    Bound::Unbounded => true,
-    Bound::Included(key) => cmp.compare(key, other).is_ge(),
-    Bound::Excluded(key) => cmp.compare(key, other).is_gt(),
-  }
-}
@@ -1,8 +1,9 @@
 use dbutils::equivalentor::Comparator;
 
 use crate::{
-  next_back_dedup, next_dedup, sealed::SealedIter, Builder, Cursor, DoubleEndedCursor, Entry,
-  Rewindable, Validator,
+  compare_keys, consume, exhausted, next_back_dedup, next_dedup, same_key, sealed::SealedIter,
+  Builder, Cursor, DoubleEndedCursor, Entry, KeyValidator, Rewindable, Validator,
+  VersionedValidator,
 };
 
 struct RefIterKeyValidator<'a, C, E, V>
@@ -13,7 +14,7 @@ where
 {
   key_validator: &'a V,
   comparator: &'a C,
-  last: Option<&'a E::Key>,
+  last: Option<&'a E>,
 }
 
 impl<'a, C, E, V> RefIterKeyValidator<'a, C, E, V>
@@ -23,7 +24,7 @@ where
   V: Validator<E::Key>,
 {
   #[inline]
-  const fn new(key_validator: &'a V, comparator: &'a C, last: Option<&'a E::Key>) -> Self {
+  const fn new(key_validator: &'a V, comparator: &'a C, last: Option<&'a E>) -> Self {
     Self {
       key_validator,
       comparator,
@@ -32,21 +33,20 @@ where
   }
 }
 
-impl<C, E, V> Validator<E::Key> for RefIterKeyValidator<'_, C, E, V>
+impl<C, E, V> KeyValidator<E> for RefIterKeyValidator<'_, C, E, V>
 where
   C: Comparator<E::Key>,
   E: Entry,
   V: Validator<E::Key>,
 {
   #[inline]
-  fn validate(&self, key: &E::Key) -> bool {
-    let same = if let Some(last) = self.last {
-      self.comparator.equivalent(key, last)
-    } else {
-      false
+  fn validate_key(&self, ent: &E) -> bool {
+    let same = match self.last {
+      Some(last) => same_key(self.comparator, ent, last),
+      None => false,
     };
 
-    !same && self.key_validator.validate(key)
+    !same && self.key_validator.validate(ent.key())
   }
 }
 
@@ -64,6 +64,9 @@ where
   tail: Option<E>,
   head: Option<E>,
   query_version: E::Version,
+  remaining: Option<usize>,
+  head_done: bool,
+  tail_done: bool,
 }
 
 impl<'a, E, R, C, K, V> SealedIter<E> for RefIter<'a, E, R, C, K, V>
@@ -93,6 +96,9 @@ where
       head: None,
       tail: None,
       query_version: version,
+      remaining: builder.limit,
+      head_done: false,
+      tail_done: false,
     }
   }
 }
@@ -118,29 +124,39 @@ where
   pub const fn tail(&self) -> Option<&E> {
     self.tail.as_ref()
   }
+
+  /// Returns `true` if the iterator has been driven from either end.
+  ///
+  /// The underlying iterator is not touched until the first call to `next` or `next_back`,
+  /// so an iterator which has not started can still be inspected without executing the scan.
+  #[inline]
+  pub const fn is_started(&self) -> bool {
+    self.head.is_some() || self.tail.is_some() || self.head_done || self.tail_done
+  }
 }
 
 impl<E, R, C, K, V> Iterator for RefIter<'_, E, R, C, K, V>
 where
   C: Comparator<E::Key>,
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   R: Rewindable<Entry = E>,
   E: Cursor + Clone,
 {
   type Item = E;
 
   fn next(&mut self) -> Option<Self::Item> {
+    if self.head_done || exhausted(&self.remaining) {
+      return None;
+    }
+
     let mut next_head = match self.head.as_ref() {
       Some(head) => head.next(),
       None => self.rewinder.first(),
     };
 
-    let kv = RefIterKeyValidator::<C, E, K>::new(
-      &self.key_validator,
-      self.comparator,
-      self.head.as_ref().map(|h| h.key()),
-    );
+    let kv =
+      RefIterKeyValidator::<C, E, K>::new(&self.key_validator, self.comparator, self.head.as_ref());
 
     next_head = next_dedup(
       next_head,
@@ -152,21 +168,21 @@ where
 
     match (next_head, &self.tail) {
       (Some(next), Some(t))
-        if self
-          .comparator
-          .compare(next.key(), t.key())
+        if compare_keys(&self.comparator, &next, t)
           .then_with(|| t.version().cmp(&next.version()))
           .is_ge() =>
       {
         self.head = Some(next);
+        self.head_done = true;
         None
       }
       (Some(next), _) => {
         self.head = Some(next);
-        self.head.clone()
+        consume(&mut self.remaining, self.head.clone())
       }
       (None, _) => {
         self.head = None;
+        self.head_done = true;
         None
       }
     }
@@ -177,21 +193,22 @@ impl<E, R, C, K, V> DoubleEndedIterator for RefIter<'_, E, R, C, K, V>
 where
   C: Comparator<E::Key>,
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   R: Rewindable<Entry = E>,
   E: DoubleEndedCursor + Clone,
 {
   fn next_back(&mut self) -> Option<Self::Item> {
+    if self.tail_done || exhausted(&self.remaining) {
+      return None;
+    }
+
     let mut next_tail = match self.tail.as_ref() {
       Some(tail) => tail.next_back(),
       None => self.rewinder.last(),
     };
 
-    let kv = RefIterKeyValidator::<C, E, K>::new(
-      &self.key_validator,
-      self.comparator,
-      self.tail.as_ref().map(|h| h.key()),
-    );
+    let kv =
+      RefIterKeyValidator::<C, E, K>::new(&self.key_validator, self.comparator, self.tail.as_ref());
 
     next_tail = next_back_dedup(
       next_tail,
@@ -203,21 +220,21 @@ where
 
     match (&self.head, next_tail) {
       (Some(h), Some(next))
-        if self
-          .comparator
-          .compare(h.key(), next.key())
+        if compare_keys(&self.comparator, h, &next)
           .then_with(|| h.version().cmp(&next.version()))
           .is_ge() =>
       {
         self.tail = Some(next);
+        self.tail_done = true;
         None
       }
       (_, Some(next)) => {
         self.tail = Some(next);
-        self.tail.clone()
+        consume(&mut self.remaining, self.tail.clone())
       }
       (_, None) => {
         self.tail = None;
+        self.tail_done = true;
         None
       }
     }
@@ -4,10 +4,18 @@
 #![cfg_attr(docsrs, allow(unused_attributes))]
 #![deny(missing_docs)]
 
-use core::ops::{Bound, RangeBounds};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::{
+  borrow::Borrow,
+  iter::FusedIterator,
+  ops::{Bound, RangeBounds},
+};
 
 pub use dbutils::equivalentor;
-use equivalentor::{Ascend, Equivalentor};
+use dbutils::{equivalent::VersionedQuery, CheapClone};
+use equivalentor::{Ascend, Comparator, Equivalentor};
 
 /// Provides deduplication functionality for iterators and ranges.
 ///
@@ -36,6 +44,31 @@ pub mod dedup;
 /// - Ensures iteration only includes entries meeting specified criteria
 pub mod valid;
 
+/// Provides the raw version-filtered iterator.
+///
+/// This module ensures that:
+/// - Only entries less than or equal to the specified query version are yielded
+/// - Tombstones and every version of the same key are yielded, in the order of the underlying structure
+///
+/// # Key Features
+/// - Version-based filtering of entries, without calling any validator
+/// - Suitable for dumping the version history visible to a reader
+pub mod raw;
+
+mod merge;
+pub use merge::{HeapEntry, MergeIter};
+
+mod sorted;
+pub use sorted::{EntrySlice, SliceCursor, SliceEntry, SortedSlice};
+
+mod sorted_iter;
+pub use sorted_iter::{dedup_sorted_iter, DedupSortedIter};
+
+mod try_cursor;
+pub use try_cursor::{
+  try_dedup, try_dedup_back, try_valid, try_valid_back, TryCursor, TryDoubleEndedCursor,
+};
+
 mod sealed;
 
 /// A trait for types that can be finalized to a `Range`.
@@ -88,8 +121,11 @@ where
 }
 
 /// A no-op validator.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct NoopValidator;
 
+impl CheapClone for NoopValidator {}
+
 impl<T: ?Sized> Validator<T> for NoopValidator {
   #[inline(always)]
   fn validate(&self, _: &T) -> bool {
@@ -110,6 +146,27 @@ where
   }
 }
 
+/// Validate a value together with the version of its entry.
+///
+/// Every [`Validator`] is a versioned validator which ignores the version, so the value
+/// validator of a [`Builder`] can be either of them.
+pub trait VersionedValidator<Ver: ?Sized, T: ?Sized> {
+  /// Returns `true` if the value of the given version is valid.
+  fn validate(&self, version: &Ver, value: &T) -> bool;
+}
+
+impl<Ver, T, V> VersionedValidator<Ver, T> for V
+where
+  Ver: ?Sized,
+  T: ?Sized,
+  V: Validator<T>,
+{
+  #[inline]
+  fn validate(&self, _: &Ver, value: &T) -> bool {
+    <V as Validator<T>>::validate(self, value)
+  }
+}
+
 /// Entry absbstrations
 pub trait Entry {
   /// The key type of the entry.
@@ -127,6 +184,17 @@ pub trait Entry {
 
   /// Returns the version of the entry.
   fn version(&self) -> Self::Version;
+
+  /// Returns the encoded bytes of the key, if available.
+  ///
+  /// When the comparator [orders keys by `Ord`](Comparator::is_ascend), e.g. [`Ascend`], and two
+  /// entries of the same cursor both return `Some`, iterators compare the bytes directly instead
+  /// of calling the comparator. Only return `Some` if the lexicographic order of the bytes
+  /// matches the [`Ord`] of the keys.
+  #[inline]
+  fn key_bytes(&self) -> Option<&[u8]> {
+    None
+  }
 }
 
 /// A trait for cursor entries.
@@ -137,6 +205,29 @@ pub trait Cursor: Entry {
   fn next(&self) -> Option<Self>
   where
     Self: Sized;
+
+  /// Returns the first entry after this entry whose key is different from the key of this entry.
+  ///
+  /// The default implementation walks entry by entry. Implementors backed by a seekable
+  /// structure can override it to jump directly to the next distinct key, which avoids
+  /// walking through all versions of a key with many versions.
+  fn skip_to_different_key<E>(&self, equivalentor: &E) -> Option<Self>
+  where
+    Self: Sized,
+    E: Equivalentor<Self::Key>,
+  {
+    let key = self.key();
+    let mut next = self.next();
+    while let Some(ent) = next {
+      if !equivalentor.equivalent(ent.key(), key) {
+        return Some(ent);
+      }
+
+      next = ent.next();
+    }
+
+    None
+  }
 }
 
 /// A trait for cursor entries.
@@ -149,6 +240,85 @@ pub trait DoubleEndedCursor: Cursor {
     Self: Sized;
 }
 
+#[cfg(feature = "alloc")]
+const _: () = {
+  use alloc::sync::Arc;
+
+  impl<E: Entry> Entry for Arc<E> {
+    type Key = E::Key;
+    type Value = E::Value;
+    type Version = E::Version;
+
+    #[inline]
+    fn key(&self) -> &Self::Key {
+      E::key(self)
+    }
+
+    #[inline]
+    fn value(&self) -> &Self::Value {
+      E::value(self)
+    }
+
+    #[inline]
+    fn version(&self) -> Self::Version {
+      E::version(self)
+    }
+
+    #[inline]
+    fn key_bytes(&self) -> Option<&[u8]> {
+      E::key_bytes(self)
+    }
+  }
+
+  impl<E: Cursor> Cursor for Arc<E> {
+    #[inline]
+    fn next(&self) -> Option<Self> {
+      E::next(self).map(Arc::new)
+    }
+
+    #[inline]
+    fn skip_to_different_key<C>(&self, equivalentor: &C) -> Option<Self>
+    where
+      C: Equivalentor<Self::Key>,
+    {
+      E::skip_to_different_key(self, equivalentor).map(Arc::new)
+    }
+  }
+
+  impl<E: DoubleEndedCursor> DoubleEndedCursor for Arc<E> {
+    #[inline]
+    fn next_back(&self) -> Option<Self> {
+      E::next_back(self).map(Arc::new)
+    }
+  }
+};
+
+/// A plain `(key, version, value)` tuple is an entry, which is handy for tests and small
+/// in-memory cases. Use an [`EntrySlice`] to navigate a sorted slice of tuples.
+impl<K, Ver, V> Entry for (K, Ver, V)
+where
+  Ver: Ord + Copy,
+{
+  type Key = K;
+  type Value = V;
+  type Version = Ver;
+
+  #[inline]
+  fn key(&self) -> &Self::Key {
+    &self.0
+  }
+
+  #[inline]
+  fn value(&self) -> &Self::Value {
+    &self.2
+  }
+
+  #[inline]
+  fn version(&self) -> Self::Version {
+    self.1
+  }
+}
+
 /// A trait for rewinding between the front and back.
 pub trait Rewindable {
   /// The entry can be yielded by the seeker.
@@ -161,6 +331,72 @@ pub trait Rewindable {
   fn last(&self) -> Option<Self::Entry>;
 }
 
+/// A [`Rewindable`] which yields the first and the last entry from two closures.
+///
+/// The closures are called lazily, when the iterator is driven from the front or the back
+/// for the first time, so ad-hoc initialization does not need a named type.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapshotor::{
+///   dedup, equivalentor::Ascend, Builder, Entry, InitializorFn, NoopValidator, Rewindable,
+///   SortedSlice,
+/// };
+///
+/// let data = [("a", 1, Some(1)), ("b", 1, Some(2))];
+/// let slice = SortedSlice::new(&data);
+/// let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+///   Builder::new(InitializorFn::new(|| slice.first(), || slice.last())).iter(1);
+/// assert_eq!(it.map(|ent| *ent.key()).collect::<Vec<_>>(), ["a", "b"]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct InitializorFn<F, L>(pub F, pub L);
+
+impl<F, L> InitializorFn<F, L> {
+  /// Creates a new `InitializorFn` from the closures returning the first and the last entry.
+  #[inline]
+  pub const fn new(first: F, last: L) -> Self {
+    Self(first, last)
+  }
+}
+
+impl<F, L> core::fmt::Debug for InitializorFn<F, L> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("InitializorFn").finish()
+  }
+}
+
+impl<F, L, E> Rewindable for InitializorFn<F, L>
+where
+  F: Fn() -> Option<E>,
+  L: Fn() -> Option<E>,
+{
+  type Entry = E;
+
+  #[inline]
+  fn first(&self) -> Option<Self::Entry> {
+    (self.0)()
+  }
+
+  #[inline]
+  fn last(&self) -> Option<Self::Entry> {
+    (self.1)()
+  }
+}
+
+/// A trait for structures which know the newest version they contain.
+///
+/// Used by [`Builder::iter_latest`] and [`Builder::range_latest`] to read the latest
+/// snapshot without the caller passing a version.
+pub trait Versioned {
+  /// The version type of the structure.
+  type Version;
+
+  /// Returns the maximum version in the structure, or `None` if it is empty.
+  fn max_version(&self) -> Option<Self::Version>;
+}
+
 /// A trait for seeking between entries.
 pub trait Seekable<Q: ?Sized> {
   /// The entry can be yielded by the seeker.
@@ -173,17 +409,107 @@ pub trait Seekable<Q: ?Sized> {
   fn upper_bound(&self, bound: Bound<&Q>) -> Option<Self::Entry>;
 }
 
+/// Converts the start bound of a range into the bound a [`Seekable::lower_bound`] implementation
+/// should seek to, for storages whose entries are ordered by key ascending and then by version
+/// descending (see [`VersionedKey`](dbutils::equivalent::VersionedKey)).
+///
+/// - `Included(key)` pins the query to `version`, so the seek lands directly on the newest
+///   version of `key` visible to the reader and skips the newer, invisible ones.
+/// - `Excluded(key)` pins the query to version `0`, which sorts after every version of `key`,
+///   so the seek lands on the first entry of the next key instead of on an older version of `key`.
+/// - `Unbounded` stays unbounded.
+///
+/// ## Example
+///
+/// ```rust
+/// use core::ops::Bound;
+/// use snapshotor::seek_lower;
+///
+/// let bound = seek_lower(Bound::Included(&"a"), 3);
+/// assert!(matches!(bound, Bound::Included(q) if q.version() == 3));
+///
+/// let bound = seek_lower(Bound::Excluded(&"a"), 3);
+/// assert!(matches!(bound, Bound::Excluded(q) if q.version() == 0));
+/// ```
+#[inline]
+pub fn seek_lower<Q: ?Sized>(bound: Bound<&Q>, version: u64) -> Bound<VersionedQuery<'_, Q>> {
+  match bound {
+    Bound::Included(key) => Bound::Included(VersionedQuery::new(key, version)),
+    Bound::Excluded(key) => Bound::Excluded(VersionedQuery::new(key, 0)),
+    Bound::Unbounded => Bound::Unbounded,
+  }
+}
+
+/// Converts the end bound of a range into the bound a [`Seekable::upper_bound`] implementation
+/// should seek to, for storages whose entries are ordered by key ascending and then by version
+/// descending (see [`VersionedKey`](dbutils::equivalent::VersionedKey)).
+///
+/// Unlike [`seek_lower`], the seek does not depend on the query version. A backward seek must
+/// land on the oldest version of the boundary key, because the cursor then walks towards the
+/// newer versions and picks the one visible to the reader. Pinning the query to the query
+/// version would skip the versions in between instead.
+///
+/// - `Included(key)` pins the query to version `0`, which sorts after every version of `key`, so
+///   the seek lands on the oldest version of `key`.
+/// - `Excluded(key)` pins the query to version `u64::MAX`, which sorts before every version of
+///   `key`, so the seek lands on the last entry of the previous key.
+/// - `Unbounded` stays unbounded.
+///
+/// ## Example
+///
+/// ```rust
+/// use core::ops::Bound;
+/// use snapshotor::seek_upper;
+///
+/// let bound = seek_upper(Bound::Included(&"a"));
+/// assert!(matches!(bound, Bound::Included(q) if q.version() == 0));
+///
+/// let bound = seek_upper(Bound::Excluded(&"a"));
+/// assert!(matches!(bound, Bound::Excluded(q) if q.version() == u64::MAX));
+/// ```
+#[inline]
+pub fn seek_upper<Q: ?Sized>(bound: Bound<&Q>) -> Bound<VersionedQuery<'_, Q>> {
+  match bound {
+    Bound::Included(key) => Bound::Included(VersionedQuery::new(key, 0)),
+    Bound::Excluded(key) => Bound::Excluded(VersionedQuery::new(key, u64::MAX)),
+    Bound::Unbounded => Bound::Unbounded,
+  }
+}
+
+/// A query version which can be compared with the version `V` of the entries.
+///
+/// Implemented for every type the entry version borrows as, so the extension methods can be
+/// queried with the raw version a newtype version wraps.
+pub trait QueryVersion<V> {
+  /// Returns `true` if `version` is newer than this query version, i.e. invisible to the reader.
+  fn precedes(&self, version: &V) -> bool;
+}
+
+impl<V, Q> QueryVersion<V> for Q
+where
+  V: Borrow<Q>,
+  Q: ?Sized + Ord,
+{
+  #[inline]
+  fn precedes(&self, version: &V) -> bool {
+    version.borrow() > self
+  }
+}
+
 /// Extension methods for single-directional cursors with additional validation and deduplication capabilities.
 ///
 /// This trait adds advanced traversal methods to the base [`Cursor`] trait, allowing for:
 /// - Version-based filtering
 /// - Key and value validation
 /// - Deduplication of entries
+///
+/// The query version can be any [`QueryVersion`], e.g. a `u64` for entries whose version is a
+/// newtype implementing `Borrow<u64>`.
 pub trait CursorExt: Cursor {
   /// Advances to the next entry that is valid according to the specified version and validators.
   fn next_valid<E, K, V>(
     &self,
-    version: &Self::Version,
+    version: &(impl ?Sized + QueryVersion<Self::Version>),
     key_validator: &K,
     value_validator: &V,
   ) -> Option<Self>
@@ -191,7 +517,7 @@ pub trait CursorExt: Cursor {
     Self: Sized,
     E: Equivalentor<Self::Key>,
     K: Validator<Self::Key>,
-    V: Validator<Self::Value>,
+    V: VersionedValidator<Self::Version, Self::Value>,
   {
     let curr = self.next();
     next_valid(curr, version, key_validator, value_validator)
@@ -201,9 +527,12 @@ pub trait CursorExt: Cursor {
   ///
   /// - Skips entries that do not meet version or validation criteria.
   /// - When multiple entries exist for the same key, returns the entry with the maximum version.
+  ///
+  /// See [`next_back_dedup`](DoubleEndedCursorExt::next_back_dedup) for the exact selection rule,
+  /// which both directions share.
   fn next_dedup<E, K, V>(
     &self,
-    version: &Self::Version,
+    version: &(impl ?Sized + QueryVersion<Self::Version>),
     equivalentor: &E,
     key_validator: &K,
     value_validator: &V,
@@ -212,10 +541,79 @@ pub trait CursorExt: Cursor {
     Self: Sized,
     E: Equivalentor<Self::Key>,
     K: Validator<Self::Key>,
-    V: Validator<Self::Value>,
+    V: VersionedValidator<Self::Version, Self::Value>,
   {
     let curr = self.next();
-    next_dedup(curr, version, equivalentor, key_validator, value_validator)
+    next_dedup(
+      curr,
+      version,
+      equivalentor,
+      &ByKey(key_validator),
+      value_validator,
+    )
+  }
+
+  /// Advances past `n` valid entries and returns the next valid one, as if
+  /// [`next_valid`](CursorExt::next_valid) were called `n + 1` times.
+  ///
+  /// This is useful for offset based pagination. The skipped entries are only
+  /// inspected by the validators, they are not yielded.
+  fn nth_valid<K, V>(
+    &self,
+    n: usize,
+    version: &(impl ?Sized + QueryVersion<Self::Version>),
+    key_validator: &K,
+    value_validator: &V,
+  ) -> Option<Self>
+  where
+    Self: Sized,
+    K: Validator<Self::Key>,
+    V: VersionedValidator<Self::Version, Self::Value>,
+  {
+    let mut curr = next_valid(self.next(), version, key_validator, value_validator);
+    for _ in 0..n {
+      curr = next_valid(curr?.next(), version, key_validator, value_validator);
+    }
+    curr
+  }
+
+  /// Advances past `n` deduplicated entries and returns the next one.
+  ///
+  /// Each step selects an entry with [`next_dedup`](CursorExt::next_dedup) and then moves to the
+  /// next distinct key, so the older versions of a skipped key are never counted. This is
+  /// useful for offset based pagination. The skipped entries are only inspected by the
+  /// validators, they are not yielded.
+  fn nth_dedup<E, K, V>(
+    &self,
+    n: usize,
+    version: &(impl ?Sized + QueryVersion<Self::Version>),
+    equivalentor: &E,
+    key_validator: &K,
+    value_validator: &V,
+  ) -> Option<Self>
+  where
+    Self: Sized,
+    E: Equivalentor<Self::Key>,
+    K: Validator<Self::Key>,
+    V: VersionedValidator<Self::Version, Self::Value>,
+  {
+    let mut curr = next_dedup(
+      self.next(),
+      version,
+      equivalentor,
+      &ByKey(key_validator),
+      value_validator,
+    );
+    for _ in 0..n {
+      curr = next_dedup(
+        curr?.skip_to_different_key(equivalentor),
+        version,
+        equivalentor,
+        &ByKey(key_validator),
+        value_validator,
+      );
+    }
+    curr
   }
 }
 
@@ -229,7 +627,7 @@ pub trait DoubleEndedCursorExt: DoubleEndedCursor {
   /// Moves backwards to the next entry that is valid according to the specified version and validators.
   fn next_back_valid<E, K, V>(
     &self,
-    version: &Self::Version,
+    version: &(impl ?Sized + QueryVersion<Self::Version>),
     key_validator: &K,
     value_validator: &V,
   ) -> Option<Self>
@@ -237,9 +635,9 @@ pub trait DoubleEndedCursorExt: DoubleEndedCursor {
     Self: Sized,
     E: Equivalentor<Self::Key>,
     K: Validator<Self::Key>,
-    V: Validator<Self::Value>,
+    V: VersionedValidator<Self::Version, Self::Value>,
   {
-    let curr = self.next();
+    let curr = self.next_back();
     next_back_valid(curr, version, key_validator, value_validator)
   }
 
@@ -247,9 +645,14 @@ pub trait DoubleEndedCursorExt: DoubleEndedCursor {
   ///
   /// - Skips entries that do not meet version or validation criteria.
   /// - When multiple entries exist for the same key, returns the entry with the maximum version when moving backwards.
+  ///
+  /// For each key, the selected entry is the one with the greatest version that is
+  /// less than or equal to `version`; an entry whose version equals `version` is visible.
+  /// If the selected entry fails the value validator (e.g. a tombstone), the key is
+  /// skipped entirely rather than falling back to an older version.
   fn next_back_dedup<E, K, V>(
     &self,
-    version: &Self::Version,
+    version: &(impl ?Sized + QueryVersion<Self::Version>),
     equivalentor: &E,
     key_validator: &K,
     value_validator: &V,
@@ -258,21 +661,125 @@ pub trait DoubleEndedCursorExt: DoubleEndedCursor {
     Self: Sized,
     E: Equivalentor<Self::Key>,
     K: Validator<Self::Key>,
-    V: Validator<Self::Value>,
+    V: VersionedValidator<Self::Version, Self::Value>,
   {
     let curr = self.next_back();
-    next_back_dedup(curr, version, equivalentor, key_validator, value_validator)
+    next_back_dedup(
+      curr,
+      version,
+      equivalentor,
+      &ByKey(key_validator),
+      value_validator,
+    )
   }
 }
 
 impl<R> DoubleEndedCursorExt for R where R: DoubleEndedCursor + ?Sized {}
 
+/// Extension methods for [`Rewindable`] structures, which return the edge entries as a
+/// deduplicating iterator would yield them, without building one.
+pub trait RewindableExt: Rewindable {
+  /// Returns the first entry visible at `version` which passes both validators, i.e. the
+  /// first entry a [`dedup::Iter`] would yield.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use snapshotor::{
+  ///   equivalentor::Ascend, AnyValidator, Entry, NoopValidator, RewindableExt, SortedSlice,
+  /// };
+  ///
+  /// let data = [("a", 2, None), ("a", 1, Some(1)), ("b", 1, Some(2))];
+  /// let slice = SortedSlice::new(&data);
+  /// let live = AnyValidator(|v: &Option<i32>| v.is_some());
+  ///
+  /// // `a` is deleted at version 2.
+  /// let first = slice.first_valid(&2, &Ascend, &NoopValidator, &live).unwrap();
+  /// assert_eq!(*first.key(), "b");
+  /// let first = slice.first_valid(&1, &Ascend, &NoopValidator, &live).unwrap();
+  /// assert_eq!(*first.key(), "a");
+  /// ```
+  fn first_valid<E, K, V>(
+    &self,
+    version: &(impl ?Sized + QueryVersion<<Self::Entry as Entry>::Version>),
+    equivalentor: &E,
+    key_validator: &K,
+    value_validator: &V,
+  ) -> Option<Self::Entry>
+  where
+    Self::Entry: Cursor,
+    E: Equivalentor<<Self::Entry as Entry>::Key>,
+    K: Validator<<Self::Entry as Entry>::Key>,
+    V: VersionedValidator<<Self::Entry as Entry>::Version, <Self::Entry as Entry>::Value>,
+  {
+    next_dedup(
+      self.first(),
+      version,
+      equivalentor,
+      &ByKey(key_validator),
+      value_validator,
+    )
+  }
+
+  /// Returns the last entry visible at `version` which passes both validators, i.e. the
+  /// first entry a [`dedup::Iter`] would yield when iterated backwards.
+  fn last_valid<E, K, V>(
+    &self,
+    version: &(impl ?Sized + QueryVersion<<Self::Entry as Entry>::Version>),
+    equivalentor: &E,
+    key_validator: &K,
+    value_validator: &V,
+  ) -> Option<Self::Entry>
+  where
+    Self::Entry: DoubleEndedCursor,
+    E: Equivalentor<<Self::Entry as Entry>::Key>,
+    K: Validator<<Self::Entry as Entry>::Key>,
+    V: VersionedValidator<<Self::Entry as Entry>::Version, <Self::Entry as Entry>::Value>,
+  {
+    next_back_dedup(
+      self.last(),
+      version,
+      equivalentor,
+      &ByKey(key_validator),
+      value_validator,
+    )
+  }
+}
+
+impl<R> RewindableExt for R where R: Rewindable + ?Sized {}
+
 /// The builder for creating an iterator.
+///
+/// A configured builder can be cloned to construct several independent
+/// iterators, e.g. one per worker scanning a sub-range.
+#[derive(Clone)]
 pub struct Builder<I, C = Ascend, K = NoopValidator, V = NoopValidator> {
   comparator: C,
   key_validator: K,
   value_validator: V,
   initializor: I,
+  limit: Option<usize>,
+  sort_versions: bool,
+}
+
+impl<I, C, K, V> CheapClone for Builder<I, C, K, V>
+where
+  I: CheapClone,
+  C: CheapClone,
+  K: CheapClone,
+  V: CheapClone,
+{
+  #[inline]
+  fn cheap_clone(&self) -> Self {
+    Self {
+      comparator: self.comparator.cheap_clone(),
+      key_validator: self.key_validator.cheap_clone(),
+      value_validator: self.value_validator.cheap_clone(),
+      initializor: self.initializor.cheap_clone(),
+      limit: self.limit,
+      sort_versions: self.sort_versions,
+    }
+  }
 }
 
 impl<I, C, K, V> Default for Builder<I, C, K, V>
@@ -288,6 +795,8 @@ where
       key_validator: Default::default(),
       value_validator: Default::default(),
       initializor: Default::default(),
+      limit: None,
+      sort_versions: false,
     }
   }
 }
@@ -301,6 +810,8 @@ impl<I> Builder<I> {
       key_validator: NoopValidator,
       value_validator: NoopValidator,
       initializor: init,
+      limit: None,
+      sort_versions: false,
     }
   }
 }
@@ -314,6 +825,8 @@ impl<I, C, K, V> Builder<I, C, K, V> {
       key_validator: self.key_validator,
       value_validator: self.value_validator,
       initializor: self.initializor,
+      limit: self.limit,
+      sort_versions: self.sort_versions,
     }
   }
 
@@ -325,6 +838,8 @@ impl<I, C, K, V> Builder<I, C, K, V> {
       key_validator,
       value_validator: self.value_validator,
       initializor: self.initializor,
+      limit: self.limit,
+      sort_versions: self.sort_versions,
     }
   }
 
@@ -336,9 +851,44 @@ impl<I, C, K, V> Builder<I, C, K, V> {
       key_validator: self.key_validator,
       value_validator,
       initializor: self.initializor,
+      limit: self.limit,
+      sort_versions: self.sort_versions,
     }
   }
 
+  /// Sets a value validator which can inspect the version of the entry, e.g. to treat
+  /// values older than a snapshot boundary as invalid.
+  ///
+  /// The validator is called for the candidates at or below the query version, in the
+  /// same places a [`Validator`] set by [`with_value_validator`](Builder::with_value_validator)
+  /// would be.
+  #[inline]
+  pub fn with_versioned_value_validator<NV>(self, value_validator: NV) -> Builder<I, C, K, NV> {
+    self.with_value_validator(value_validator)
+  }
+
+  /// Caps the number of entries the finalized iterator will yield.
+  ///
+  /// The limit is shared between both ends of the iterator, and entries skipped by
+  /// deduplication or validation do not count against it.
+  #[inline]
+  pub fn take(mut self, n: usize) -> Self {
+    self.limit = Some(n);
+    self
+  }
+
+  /// Makes [`valid::Iter`] and [`valid::RefIter`] yield the versions of the same key strictly
+  /// in descending order (newest first) when iterating forwards, and in ascending order when
+  /// iterating backwards, regardless of how the underlying structure orders them.
+  ///
+  /// Each step scans all versions of the current key, so prefer to leave it off for
+  /// structures which already keep the versions of a key newest first.
+  #[inline]
+  pub fn sort_versions(mut self) -> Self {
+    self.sort_versions = true;
+    self
+  }
+
   /// Finalizes the builder into an iterator.
   #[inline]
   pub fn iter<E, F>(self, version: E::Version) -> F
@@ -350,6 +900,21 @@ impl<I, C, K, V> Builder<I, C, K, V> {
     F::new(version, self)
   }
 
+  /// Finalizes the builder into an iterator at the maximum version of the initializor.
+  ///
+  /// If the initializor is empty, the iterator is created at the default version.
+  #[inline]
+  pub fn iter_latest<E, F>(self) -> F
+  where
+    E: Entry,
+    E::Version: Default,
+    F: ToIter<E, Initializor = I, Comparator = C, KeyValidator = K, ValueValidator = V>,
+    I: Rewindable<Entry = E> + Versioned<Version = E::Version>,
+  {
+    let version = self.initializor.max_version().unwrap_or_default();
+    F::new(version, self)
+  }
+
   /// Finalizes the builder into a range.
   #[inline]
   pub fn range<E, F, Q, R>(self, version: E::Version, range: R) -> F
@@ -362,92 +927,694 @@ impl<I, C, K, V> Builder<I, C, K, V> {
   {
     F::range(version, range, self)
   }
-}
-
-fn next_dedup<ENT, E, K, V>(
-  mut curr: Option<ENT>,
-  version: &ENT::Version,
-  equivalentor: &E,
-  key_validator: &K,
-  value_validator: &V,
-) -> Option<ENT>
-where
-  ENT: Sized + Entry + Cursor,
-  E: Equivalentor<ENT::Key>,
-  K: Validator<ENT::Key>,
-  V: Validator<ENT::Value>,
-{
-  while let Some(ent) = curr {
-    let curr_key = ent.key();
-    // if the current version is larger than the query version, we should move next to find a smaller version.
-    if ent.version().gt(version) {
-      curr = ent.next();
-      continue;
-    }
-
-    // if the value of the entry is not in a valid state, we should move next to find a valid entry.
-    if !value_validator.validate(ent.value()) {
-      let mut next = ent.next();
-      loop {
-        match next {
-          None => return None,
-          Some(next_ent) => {
-            // if next's key is different from the current key, we should break the loop
-            if !equivalentor.equivalent(next_ent.key(), curr_key) {
-              curr = Some(next_ent);
-              break;
-            }
-
-            next = next_ent.next();
-          }
-        }
-      }
-
-      continue;
-    }
-
-    // if the key of the entry is not valid, we should move next to find a valid entry.
-    if key_validator.validate(curr_key) {
-      return Some(ent);
-    }
 
-    curr = ent.next();
+  /// Finalizes the builder into a range at the maximum version of the initializor.
+  ///
+  /// If the initializor is empty, the range is created at the default version.
+  #[inline]
+  pub fn range_latest<E, F, Q, R>(self, range: R) -> F
+  where
+    R: RangeBounds<Q>,
+    Q: ?Sized,
+    E: Entry,
+    E::Version: Default,
+    F: ToRange<Q, R, E, Initializor = I, Comparator = C, KeyValidator = K, ValueValidator = V>,
+    I: Seekable<Q, Entry = E> + Versioned<Version = E::Version>,
+  {
+    let version = self.initializor.max_version().unwrap_or_default();
+    F::range(version, range, self)
   }
 
-  None
-}
-
-fn next_back_dedup<ENT, E, K, V>(
-  mut curr: Option<ENT>,
-  version: &ENT::Version,
-  equivalentor: &E,
+  /// Finalizes the builder into a range with owned bounds.
+  ///
+  /// The bounds are stored in the range, so the caller does not have to keep them alive, e.g.
+  /// a range over `str` can be created from `String` bounds built on the fly.
+  #[inline]
+  pub fn range_owned<E, F, Q, T>(self, version: E::Version, range: (Bound<T>, Bound<T>)) -> F
+  where
+    T: Borrow<Q>,
+    Q: ?Sized,
+    E: Entry,
+    F: ToRange<
+      Q,
+      OwnedRange<T>,
+      E,
+      Initializor = I,
+      Comparator = C,
+      KeyValidator = K,
+      ValueValidator = V,
+    >,
+    I: Seekable<Q, Entry = E>,
+  {
+    let (start, end) = range;
+    F::range(version, OwnedRange { start, end }, self)
+  }
+
+  /// Finalizes the builder into a range which starts strictly after `last_key`.
+  ///
+  /// This is useful for keyset pagination, pass the last key yielded by the previous page to
+  /// resume the scan without overlapping. If `last_key` is before the start of `range`, the
+  /// range starts as usual, and if it is after the end of `range`, the range is empty.
+  #[inline]
+  pub fn range_after<'a, E, F, Q, R>(self, version: E::Version, range: R, last_key: &'a Q) -> F
+  where
+    R: RangeBounds<Q>,
+    Q: ?Sized,
+    C: Comparator<Q>,
+    E: Entry,
+    F: ToRange<
+      Q,
+      RangeAfter<'a, Q, R>,
+      E,
+      Initializor = I,
+      Comparator = C,
+      KeyValidator = K,
+      ValueValidator = V,
+    >,
+    I: Seekable<Q, Entry = E>,
+  {
+    let after = match range.start_bound() {
+      Bound::Included(start) | Bound::Excluded(start) => {
+        self.comparator.compare(last_key, start).is_ge()
+      }
+      Bound::Unbounded => true,
+    };
+
+    let range = RangeAfter {
+      range,
+      after: after.then_some(last_key),
+    };
+    F::range(version, range, self)
+  }
+
+  /// Finalizes the builder into a range which yields from the high end of `range` to the low
+  /// end, whatever the order of the comparator.
+  ///
+  /// `range` is written low to high, e.g. `a..=c`. If the comparator orders the start after the
+  /// end, i.e. it is descending, the bounds are swapped and the range is walked forwards,
+  /// otherwise it is walked backwards, so `range_rev(v, a..=c)` always yields `c` before `a`.
+  /// A range with an unbounded side cannot tell, and is taken in the order of the comparator.
+  #[inline]
+  pub fn range_rev<E, F, Q, R>(self, version: E::Version, range: R) -> Descending<F>
+  where
+    R: RangeBounds<Q>,
+    Q: ?Sized,
+    C: Comparator<Q>,
+    E: Entry,
+    F: ToRange<
+        Q,
+        RangeRev<R>,
+        E,
+        Initializor = I,
+        Comparator = C,
+        KeyValidator = K,
+        ValueValidator = V,
+      > + DoubleEndedIterator,
+    I: Seekable<Q, Entry = E>,
+  {
+    let swapped = match (range.start_bound(), range.end_bound()) {
+      (
+        Bound::Included(start) | Bound::Excluded(start),
+        Bound::Included(end) | Bound::Excluded(end),
+      ) => self.comparator.compare(start, end).is_gt(),
+      _ => false,
+    };
+
+    let range = RangeRev { range, swapped };
+    Descending {
+      iter: F::range(version, range, self),
+      forwards: swapped,
+    }
+  }
+}
+
+/// Creates an iterator from explicit components, without going through [`Builder`].
+///
+/// This is equivalent to building a [`Builder`] with the given components and calling
+/// [`Builder::iter`], and is useful when the builder's generic inference gets unwieldy.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapshotor::{
+///   dedup, equivalentor::Ascend, iter_from, AnyValidator, Entry, NoopValidator, SortedSlice,
+/// };
+///
+/// let data = [("a", 2, Some(2)), ("a", 1, Some(1)), ("b", 1, None)];
+/// let it: dedup::Iter<_, _, _, _, _> = iter_from(
+///   2,
+///   SortedSlice::new(&data),
+///   Ascend,
+///   NoopValidator,
+///   AnyValidator(|value: &Option<i32>| value.is_some()),
+/// );
+/// assert_eq!(
+///   it.map(|ent| (*ent.key(), ent.version())).collect::<Vec<_>>(),
+///   [("a", 2)]
+/// );
+/// ```
+#[inline]
+pub fn iter_from<E, F, I, C, K, V>(
+  version: E::Version,
+  initializor: I,
+  comparator: C,
+  key_validator: K,
+  value_validator: V,
+) -> F
+where
+  E: Entry,
+  F: ToIter<E, Initializor = I, Comparator = C, KeyValidator = K, ValueValidator = V>,
+  I: Rewindable<Entry = E>,
+{
+  Builder {
+    comparator,
+    key_validator,
+    value_validator,
+    initializor,
+    limit: None,
+    sort_versions: false,
+  }
+  .iter(version)
+}
+
+/// Creates a range from explicit components, without going through [`Builder`].
+///
+/// This is equivalent to building a [`Builder`] with the given components and calling
+/// [`Builder::range`].
+#[inline]
+pub fn range_from<E, F, Q, R, I, C, K, V>(
+  version: E::Version,
+  range: R,
+  initializor: I,
+  comparator: C,
+  key_validator: K,
+  value_validator: V,
+) -> F
+where
+  R: RangeBounds<Q>,
+  Q: ?Sized,
+  E: Entry,
+  F: ToRange<Q, R, E, Initializor = I, Comparator = C, KeyValidator = K, ValueValidator = V>,
+  I: Seekable<Q, Entry = E>,
+{
+  Builder {
+    comparator,
+    key_validator,
+    value_validator,
+    initializor,
+    limit: None,
+    sort_versions: false,
+  }
+  .range(version, range)
+}
+
+/// A range which starts strictly after a key, created by [`Builder::range_after`].
+pub struct RangeAfter<'a, Q: ?Sized, R> {
+  range: R,
+  after: Option<&'a Q>,
+}
+
+impl<'a, Q: ?Sized, R> RangeAfter<'a, Q, R> {
+  /// Returns the original range.
+  #[inline]
+  pub const fn range(&self) -> &R {
+    &self.range
+  }
+
+  /// Returns the key the range starts after, or `None` if the key is not after the start of the original range.
+  #[inline]
+  pub const fn after(&self) -> Option<&'a Q> {
+    self.after
+  }
+}
+
+impl<Q: ?Sized, R: RangeBounds<Q>> RangeBounds<Q> for RangeAfter<'_, Q, R> {
+  #[inline]
+  fn start_bound(&self) -> Bound<&Q> {
+    match self.after {
+      Some(after) => Bound::Excluded(after),
+      None => self.range.start_bound(),
+    }
+  }
+
+  #[inline]
+  fn end_bound(&self) -> Bound<&Q> {
+    self.range.end_bound()
+  }
+}
+
+/// A range whose bounds may be swapped to match the comparator, created by
+/// [`Builder::range_rev`].
+pub struct RangeRev<R> {
+  range: R,
+  swapped: bool,
+}
+
+impl<R> RangeRev<R> {
+  /// Returns the original range.
+  #[inline]
+  pub const fn range(&self) -> &R {
+    &self.range
+  }
+
+  /// Returns `true` if the bounds of the original range are swapped.
+  #[inline]
+  pub const fn is_swapped(&self) -> bool {
+    self.swapped
+  }
+}
+
+impl<Q: ?Sized, R: RangeBounds<Q>> RangeBounds<Q> for RangeRev<R> {
+  #[inline]
+  fn start_bound(&self) -> Bound<&Q> {
+    if self.swapped {
+      self.range.end_bound()
+    } else {
+      self.range.start_bound()
+    }
+  }
+
+  #[inline]
+  fn end_bound(&self) -> Bound<&Q> {
+    if self.swapped {
+      self.range.start_bound()
+    } else {
+      self.range.end_bound()
+    }
+  }
+}
+
+/// An iterator which yields a range from its high end to its low end, created by
+/// [`Builder::range_rev`].
+pub struct Descending<F> {
+  iter: F,
+  forwards: bool,
+}
+
+impl<F> Descending<F> {
+  /// Returns the underlying range.
+  #[inline]
+  pub const fn inner(&self) -> &F {
+    &self.iter
+  }
+
+  /// Consumes the iterator and returns the underlying range.
+  #[inline]
+  pub fn into_inner(self) -> F {
+    self.iter
+  }
+}
+
+impl<F: DoubleEndedIterator> Iterator for Descending<F> {
+  type Item = F::Item;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.forwards {
+      self.iter.next()
+    } else {
+      self.iter.next_back()
+    }
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<F: DoubleEndedIterator> DoubleEndedIterator for Descending<F> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.forwards {
+      self.iter.next_back()
+    } else {
+      self.iter.next()
+    }
+  }
+}
+
+impl<F: DoubleEndedIterator + FusedIterator> FusedIterator for Descending<F> {}
+
+/// A range with owned bounds, created by [`Builder::range_owned`].
+pub struct OwnedRange<T> {
+  start: Bound<T>,
+  end: Bound<T>,
+}
+
+impl<T> OwnedRange<T> {
+  /// Returns the bounds of the range.
+  #[inline]
+  pub fn bounds(&self) -> (Bound<&T>, Bound<&T>) {
+    (self.start.as_ref(), self.end.as_ref())
+  }
+
+  /// Consumes the range and returns the bounds.
+  #[inline]
+  pub fn into_bounds(self) -> (Bound<T>, Bound<T>) {
+    (self.start, self.end)
+  }
+}
+
+impl<Q: ?Sized, T: Borrow<Q>> RangeBounds<Q> for OwnedRange<T> {
+  #[inline]
+  fn start_bound(&self) -> Bound<&Q> {
+    self.start.as_ref().map(|start| start.borrow())
+  }
+
+  #[inline]
+  fn end_bound(&self) -> Bound<&Q> {
+    self.end.as_ref().map(|end| end.borrow())
+  }
+}
+
+/// Returns `true` if the iterator has already yielded as many entries as it is allowed to.
+#[inline]
+const fn exhausted(remaining: &Option<usize>) -> bool {
+  matches!(remaining, Some(0))
+}
+
+/// Consumes one unit of the limit if an entry is going to be yielded.
+#[inline]
+fn consume<E>(remaining: &mut Option<usize>, ent: Option<E>) -> Option<E> {
+  if ent.is_some() {
+    if let Some(remaining) = remaining {
+      *remaining -= 1;
+    }
+  }
+  ent
+}
+
+/// Returns the encoded key bytes of both entries, if `comparator` orders keys like the bytes.
+#[inline]
+fn key_bytes_pair<'a, C, E>(comparator: &C, a: &'a E, b: &'a E) -> Option<(&'a [u8], &'a [u8])>
+where
+  C: Comparator<E::Key>,
+  E: Entry,
+{
+  if !comparator.is_ascend() {
+    return None;
+  }
+
+  match (a.key_bytes(), b.key_bytes()) {
+    (Some(a), Some(b)) => Some((a, b)),
+    _ => None,
+  }
+}
+
+/// Compares the keys of two entries of the same cursor, preferring their encoded bytes.
+#[inline]
+fn compare_keys<C, E>(comparator: &C, a: &E, b: &E) -> core::cmp::Ordering
+where
+  C: Comparator<E::Key>,
+  E: Entry,
+{
+  match key_bytes_pair(comparator, a, b) {
+    Some((a, b)) => a.cmp(b),
+    None => comparator.compare(a.key(), b.key()),
+  }
+}
+
+/// Returns `true` if two entries of the same cursor have equivalent keys, preferring their
+/// encoded bytes.
+#[inline]
+fn same_key<C, E>(comparator: &C, a: &E, b: &E) -> bool
+where
+  C: Comparator<E::Key>,
+  E: Entry,
+{
+  match key_bytes_pair(comparator, a, b) {
+    Some((a, b)) => a == b,
+    None => comparator.equivalent(a.key(), b.key()),
+  }
+}
+
+/// Validates the key of an entry, so a validator can use [`Entry::key_bytes`].
+trait KeyValidator<E: Entry> {
+  fn validate_key(&self, ent: &E) -> bool;
+}
+
+/// Adapts a [`Validator`] of keys to a [`KeyValidator`].
+struct ByKey<'a, V>(&'a V);
+
+impl<E, V> KeyValidator<E> for ByKey<'_, V>
+where
+  E: Entry,
+  V: Validator<E::Key>,
+{
+  #[inline]
+  fn validate_key(&self, ent: &E) -> bool {
+    self.0.validate(ent.key())
+  }
+}
+
+/// Skips the entries starting at `curr` which are exact key and version duplicates of the
+/// already yielded `prev`, `step` moves to the next visible and valid entry.
+///
+/// MVCC inputs never contain such duplicates, but a buggy one may, and the copies are then
+/// yielded once instead of once per copy.
+#[inline]
+fn skip_duplicates<C, E>(
+  mut curr: Option<E>,
+  prev: Option<&E>,
+  comparator: &C,
+  mut step: impl FnMut(&E) -> Option<E>,
+) -> Option<E>
+where
+  C: Comparator<E::Key>,
+  E: Entry,
+{
+  let prev = match prev {
+    Some(prev) => prev,
+    None => return curr,
+  };
+
+  while let Some(ent) = curr.as_ref() {
+    if ent.version() != prev.version() || compare_keys(comparator, ent, prev).is_ne() {
+      break;
+    }
+    curr = step(ent);
+  }
+  curr
+}
+
+/// Traversal statistics of a [`dedup::Iter`], returned by [`dedup::Iter::stats`].
+///
+/// Useful for diagnosing slow scans, e.g. a key with thousands of tombstones shows up as
+/// many visited entries for few yielded ones.
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stats {
+  /// The number of entries the iterator looked at.
+  pub visited: usize,
+  /// The number of entries yielded.
+  pub yielded: usize,
+  /// The number of entries skipped because they are newer than the query version.
+  pub skipped_version: usize,
+  /// The number of visible entries which were not yielded, because they were rejected by a
+  /// validator or shadowed by a newer version of the same key.
+  pub skipped_invalid: usize,
+}
+
+/// The statistics collector of the iterators, a no-op unless the `stats` feature is enabled.
+#[cfg(feature = "stats")]
+type Tracker = Stats;
+#[cfg(not(feature = "stats"))]
+type Tracker = ();
+
+trait Recorder {
+  fn visited(&mut self);
+
+  fn yielded(&mut self);
+
+  fn skipped_version(&mut self);
+
+  fn skipped_invalid(&mut self);
+}
+
+impl Recorder for () {
+  #[inline(always)]
+  fn visited(&mut self) {}
+
+  #[inline(always)]
+  fn yielded(&mut self) {}
+
+  #[inline(always)]
+  fn skipped_version(&mut self) {}
+
+  #[inline(always)]
+  fn skipped_invalid(&mut self) {}
+}
+
+#[cfg(feature = "stats")]
+impl Recorder for Stats {
+  #[inline]
+  fn visited(&mut self) {
+    self.visited += 1;
+  }
+
+  #[inline]
+  fn yielded(&mut self) {
+    self.yielded += 1;
+  }
+
+  #[inline]
+  fn skipped_version(&mut self) {
+    self.skipped_version += 1;
+  }
+
+  #[inline]
+  fn skipped_invalid(&mut self) {
+    self.skipped_invalid += 1;
+  }
+}
+
+/// Returns `true` if the entry is newer than the query version, i.e. invisible to the reader.
+#[inline]
+fn is_newer<ENT, Q>(ent: &ENT, version: &Q) -> bool
+where
+  ENT: Entry,
+  Q: ?Sized + QueryVersion<ENT::Version>,
+{
+  version.precedes(&ent.version())
+}
+
+#[inline]
+fn next_dedup<ENT, E, K, V, Q>(
+  curr: Option<ENT>,
+  version: &Q,
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+) -> Option<ENT>
+where
+  ENT: Sized + Entry + Cursor,
+  E: Equivalentor<ENT::Key>,
+  K: KeyValidator<ENT>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+  Q: ?Sized + QueryVersion<ENT::Version>,
+{
+  next_dedup_recorded(
+    curr,
+    version,
+    equivalentor,
+    key_validator,
+    value_validator,
+    &mut (),
+  )
+}
+
+fn next_dedup_recorded<ENT, E, K, V, S, Q>(
+  mut curr: Option<ENT>,
+  version: &Q,
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+  stats: &mut S,
+) -> Option<ENT>
+where
+  ENT: Sized + Entry + Cursor,
+  E: Equivalentor<ENT::Key>,
+  K: KeyValidator<ENT>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+  S: Recorder,
+  Q: ?Sized + QueryVersion<ENT::Version>,
+{
+  while let Some(ent) = curr {
+    stats.visited();
+    // if the current version is larger than the query version, we should move next to find a smaller version.
+    if is_newer(&ent, version) {
+      stats.skipped_version();
+      curr = ent.next();
+      continue;
+    }
+
+    // if the value of the entry is not in a valid state, we should move to the next key to find a valid entry.
+    if !value_validator.validate(&ent.version(), ent.value()) {
+      stats.skipped_invalid();
+      curr = ent.skip_to_different_key(equivalentor);
+      continue;
+    }
+
+    // if the key of the entry is not valid, we should move next to find a valid entry.
+    if key_validator.validate_key(&ent) {
+      return Some(ent);
+    }
+
+    stats.skipped_invalid();
+    curr = ent.next();
+  }
+
+  None
+}
+
+#[inline]
+fn next_back_dedup<ENT, E, K, V, Q>(
+  curr: Option<ENT>,
+  version: &Q,
+  equivalentor: &E,
   key_validator: &K,
   value_validator: &V,
 ) -> Option<ENT>
 where
   ENT: Sized + Entry + DoubleEndedCursor,
   E: Equivalentor<ENT::Key>,
-  K: Validator<ENT::Key>,
-  V: Validator<ENT::Value>,
+  K: KeyValidator<ENT>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+  Q: ?Sized + QueryVersion<ENT::Version>,
+{
+  next_back_dedup_recorded(
+    curr,
+    version,
+    equivalentor,
+    key_validator,
+    value_validator,
+    &mut (),
+  )
+}
+
+fn next_back_dedup_recorded<ENT, E, K, V, S, Q>(
+  mut curr: Option<ENT>,
+  version: &Q,
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+  stats: &mut S,
+) -> Option<ENT>
+where
+  ENT: Sized + Entry + DoubleEndedCursor,
+  E: Equivalentor<ENT::Key>,
+  K: KeyValidator<ENT>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+  S: Recorder,
+  Q: ?Sized + QueryVersion<ENT::Version>,
 {
   while let Some(ent) = curr {
+    stats.visited();
     let curr_key = ent.key();
-    if ent.version().gt(version) {
+    if is_newer(&ent, version) {
+      stats.skipped_version();
       curr = ent.next_back();
       continue;
     }
 
+    // Moving backwards, the versions of a key are visited in ascending order, so `ent`
+    // is the selected entry only if `prev` is a different key or is not visible.
+    // A same-key `prev` whose version equals the query version is visible and newer,
+    // so it supersedes `ent`.
     let prev = ent.next_back();
 
     match prev {
       None => {
-        if value_validator.validate(ent.value()) {
+        if value_validator.validate(&ent.version(), ent.value()) {
           // the current node is valid, we should return it.
-          if key_validator.validate(curr_key) {
+          if key_validator.validate_key(&ent) {
             return Some(ent);
           }
         }
 
+        stats.skipped_invalid();
         return None;
       }
       Some(prev) => {
@@ -455,13 +1622,14 @@ where
         // if the prev's version is greater than the query version or the prev's key is different from the current key,
         // we should try to return the current node.
         let prev_key = prev.key();
-        if (prev.version().gt(version) || !equivalentor.equivalent(curr_key, prev_key))
-          && value_validator.validate(ent.value())
-          && key_validator.validate(curr_key)
+        if (is_newer(&prev, version) || !equivalentor.equivalent(curr_key, prev_key))
+          && value_validator.validate(&ent.version(), ent.value())
+          && key_validator.validate_key(&ent)
         {
           return Some(ent);
         }
 
+        stats.skipped_invalid();
         curr = Some(prev);
       }
     }
@@ -470,26 +1638,27 @@ where
   None
 }
 
-fn next_valid<ENT, K, V>(
+fn next_valid<ENT, K, V, Q>(
   mut curr: Option<ENT>,
-  version: &ENT::Version,
+  version: &Q,
   key_validator: &K,
   value_validator: &V,
 ) -> Option<ENT>
 where
   ENT: Sized + Entry + Cursor,
   K: Validator<ENT::Key>,
-  V: Validator<ENT::Value>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+  Q: ?Sized + QueryVersion<ENT::Version>,
 {
   while let Some(ent) = curr {
     let curr_key = ent.key();
-    if ent.version().gt(version) {
+    if is_newer(&ent, version) {
       curr = ent.next();
       continue;
     }
 
     // if the key of the entry is not valid, we should move next to find a valid entry.
-    if key_validator.validate(curr_key) && value_validator.validate(ent.value()) {
+    if key_validator.validate(curr_key) && value_validator.validate(&ent.version(), ent.value()) {
       return Some(ent);
     }
 
@@ -499,26 +1668,27 @@ where
   None
 }
 
-fn next_back_valid<ENT, K, V>(
+fn next_back_valid<ENT, K, V, Q>(
   mut curr: Option<ENT>,
-  version: &ENT::Version,
+  version: &Q,
   key_validator: &K,
   value_validator: &V,
 ) -> Option<ENT>
 where
   ENT: Sized + Entry + DoubleEndedCursor,
   K: Validator<ENT::Key>,
-  V: Validator<ENT::Value>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+  Q: ?Sized + QueryVersion<ENT::Version>,
 {
   while let Some(ent) = curr {
     let curr_key = ent.key();
-    if ent.version().gt(version) {
+    if is_newer(&ent, version) {
       curr = ent.next_back();
       continue;
     }
 
     // if the key of the entry is not valid, we should move next to find a valid entry.
-    if key_validator.validate(curr_key) && value_validator.validate(ent.value()) {
+    if key_validator.validate(curr_key) && value_validator.validate(&ent.version(), ent.value()) {
       return Some(ent);
     }
 
@@ -527,3 +1697,153 @@ where
 
   None
 }
+
+/// Returns the valid entry of the key group starting at `start`, whose version is the closest one
+/// after `after` in version order, i.e. the newest version older than `after` if `newest_first`,
+/// otherwise the oldest version newer than `after`.
+#[allow(clippy::too_many_arguments)]
+fn pick_version<ENT, E, K, V>(
+  start: &ENT,
+  after: Option<&ENT::Version>,
+  version: &ENT::Version,
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+  newest_first: bool,
+  step: impl Fn(&ENT) -> Option<ENT>,
+) -> Option<ENT>
+where
+  ENT: Entry + Clone,
+  E: Equivalentor<ENT::Key>,
+  K: Validator<ENT::Key>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+{
+  if !key_validator.validate(start.key()) {
+    return None;
+  }
+
+  let mut picked: Option<ENT> = None;
+  let mut curr = Some(start.clone());
+  while let Some(ent) = curr {
+    if !equivalentor.equivalent(ent.key(), start.key()) {
+      break;
+    }
+
+    let ent_version = ent.version();
+    let in_order = match after {
+      None => true,
+      Some(after) if newest_first => ent_version.lt(after),
+      Some(after) => ent_version.gt(after),
+    };
+
+    if in_order && ent_version.le(version) && value_validator.validate(&ent_version, ent.value()) {
+      let closer = match &picked {
+        None => true,
+        Some(picked) if newest_first => ent_version.gt(&picked.version()),
+        Some(picked) => ent_version.lt(&picked.version()),
+      };
+
+      if closer {
+        picked = Some(ent.clone());
+      }
+    }
+
+    curr = step(&ent);
+  }
+
+  picked
+}
+
+/// Moves forwards in `(key ascending, version descending)` order, `group` is the first entry of
+/// the key group which `curr` belongs to.
+fn next_sorted<ENT, E, K, V>(
+  group: &mut Option<ENT>,
+  curr: Option<&ENT>,
+  first: impl FnOnce() -> Option<ENT>,
+  version: &ENT::Version,
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+) -> Option<ENT>
+where
+  ENT: Cursor + Clone,
+  E: Equivalentor<ENT::Key>,
+  K: Validator<ENT::Key>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+{
+  let mut after = curr.map(|ent| ent.version());
+  if curr.is_none() {
+    *group = first();
+  }
+
+  while let Some(start) = group.as_ref() {
+    if let Some(ent) = pick_version(
+      start,
+      after.as_ref(),
+      version,
+      equivalentor,
+      key_validator,
+      value_validator,
+      true,
+      Cursor::next,
+    ) {
+      return Some(ent);
+    }
+
+    let next_group = start.skip_to_different_key(equivalentor);
+    *group = next_group;
+    after = None;
+  }
+
+  None
+}
+
+/// Moves backwards in `(key descending, version ascending)` order, `group` is the last entry of
+/// the key group which `curr` belongs to.
+fn next_back_sorted<ENT, E, K, V>(
+  group: &mut Option<ENT>,
+  curr: Option<&ENT>,
+  last: impl FnOnce() -> Option<ENT>,
+  version: &ENT::Version,
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+) -> Option<ENT>
+where
+  ENT: DoubleEndedCursor + Clone,
+  E: Equivalentor<ENT::Key>,
+  K: Validator<ENT::Key>,
+  V: VersionedValidator<ENT::Version, ENT::Value>,
+{
+  let mut after = curr.map(|ent| ent.version());
+  if curr.is_none() {
+    *group = last();
+  }
+
+  while let Some(start) = group.as_ref() {
+    if let Some(ent) = pick_version(
+      start,
+      after.as_ref(),
+      version,
+      equivalentor,
+      key_validator,
+      value_validator,
+      false,
+      DoubleEndedCursor::next_back,
+    ) {
+      return Some(ent);
+    }
+
+    let mut prev_group = start.next_back();
+    while let Some(ent) = prev_group.as_ref() {
+      if !equivalentor.equivalent(ent.key(), start.key()) {
+        break;
+      }
+      prev_group = ent.next_back();
+    }
+    *group = prev_group;
+    after = None;
+  }
+
+  None
+}
@@ -0,0 +1,222 @@
+use core::{cmp, iter::Peekable};
+
+use dbutils::equivalentor::{Ascend, Comparator};
+
+use crate::{Entry, NoopValidator, Validator};
+
+/// An iterator which merges two deduplicated sources into one, e.g. a memtable
+/// overlaid on an immutable table.
+///
+/// Both sources must yield keys in the order of the comparator, at most once per
+/// key (e.g. [`dedup::Iter`](crate::dedup::Iter) or [`dedup::Range`](crate::dedup::Range)).
+/// When both sources yield the same key, the entry with the higher version wins, and on
+/// equal versions the entry from the first source wins.
+///
+/// The value validator is applied once, to the winning entry. The sources should therefore
+/// keep tombstones (i.e. be built without a value validator), so that a tombstone in the newer
+/// source shadows a live value in the older one.
+pub struct MergeIter<A, B, C = Ascend, V = NoopValidator>
+where
+  A: Iterator,
+  B: Iterator,
+{
+  a: Peekable<A>,
+  b: Peekable<B>,
+  comparator: C,
+  value_validator: V,
+}
+
+impl<A, B> MergeIter<A, B>
+where
+  A: Iterator,
+  B: Iterator,
+{
+  /// Creates a new merge iterator over two sources, with the default comparator and
+  /// without filtering values.
+  #[inline]
+  pub fn new(a: A, b: B) -> Self {
+    Self {
+      a: a.peekable(),
+      b: b.peekable(),
+      comparator: Ascend,
+      value_validator: NoopValidator,
+    }
+  }
+}
+
+impl<A, B, C, V> MergeIter<A, B, C, V>
+where
+  A: Iterator,
+  B: Iterator,
+{
+  /// Sets the comparator used to order the keys of the two sources.
+  ///
+  /// It must be the same comparator the sources were built with.
+  #[inline]
+  pub fn with_comparator<NC>(self, comparator: NC) -> MergeIter<A, B, NC, V> {
+    MergeIter {
+      a: self.a,
+      b: self.b,
+      comparator,
+      value_validator: self.value_validator,
+    }
+  }
+
+  /// Sets the value validator applied to the merged stream.
+  #[inline]
+  pub fn with_value_validator<NV>(self, value_validator: NV) -> MergeIter<A, B, C, NV> {
+    MergeIter {
+      a: self.a,
+      b: self.b,
+      comparator: self.comparator,
+      value_validator,
+    }
+  }
+}
+
+impl<A, B, E, C, V> Iterator for MergeIter<A, B, C, V>
+where
+  A: Iterator<Item = E>,
+  B: Iterator<Item = E>,
+  E: Entry,
+  C: Comparator<E::Key>,
+  V: Validator<E::Value>,
+{
+  type Item = E;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let ent = match (self.a.peek(), self.b.peek()) {
+        (None, None) => return None,
+        (Some(_), None) => self.a.next(),
+        (None, Some(_)) => self.b.next(),
+        (Some(a), Some(b)) => match self.comparator.compare(a.key(), b.key()) {
+          cmp::Ordering::Less => self.a.next(),
+          cmp::Ordering::Greater => self.b.next(),
+          cmp::Ordering::Equal => {
+            let a = self.a.next();
+            let b = self.b.next();
+            match (a, b) {
+              (Some(a), Some(b)) if b.version() > a.version() => Some(b),
+              (a, _) => a,
+            }
+          }
+        },
+      }?;
+
+      if self.value_validator.validate(ent.value()) {
+        return Some(ent);
+      }
+    }
+  }
+}
+
+/// A wrapper which orders entries for merging with a `BinaryHeap`.
+///
+/// Entries are ordered by key using the comparator, and by version for the same key. The
+/// order is reversed, so the max-heap pops the entry with the smallest key first, and the
+/// newest version first for the same key, i.e. the same order as the sources yield.
+///
+/// The comparator is stored in every entry, so it should be cheap to clone, e.g. a
+/// zero-sized comparator like [`Ascend`].
+///
+/// ## Example
+///
+/// ```rust
+/// use std::collections::BinaryHeap;
+/// use snapshotor::{HeapEntry, SortedSlice, Rewindable, Cursor, Entry};
+///
+/// let a = SortedSlice::new(&[("a", 2, Some(1)), ("c", 1, Some(1))]);
+/// let b = SortedSlice::new(&[("a", 3, Some(2)), ("b", 1, Some(2))]);
+///
+/// let mut heap = BinaryHeap::new();
+/// heap.extend(a.first().map(HeapEntry::new));
+/// heap.extend(b.first().map(HeapEntry::new));
+///
+/// let mut merged = Vec::new();
+/// while let Some(ent) = heap.pop() {
+///   let ent = ent.into_entry();
+///   merged.push((*ent.key(), ent.version()));
+///   heap.extend(ent.next().map(HeapEntry::new));
+/// }
+/// assert_eq!(merged, [("a", 3), ("a", 2), ("b", 1), ("c", 1)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HeapEntry<E, C = Ascend> {
+  entry: E,
+  comparator: C,
+}
+
+impl<E> HeapEntry<E> {
+  /// Wraps an entry with the default comparator.
+  #[inline]
+  pub const fn new(entry: E) -> Self {
+    Self {
+      entry,
+      comparator: Ascend,
+    }
+  }
+}
+
+impl<E, C> HeapEntry<E, C> {
+  /// Wraps an entry with the given comparator.
+  #[inline]
+  pub const fn with_comparator(entry: E, comparator: C) -> Self {
+    Self { entry, comparator }
+  }
+
+  /// Returns a reference to the entry.
+  #[inline]
+  pub const fn entry(&self) -> &E {
+    &self.entry
+  }
+
+  /// Consumes the wrapper and returns the entry.
+  #[inline]
+  pub fn into_entry(self) -> E {
+    self.entry
+  }
+}
+
+impl<E, C> PartialEq for HeapEntry<E, C>
+where
+  E: Entry,
+  C: Comparator<E::Key>,
+{
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other).is_eq()
+  }
+}
+
+impl<E, C> Eq for HeapEntry<E, C>
+where
+  E: Entry,
+  C: Comparator<E::Key>,
+{
+}
+
+impl<E, C> PartialOrd for HeapEntry<E, C>
+where
+  E: Entry,
+  C: Comparator<E::Key>,
+{
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<E, C> Ord for HeapEntry<E, C>
+where
+  E: Entry,
+  C: Comparator<E::Key>,
+{
+  #[inline]
+  fn cmp(&self, other: &Self) -> cmp::Ordering {
+    self
+      .comparator
+      .compare(other.entry.key(), self.entry.key())
+      .then_with(|| self.entry.version().cmp(&other.entry.version()))
+  }
+}
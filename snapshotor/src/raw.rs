@@ -0,0 +1,3 @@
+pub use iter::Iter;
+
+mod iter;
@@ -0,0 +1,196 @@
+use dbutils::equivalentor::{Ascend, Comparator};
+
+use crate::{
+  compare_keys, consume, exhausted, sealed::SealedIter, Builder, Cursor, DoubleEndedCursor, Entry,
+  NoopValidator, Rewindable,
+};
+
+/// An iterator wrapper on any iterator yielding [`Entry`].
+///
+/// The iterator only skips the entries whose version is greater than the query version, every
+/// other entry is yielded in the order of the underlying structure, including tombstones and
+/// all of the versions of the same key. No validator is called, so this is the cheapest way
+/// to dump the version history visible to a reader.
+pub struct Iter<E, R, C = Ascend>
+where
+  E: Entry,
+{
+  comparator: C,
+  rewinder: R,
+  tail: Option<E>,
+  head: Option<E>,
+  query_version: E::Version,
+  remaining: Option<usize>,
+  head_done: bool,
+  tail_done: bool,
+}
+
+impl<E, R, C> SealedIter<E> for Iter<E, R, C>
+where
+  E: Entry,
+{
+  type Initializor = R;
+
+  type KeyValidator = NoopValidator;
+
+  type ValueValidator = NoopValidator;
+
+  type Comparator = C;
+
+  fn new(
+    version: E::Version,
+    builder: Builder<Self::Initializor, Self::Comparator, Self::KeyValidator, Self::ValueValidator>,
+  ) -> Self
+  where
+    E: Entry,
+  {
+    Self {
+      rewinder: builder.initializor,
+      comparator: builder.comparator,
+      head: None,
+      tail: None,
+      query_version: version,
+      remaining: builder.limit,
+      head_done: false,
+      tail_done: false,
+    }
+  }
+}
+
+impl<E, R, C> Iter<E, R, C>
+where
+  E: Entry,
+{
+  /// Returns the query version of the iterator.
+  #[inline]
+  pub const fn query_version(&self) -> &E::Version {
+    &self.query_version
+  }
+
+  /// Returns the current head of the iterator.
+  #[inline]
+  pub const fn head(&self) -> Option<&E> {
+    self.head.as_ref()
+  }
+
+  /// Returns the current tail of the iterator.
+  #[inline]
+  pub const fn tail(&self) -> Option<&E> {
+    self.tail.as_ref()
+  }
+
+  /// Returns `true` if the iterator has been driven from either end.
+  ///
+  /// The underlying iterator is not touched until the first call to `next` or `next_back`,
+  /// so an iterator which has not started can still be inspected without executing the scan.
+  #[inline]
+  pub const fn is_started(&self) -> bool {
+    self.head.is_some() || self.tail.is_some() || self.head_done || self.tail_done
+  }
+}
+
+impl<E, R, C> Iterator for Iter<E, R, C>
+where
+  C: Comparator<E::Key>,
+  R: Rewindable<Entry = E>,
+  E: Cursor + Clone,
+{
+  type Item = E;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.head_done || exhausted(&self.remaining) {
+      return None;
+    }
+
+    let next_head = match self.head.as_ref() {
+      Some(head) => head.next(),
+      None => self.rewinder.first(),
+    };
+    let next_head = next_visible(next_head, &self.query_version);
+
+    match (next_head, &self.tail) {
+      (Some(next), Some(t))
+        if compare_keys(&self.comparator, &next, t)
+          .then_with(|| t.version().cmp(&next.version()))
+          .is_ge() =>
+      {
+        self.head = Some(next);
+        self.head_done = true;
+        None
+      }
+      (Some(next), _) => {
+        self.head = Some(next);
+        consume(&mut self.remaining, self.head.clone())
+      }
+      (None, _) => {
+        self.head = None;
+        self.head_done = true;
+        None
+      }
+    }
+  }
+}
+
+impl<E, R, C> DoubleEndedIterator for Iter<E, R, C>
+where
+  C: Comparator<E::Key>,
+  R: Rewindable<Entry = E>,
+  E: DoubleEndedCursor + Clone,
+{
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.tail_done || exhausted(&self.remaining) {
+      return None;
+    }
+
+    let next_tail = match self.tail.as_ref() {
+      Some(tail) => tail.next_back(),
+      None => self.rewinder.last(),
+    };
+    let next_tail = next_back_visible(next_tail, &self.query_version);
+
+    match (&self.head, next_tail) {
+      (Some(h), Some(next))
+        if compare_keys(&self.comparator, h, &next)
+          .then_with(|| next.version().cmp(&h.version()))
+          .is_ge() =>
+      {
+        self.tail = Some(next);
+        self.tail_done = true;
+        None
+      }
+      (_, Some(next)) => {
+        self.tail = Some(next);
+        consume(&mut self.remaining, self.tail.clone())
+      }
+      (_, None) => {
+        self.tail = None;
+        self.tail_done = true;
+        None
+      }
+    }
+  }
+}
+
+/// Returns the first entry, starting at `curr` and moving forwards, visible at `version`.
+#[inline]
+fn next_visible<E: Cursor>(mut curr: Option<E>, version: &E::Version) -> Option<E> {
+  while let Some(ent) = curr {
+    if ent.version().le(version) {
+      return Some(ent);
+    }
+    curr = ent.next();
+  }
+  None
+}
+
+/// Works like [`next_visible`], but moves backwards.
+#[inline]
+fn next_back_visible<E: DoubleEndedCursor>(mut curr: Option<E>, version: &E::Version) -> Option<E> {
+  while let Some(ent) = curr {
+    if ent.version().le(version) {
+      return Some(ent);
+    }
+    curr = ent.next_back();
+  }
+  None
+}
@@ -0,0 +1,253 @@
+use core::ops::Bound;
+
+use dbutils::{equivalent::Comparable, equivalentor::Equivalentor};
+
+use crate::{Cursor, DoubleEndedCursor, Entry, Rewindable, Seekable, Versioned};
+
+/// A sorted slice of `(key, version, value)` entries, which can be used as the
+/// initializor of a [`Builder`](crate::Builder).
+///
+/// The entries must be sorted by key ascending and then by version descending, a `None`
+/// value is a tombstone. Moving a cursor is an index increment or decrement, and seeking
+/// is a binary search, which makes the slice a cache-friendly backend for small immutable
+/// snapshots.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapshotor::{dedup, equivalentor::Ascend, Builder, Entry, NoopValidator, SortedSlice};
+///
+/// let data = [("a", 2, Some(2)), ("a", 1, Some(1)), ("b", 1, Some(1))];
+/// let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+///   Builder::new(SortedSlice::new(&data)).iter(2);
+/// assert_eq!(
+///   it.map(|ent| (*ent.key(), ent.version())).collect::<Vec<_>>(),
+///   [("a", 2), ("b", 1)]
+/// );
+/// ```
+pub type SortedSlice<'a, K, V> = EntrySlice<'a, (K, u64, Option<V>)>;
+
+/// An entry of a [`SortedSlice`].
+pub type SliceEntry<'a, K, V> = SliceCursor<'a, (K, u64, Option<V>)>;
+
+/// A sorted slice of any [`Entry`] type, e.g. `(key, version, value)` tuples, which can be
+/// used as the initializor of a [`Builder`](crate::Builder).
+///
+/// The entries must be sorted by key ascending and then by version descending. A
+/// [`SortedSlice`] is an `EntrySlice` of `(key, version, Option<value>)` tuples, where `None`
+/// is a tombstone.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapshotor::{dedup, equivalentor::Ascend, Builder, Entry, EntrySlice, NoopValidator};
+///
+/// let data = [("a", 2u64, 'x'), ("a", 1, 'y'), ("b", 1, 'z')];
+/// let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+///   Builder::new(EntrySlice::new(&data)).iter(2);
+/// assert_eq!(
+///   it.map(|ent| (*ent.key(), ent.version())).collect::<Vec<_>>(),
+///   [("a", 2), ("b", 1)]
+/// );
+/// ```
+pub struct EntrySlice<'a, T> {
+  data: &'a [T],
+}
+
+impl<T> Clone for EntrySlice<'_, T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for EntrySlice<'_, T> {}
+
+impl<'a, T> EntrySlice<'a, T> {
+  /// Creates a new entry slice.
+  ///
+  /// The entries must be sorted by key ascending and then by version descending.
+  #[inline]
+  pub const fn new(data: &'a [T]) -> Self {
+    Self { data }
+  }
+
+  /// Returns the underlying entries.
+  #[inline]
+  pub const fn as_slice(&self) -> &'a [T] {
+    self.data
+  }
+
+  #[inline]
+  fn cursor(&self, idx: usize) -> Option<SliceCursor<'a, T>> {
+    (idx < self.data.len()).then_some(SliceCursor {
+      data: self.data,
+      idx,
+    })
+  }
+}
+
+impl<'a, T: Entry> Rewindable for EntrySlice<'a, T> {
+  type Entry = SliceCursor<'a, T>;
+
+  #[inline]
+  fn first(&self) -> Option<Self::Entry> {
+    self.cursor(0)
+  }
+
+  #[inline]
+  fn last(&self) -> Option<Self::Entry> {
+    self
+      .data
+      .len()
+      .checked_sub(1)
+      .and_then(|idx| self.cursor(idx))
+  }
+}
+
+impl<T: Entry> Versioned for EntrySlice<'_, T> {
+  type Version = T::Version;
+
+  /// Scans the whole slice, as the versions are only sorted within a key.
+  #[inline]
+  fn max_version(&self) -> Option<Self::Version> {
+    self.data.iter().map(Entry::version).max()
+  }
+}
+
+impl<'a, T, Q> Seekable<Q> for EntrySlice<'a, T>
+where
+  T: Entry,
+  T::Key: Comparable<Q>,
+  Q: ?Sized,
+{
+  type Entry = SliceCursor<'a, T>;
+
+  /// Returns the newest version of the first key in the bound.
+  #[inline]
+  fn lower_bound(&self, bound: Bound<&Q>) -> Option<Self::Entry> {
+    let idx = match bound {
+      Bound::Included(q) => self
+        .data
+        .partition_point(|ent| ent.key().compare(q).is_lt()),
+      Bound::Excluded(q) => self
+        .data
+        .partition_point(|ent| ent.key().compare(q).is_le()),
+      Bound::Unbounded => 0,
+    };
+    self.cursor(idx)
+  }
+
+  /// Returns the oldest version of the last key in the bound.
+  #[inline]
+  fn upper_bound(&self, bound: Bound<&Q>) -> Option<Self::Entry> {
+    let idx = match bound {
+      Bound::Included(q) => self
+        .data
+        .partition_point(|ent| ent.key().compare(q).is_le()),
+      Bound::Excluded(q) => self
+        .data
+        .partition_point(|ent| ent.key().compare(q).is_lt()),
+      Bound::Unbounded => self.data.len(),
+    };
+    idx.checked_sub(1).and_then(|idx| self.cursor(idx))
+  }
+}
+
+/// A cursor over an [`EntrySlice`], which forwards the [`Entry`] methods to the entry it
+/// points at.
+pub struct SliceCursor<'a, T> {
+  data: &'a [T],
+  idx: usize,
+}
+
+impl<T> Clone for SliceCursor<'_, T> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for SliceCursor<'_, T> {}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for SliceCursor<'_, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_tuple("SliceCursor")
+      .field(&self.data[self.idx])
+      .finish()
+  }
+}
+
+impl<'a, T> SliceCursor<'a, T> {
+  /// Returns the index of the entry in the slice.
+  #[inline]
+  pub const fn index(&self) -> usize {
+    self.idx
+  }
+
+  /// Returns the entry the cursor points at.
+  #[inline]
+  pub fn get(&self) -> &'a T {
+    &self.data[self.idx]
+  }
+
+  #[inline]
+  fn at(&self, idx: usize) -> Option<Self> {
+    (idx < self.data.len()).then_some(Self {
+      data: self.data,
+      idx,
+    })
+  }
+}
+
+impl<T: Entry> Entry for SliceCursor<'_, T> {
+  type Key = T::Key;
+  type Value = T::Value;
+  type Version = T::Version;
+
+  #[inline]
+  fn key(&self) -> &Self::Key {
+    self.data[self.idx].key()
+  }
+
+  #[inline]
+  fn value(&self) -> &Self::Value {
+    self.data[self.idx].value()
+  }
+
+  #[inline]
+  fn version(&self) -> Self::Version {
+    self.data[self.idx].version()
+  }
+
+  #[inline]
+  fn key_bytes(&self) -> Option<&[u8]> {
+    self.data[self.idx].key_bytes()
+  }
+}
+
+impl<T: Entry> Cursor for SliceCursor<'_, T> {
+  #[inline]
+  fn next(&self) -> Option<Self> {
+    self.at(self.idx + 1)
+  }
+
+  #[inline]
+  fn skip_to_different_key<E>(&self, equivalentor: &E) -> Option<Self>
+  where
+    E: Equivalentor<Self::Key>,
+  {
+    // all versions of a key are adjacent, so binary search the end of the current key.
+    let key = self.key();
+    let rest = &self.data[self.idx + 1..];
+    let skipped = rest.partition_point(|ent| equivalentor.equivalent(ent.key(), key));
+    self.at(self.idx + 1 + skipped)
+  }
+}
+
+impl<T: Entry> DoubleEndedCursor for SliceCursor<'_, T> {
+  #[inline]
+  fn next_back(&self) -> Option<Self> {
+    self.idx.checked_sub(1).and_then(|idx| self.at(idx))
+  }
+}
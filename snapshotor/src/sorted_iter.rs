@@ -0,0 +1,115 @@
+use core::iter::{FusedIterator, Peekable};
+
+use dbutils::equivalentor::Equivalentor;
+
+use crate::{Entry, Validator};
+
+/// Returns an iterator which deduplicates and version-filters a sorted iterator in one pass.
+///
+/// `iter` must yield entries sorted by key and then by version descending, i.e. the order a
+/// [`Cursor`](crate::Cursor) walks forwards. For each key, the newest version visible at
+/// `version` is yielded if it passes both validators. If it fails the value validator (e.g. a
+/// tombstone), the key is skipped entirely, like [`dedup::Iter`](crate::dedup::Iter) does.
+///
+/// This is useful for stream processing, where implementing [`Cursor`](crate::Cursor) and
+/// [`Rewindable`](crate::Rewindable) for the source is not worth it.
+///
+/// ## Example
+///
+/// ```rust
+/// use snapshotor::{
+///   dedup_sorted_iter, equivalentor::Ascend, Cursor, Entry, NoopValidator, Rewindable, SortedSlice,
+/// };
+///
+/// let data = [("a", 2, Some(2)), ("a", 1, Some(1)), ("b", 3, Some(3)), ("b", 1, Some(1))];
+/// let slice = SortedSlice::new(&data);
+/// let entries = core::iter::successors(slice.first(), |ent| ent.next());
+/// let it = dedup_sorted_iter(entries, 2, Ascend, NoopValidator, NoopValidator);
+/// assert_eq!(
+///   it.map(|ent| (*ent.key(), ent.version())).collect::<Vec<_>>(),
+///   [("a", 2), ("b", 1)]
+/// );
+/// ```
+#[inline]
+pub fn dedup_sorted_iter<I, E, K, V>(
+  iter: I,
+  version: <I::Item as Entry>::Version,
+  equivalentor: E,
+  key_validator: K,
+  value_validator: V,
+) -> DedupSortedIter<I, E, K, V>
+where
+  I: Iterator,
+  I::Item: Entry,
+  E: Equivalentor<<I::Item as Entry>::Key>,
+  K: Validator<<I::Item as Entry>::Key>,
+  V: Validator<<I::Item as Entry>::Value>,
+{
+  DedupSortedIter {
+    iter: iter.fuse().peekable(),
+    version,
+    equivalentor,
+    key_validator,
+    value_validator,
+  }
+}
+
+/// An iterator which deduplicates and version-filters a sorted iterator, created by
+/// [`dedup_sorted_iter`].
+pub struct DedupSortedIter<I, E, K, V>
+where
+  I: Iterator,
+  I::Item: Entry,
+{
+  iter: Peekable<core::iter::Fuse<I>>,
+  version: <I::Item as Entry>::Version,
+  equivalentor: E,
+  key_validator: K,
+  value_validator: V,
+}
+
+impl<I, E, K, V> Iterator for DedupSortedIter<I, E, K, V>
+where
+  I: Iterator,
+  I::Item: Entry,
+  E: Equivalentor<<I::Item as Entry>::Key>,
+  K: Validator<<I::Item as Entry>::Key>,
+  V: Validator<<I::Item as Entry>::Value>,
+{
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let ent = self.iter.next()?;
+      if ent.version() > self.version {
+        continue;
+      }
+
+      // `ent` is the newest visible version of its key, the older versions are never yielded.
+      while self
+        .iter
+        .next_if(|next| self.equivalentor.equivalent(next.key(), ent.key()))
+        .is_some()
+      {}
+
+      if self.value_validator.validate(ent.value()) && self.key_validator.validate(ent.key()) {
+        return Some(ent);
+      }
+    }
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, self.iter.size_hint().1)
+  }
+}
+
+impl<I, E, K, V> FusedIterator for DedupSortedIter<I, E, K, V>
+where
+  I: Iterator,
+  I::Item: Entry,
+  E: Equivalentor<<I::Item as Entry>::Key>,
+  K: Validator<<I::Item as Entry>::Key>,
+  V: Validator<<I::Item as Entry>::Value>,
+{
+}
@@ -0,0 +1,187 @@
+use crate::{is_newer, Entry, Equivalentor, QueryVersion, Validator};
+
+/// A trait for cursor entries whose navigation can fail.
+///
+/// This is the fallible counterpart of [`Cursor`](crate::Cursor), e.g. for disk-backed cursors
+/// which may fail to read or decode the next entry. Errors are propagated by
+/// [`try_valid`] and [`try_dedup`] instead of being treated as the end of the cursor.
+pub trait TryCursor: Entry {
+  /// The error returned when the cursor fails to move.
+  type Error;
+
+  /// Returns the next entry of the entry.
+  fn try_next(&self) -> Result<Option<Self>, Self::Error>
+  where
+    Self: Sized;
+
+  /// Returns the first entry after this entry whose key is different from the key of this entry.
+  ///
+  /// See [`Cursor::skip_to_different_key`](crate::Cursor::skip_to_different_key).
+  fn try_skip_to_different_key<E>(&self, equivalentor: &E) -> Result<Option<Self>, Self::Error>
+  where
+    Self: Sized,
+    E: Equivalentor<Self::Key>,
+  {
+    let key = self.key();
+    let mut next = self.try_next()?;
+    while let Some(ent) = next {
+      if !equivalentor.equivalent(ent.key(), key) {
+        return Ok(Some(ent));
+      }
+
+      next = ent.try_next()?;
+    }
+
+    Ok(None)
+  }
+}
+
+/// A trait for cursor entries which can fallibly move to the next or previous entry.
+///
+/// This is the fallible counterpart of [`DoubleEndedCursor`](crate::DoubleEndedCursor).
+pub trait TryDoubleEndedCursor: TryCursor {
+  /// Returns the previous entry of the entry.
+  fn try_next_back(&self) -> Result<Option<Self>, Self::Error>
+  where
+    Self: Sized;
+}
+
+/// Returns the first entry, starting at `curr` and moving forwards, whose version is less than
+/// or equal to `version` and which passes both validators.
+///
+/// `curr` itself is checked first, pass `ent.try_next()?` to start after `ent`. Any error
+/// returned while moving the cursor is returned as is.
+pub fn try_valid<ENT, K, V>(
+  mut curr: Option<ENT>,
+  version: &(impl ?Sized + QueryVersion<ENT::Version>),
+  key_validator: &K,
+  value_validator: &V,
+) -> Result<Option<ENT>, ENT::Error>
+where
+  ENT: TryCursor,
+  K: Validator<ENT::Key>,
+  V: Validator<ENT::Value>,
+{
+  while let Some(ent) = curr {
+    if is_newer(&ent, version) {
+      curr = ent.try_next()?;
+      continue;
+    }
+
+    if key_validator.validate(ent.key()) && value_validator.validate(ent.value()) {
+      return Ok(Some(ent));
+    }
+
+    curr = ent.try_next()?;
+  }
+
+  Ok(None)
+}
+
+/// Works like [`try_valid`], but moves backwards.
+pub fn try_valid_back<ENT, K, V>(
+  mut curr: Option<ENT>,
+  version: &(impl ?Sized + QueryVersion<ENT::Version>),
+  key_validator: &K,
+  value_validator: &V,
+) -> Result<Option<ENT>, ENT::Error>
+where
+  ENT: TryDoubleEndedCursor,
+  K: Validator<ENT::Key>,
+  V: Validator<ENT::Value>,
+{
+  while let Some(ent) = curr {
+    if is_newer(&ent, version) {
+      curr = ent.try_next_back()?;
+      continue;
+    }
+
+    if key_validator.validate(ent.key()) && value_validator.validate(ent.value()) {
+      return Ok(Some(ent));
+    }
+
+    curr = ent.try_next_back()?;
+  }
+
+  Ok(None)
+}
+
+/// Returns the first entry, starting at `curr` and moving forwards, which is the newest version
+/// of its key visible at `version` and passes both validators.
+///
+/// `curr` must be the first entry of a key, or an entry whose newer versions are not visible.
+/// If the selected version of a key fails the value validator (e.g. a tombstone), the key is
+/// skipped entirely. Any error returned while moving the cursor is returned as is.
+pub fn try_dedup<ENT, E, K, V>(
+  mut curr: Option<ENT>,
+  version: &(impl ?Sized + QueryVersion<ENT::Version>),
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+) -> Result<Option<ENT>, ENT::Error>
+where
+  ENT: TryCursor,
+  E: Equivalentor<ENT::Key>,
+  K: Validator<ENT::Key>,
+  V: Validator<ENT::Value>,
+{
+  while let Some(ent) = curr {
+    if is_newer(&ent, version) {
+      curr = ent.try_next()?;
+      continue;
+    }
+
+    if !value_validator.validate(ent.value()) {
+      curr = ent.try_skip_to_different_key(equivalentor)?;
+      continue;
+    }
+
+    if key_validator.validate(ent.key()) {
+      return Ok(Some(ent));
+    }
+
+    curr = ent.try_next()?;
+  }
+
+  Ok(None)
+}
+
+/// Works like [`try_dedup`], but moves backwards.
+///
+/// `curr` must be the oldest entry of a key, or an entry whose older versions have been yielded.
+pub fn try_dedup_back<ENT, E, K, V>(
+  mut curr: Option<ENT>,
+  version: &(impl ?Sized + QueryVersion<ENT::Version>),
+  equivalentor: &E,
+  key_validator: &K,
+  value_validator: &V,
+) -> Result<Option<ENT>, ENT::Error>
+where
+  ENT: TryDoubleEndedCursor,
+  E: Equivalentor<ENT::Key>,
+  K: Validator<ENT::Key>,
+  V: Validator<ENT::Value>,
+{
+  while let Some(ent) = curr {
+    if is_newer(&ent, version) {
+      curr = ent.try_next_back()?;
+      continue;
+    }
+
+    // Moving backwards, the versions of a key are visited in ascending order, so `ent`
+    // is the selected entry only if `prev` is a different key or is not visible.
+    let prev = ent.try_next_back()?;
+    let selected = match &prev {
+      None => true,
+      Some(prev) => is_newer(prev, version) || !equivalentor.equivalent(ent.key(), prev.key()),
+    };
+
+    if selected && value_validator.validate(ent.value()) && key_validator.validate(ent.key()) {
+      return Ok(Some(ent));
+    }
+
+    curr = prev;
+  }
+
+  Ok(None)
+}
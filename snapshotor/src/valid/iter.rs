@@ -1,13 +1,19 @@
+use core::iter::FusedIterator;
+
 use dbutils::equivalentor::{Ascend, Comparator};
 
 use crate::{
-  next_back_valid, next_valid, sealed::SealedIter, Builder, Cursor, DoubleEndedCursor, Entry,
-  NoopValidator, Rewindable, Validator,
+  compare_keys, consume, exhausted, next_back_sorted, next_back_valid, next_sorted, next_valid,
+  sealed::SealedIter, skip_duplicates, Builder, Cursor, DoubleEndedCursor, Entry, NoopValidator,
+  Rewindable, Validator, VersionedValidator,
 };
 
 /// An iterator wrapper on any iterator yielding [`Entry`].
 ///
 /// By using the iterator wrapper, the iterator will yield [`Entry`]s with the same key only once (the entry with maximum version will be yield for the same key).
+///
+/// Entries with exactly the same key and version should not exist, if they do, only one of
+/// them is yielded, from either end.
 pub struct Iter<E, R, C = Ascend, K = NoopValidator, V = NoopValidator>
 where
   E: Entry,
@@ -19,6 +25,12 @@ where
   tail: Option<E>,
   head: Option<E>,
   query_version: E::Version,
+  remaining: Option<usize>,
+  head_done: bool,
+  tail_done: bool,
+  sort_versions: bool,
+  head_group: Option<E>,
+  tail_group: Option<E>,
 }
 
 impl<E, R, C, K, V> SealedIter<E> for Iter<E, R, C, K, V>
@@ -48,6 +60,12 @@ where
       head: None,
       tail: None,
       query_version: version,
+      remaining: builder.limit,
+      head_done: false,
+      tail_done: false,
+      sort_versions: builder.sort_versions,
+      head_group: None,
+      tail_group: None,
     }
   }
 }
@@ -73,92 +91,159 @@ where
   pub const fn tail(&self) -> Option<&E> {
     self.tail.as_ref()
   }
+
+  /// Returns `true` if the iterator has been driven from either end.
+  ///
+  /// The underlying iterator is not touched until the first call to `next` or `next_back`,
+  /// so an iterator which has not started can still be inspected without executing the scan.
+  #[inline]
+  pub const fn is_started(&self) -> bool {
+    self.head.is_some() || self.tail.is_some() || self.head_done || self.tail_done
+  }
 }
 
 impl<E, R, C, K, V> Iterator for Iter<E, R, C, K, V>
 where
   C: Comparator<E::Key>,
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   R: Rewindable<Entry = E>,
   E: Cursor + Clone,
 {
   type Item = E;
 
   fn next(&mut self) -> Option<Self::Item> {
-    let mut next_head = match self.head.as_ref() {
-      Some(head) => head.next(),
-      None => self.rewinder.first(),
-    };
+    if self.head_done || exhausted(&self.remaining) {
+      return None;
+    }
 
-    next_head = next_valid(
-      next_head,
-      &self.query_version,
-      &self.key_validator,
-      &self.value_validator,
-    );
+    let next_head = if self.sort_versions {
+      next_sorted(
+        &mut self.head_group,
+        self.head.as_ref(),
+        || self.rewinder.first(),
+        &self.query_version,
+        &self.comparator,
+        &self.key_validator,
+        &self.value_validator,
+      )
+    } else {
+      let next_head = match self.head.as_ref() {
+        Some(head) => head.next(),
+        None => self.rewinder.first(),
+      };
+
+      let next_head = next_valid(
+        next_head,
+        &self.query_version,
+        &self.key_validator,
+        &self.value_validator,
+      );
+      skip_duplicates(next_head, self.head.as_ref(), &self.comparator, |ent| {
+        next_valid(
+          ent.next(),
+          &self.query_version,
+          &self.key_validator,
+          &self.value_validator,
+        )
+      })
+    };
 
     match (next_head, &self.tail) {
       (Some(next), Some(t))
-        if self
-          .comparator
-          .compare(next.key(), t.key())
+        if compare_keys(&self.comparator, &next, t)
           .then_with(|| t.version().cmp(&next.version()))
           .is_ge() =>
       {
         self.head = Some(next);
+        self.head_done = true;
         None
       }
       (Some(next), _) => {
         self.head = Some(next);
-        self.head.clone()
+        consume(&mut self.remaining, self.head.clone())
       }
       (None, _) => {
         self.head = None;
+        self.head_done = true;
         None
       }
     }
   }
 }
 
+impl<E, R, C, K, V> FusedIterator for Iter<E, R, C, K, V>
+where
+  C: Comparator<E::Key>,
+  K: Validator<E::Key>,
+  V: VersionedValidator<E::Version, E::Value>,
+  R: Rewindable<Entry = E>,
+  E: Cursor + Clone,
+{
+}
+
 impl<E, R, C, K, V> DoubleEndedIterator for Iter<E, R, C, K, V>
 where
   C: Comparator<E::Key>,
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   R: Rewindable<Entry = E>,
   E: DoubleEndedCursor + Clone,
 {
   fn next_back(&mut self) -> Option<Self::Item> {
-    let mut next_tail = match self.tail.as_ref() {
-      Some(tail) => tail.next_back(),
-      None => self.rewinder.last(),
-    };
+    if self.tail_done || exhausted(&self.remaining) {
+      return None;
+    }
 
-    next_tail = next_back_valid(
-      next_tail,
-      &self.query_version,
-      &self.key_validator,
-      &self.value_validator,
-    );
+    let next_tail = if self.sort_versions {
+      next_back_sorted(
+        &mut self.tail_group,
+        self.tail.as_ref(),
+        || self.rewinder.last(),
+        &self.query_version,
+        &self.comparator,
+        &self.key_validator,
+        &self.value_validator,
+      )
+    } else {
+      let next_tail = match self.tail.as_ref() {
+        Some(tail) => tail.next_back(),
+        None => self.rewinder.last(),
+      };
+
+      let next_tail = next_back_valid(
+        next_tail,
+        &self.query_version,
+        &self.key_validator,
+        &self.value_validator,
+      );
+      skip_duplicates(next_tail, self.tail.as_ref(), &self.comparator, |ent| {
+        next_back_valid(
+          ent.next_back(),
+          &self.query_version,
+          &self.key_validator,
+          &self.value_validator,
+        )
+      })
+    };
 
     match (&self.head, next_tail) {
       (Some(h), Some(next))
-        if self
-          .comparator
-          .compare(h.key(), next.key())
-          .then_with(|| h.version().cmp(&next.version()))
+        if compare_keys(&self.comparator, h, &next)
+          .then_with(|| next.version().cmp(&h.version()))
           .is_ge() =>
       {
         self.tail = Some(next);
+        self.tail_done = true;
         None
       }
       (_, Some(next)) => {
         self.tail = Some(next);
-        self.tail.clone()
+        consume(&mut self.remaining, self.tail.clone())
       }
       (_, None) => {
         self.tail = None;
+        self.tail_done = true;
         None
       }
     }
@@ -3,11 +3,12 @@ use core::{
   ops::{Bound, RangeBounds},
 };
 
-use dbutils::equivalentor::{Comparator, QueryComparator};
+use dbutils::equivalentor::QueryComparator;
 
 use crate::{
-  next_back_valid, next_valid, sealed::SealedRange, Builder, Cursor, DoubleEndedCursor, Entry,
-  Seekable, Validator,
+  compare_keys, consume, exhausted, next_back_valid, next_valid, sealed::SealedRange,
+  skip_duplicates, Builder, Cursor, DoubleEndedCursor, Entry, Seekable, Validator,
+  VersionedValidator,
 };
 
 /// An iterator wrapper on any iterator yielding [`Entry`].
@@ -27,6 +28,9 @@ where
   tail: Option<E>,
   head: Option<E>,
   query_version: E::Version,
+  remaining: Option<usize>,
+  head_done: bool,
+  tail_done: bool,
   range: R,
   _q: PhantomData<Q>,
 }
@@ -64,6 +68,9 @@ where
       head: None,
       tail: None,
       query_version: version,
+      remaining: builder.limit,
+      head_done: false,
+      tail_done: false,
       range,
       _q: PhantomData,
     }
@@ -95,17 +102,44 @@ where
     self.tail.as_ref()
   }
 
+  /// Returns `true` if the iterator has been driven from either end.
+  ///
+  /// The underlying iterator is not touched until the first call to `next` or `next_back`,
+  /// so an iterator which has not started can still be inspected without executing the scan.
+  #[inline]
+  pub const fn is_started(&self) -> bool {
+    self.head.is_some() || self.tail.is_some() || self.head_done || self.tail_done
+  }
+
   /// Returns the range.
   #[inline]
   pub const fn range(&self) -> &R {
     &self.range
   }
+
+  /// Returns the range, the same as [`range`](Self::range).
+  ///
+  /// The name matches the `bounds` accessor of other scan iterators, for generic code.
+  #[inline]
+  pub const fn bounds(&self) -> &R {
+    &self.range
+  }
+
+  /// Returns the start and end bounds the iterator seeks with.
+  ///
+  /// The start bound is used to seek the head via [`Seekable::lower_bound`], and the end bound
+  /// is used to seek the tail via [`Seekable::upper_bound`]. Calling this does not drive the
+  /// iterator.
+  #[inline]
+  pub fn effective_bounds(&self) -> (Bound<&Q>, Bound<&Q>) {
+    (self.range.start_bound(), self.range.end_bound())
+  }
 }
 
 impl<R, Q, S, E, C, K, V> Iterator for Range<R, Q, S, E, C, K, V>
 where
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   S: Seekable<Q, Entry = E>,
   E: Cursor + Clone,
   C: QueryComparator<E::Key, Q>,
@@ -115,25 +149,38 @@ where
   type Item = E;
 
   fn next(&mut self) -> Option<Self::Item> {
+    if self.head_done || exhausted(&self.remaining) {
+      return None;
+    }
+
     let next_head = match self.head.as_ref() {
       Some(head) => head.next(),
       None => self.seeker.lower_bound(self.range.start_bound()),
     };
 
-    self.head = next_valid(
+    let next_head = next_valid(
       next_head,
       &self.query_version,
       &self.key_validator,
       &self.value_validator,
     );
+    self.head = skip_duplicates(next_head, self.head.as_ref(), &self.comparator, |ent| {
+      next_valid(
+        ent.next(),
+        &self.query_version,
+        &self.key_validator,
+        &self.value_validator,
+      )
+    });
 
     if let Some(ref h) = self.head {
       match &self.tail {
         Some(t) => {
-          let bound = Bound::Excluded(t.key());
-          if !below_upper_bound(&self.comparator, &bound, h.key()) {
+          if compare_keys(&self.comparator, t, h).is_le() {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
         None => {
@@ -141,19 +188,25 @@ where
           if !below_upper_bound_compare(&self.comparator, &bound, h.key()) {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
       }
     }
 
-    self.head.clone()
+    if self.head.is_none() {
+      self.head_done = true;
+    }
+
+    consume(&mut self.remaining, self.head.clone())
   }
 }
 
 impl<R, Q, S, E, C, K, V> DoubleEndedIterator for Range<R, Q, S, E, C, K, V>
 where
   K: Validator<E::Key>,
-  V: Validator<E::Value>,
+  V: VersionedValidator<E::Version, E::Value>,
   S: Seekable<Q, Entry = E>,
   E: Entry + DoubleEndedCursor + Clone,
   C: QueryComparator<E::Key, Q>,
@@ -161,25 +214,38 @@ where
   R: RangeBounds<Q>,
 {
   fn next_back(&mut self) -> Option<Self::Item> {
+    if self.tail_done || exhausted(&self.remaining) {
+      return None;
+    }
+
     let next_tail = match self.tail.as_ref() {
       Some(tail) => tail.next_back(),
       None => self.seeker.upper_bound(self.range.end_bound()),
     };
 
-    self.tail = next_back_valid(
+    let next_tail = next_back_valid(
       next_tail,
       &self.query_version,
       &self.key_validator,
       &self.value_validator,
     );
+    self.tail = skip_duplicates(next_tail, self.tail.as_ref(), &self.comparator, |ent| {
+      next_back_valid(
+        ent.next_back(),
+        &self.query_version,
+        &self.key_validator,
+        &self.value_validator,
+      )
+    });
 
     if let Some(ref t) = self.tail {
       match &self.head {
         Some(h) => {
-          let bound = Bound::Excluded(h.key());
-          if !above_lower_bound(&self.comparator, &bound, t.key()) {
+          if compare_keys(&self.comparator, h, t).is_ge() {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
         None => {
@@ -187,12 +253,18 @@ where
           if !above_lower_bound_compare(&self.comparator, &bound, t.key()) {
             self.head = None;
             self.tail = None;
+            self.head_done = true;
+            self.tail_done = true;
           }
         }
       }
     }
 
-    self.tail.clone()
+    if self.tail.is_none() {
+      self.tail_done = true;
+    }
+
+    consume(&mut self.remaining, self.tail.clone())
   }
 }
 
@@ -210,19 +282,6 @@ where
   }
 }
 
-/// Helper function to check if a value is above a lower bound
-fn above_lower_bound<C, K>(cmp: &C, bound: &Bound<&K>, other: &K) -> bool
-where
-  C: Comparator<K>,
-  K: ?Sized,
-{
-  match *bound {
-    Bound::Unbounded => true,
-    Bound::Included(key) => cmp.compare(key, other).is_le(),
-    Bound::Excluded(key) => cmp.compare(key, other).is_lt(),
-  }
-}
-
 /// Helper function to check if a value is below an upper bound
 fn below_upper_bound_compare<C, V, T>(cmp: &C, bound: &Bound<&T>, other: &V) -> bool
 where
@@ -236,16 +295,3 @@ where
     Bound::Excluded(key) => cmp.query_compare(other, key).is_lt(),
   }
 }
-
-/// Helper function to check if a value is below an upper bound
-fn below_upper_bound<C, K>(cmp: &C, bound: &Bound<&K>, other: &K) -> bool
-where
-  C: Comparator<K>,
-  K: ?Sized,
-{
-  match *bound {
-    Bound::Unbounded => true,
-    Bound::Included(key) => cmp.compare(key, other).is_ge(),
-    Bound::Excluded(key) => cmp.compare(key, other).is_gt(),
-  }
-}
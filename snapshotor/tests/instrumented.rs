@@ -0,0 +1,761 @@
+use core::{cell::Cell, ops::Bound};
+
+use dbutils::equivalentor::{
+  Ascend, Comparator, Descend, Equivalentor, QueryComparator, QueryEquivalentor,
+};
+use snapshotor::{
+  dedup, valid, AnyValidator, Builder, Cursor, CursorExt, DoubleEndedCursor, DoubleEndedCursorExt,
+  Entry, InitializorFn, NoopValidator, Rewindable, Seekable,
+};
+
+/// A cursor over a sorted slice of `(key, version)` pairs which counts how many times it is moved.
+///
+/// If `seekable` is `true`, the cursor jumps to the next distinct key in one move.
+#[derive(Clone)]
+struct Counted<'a> {
+  data: &'a [(u32, u64)],
+  idx: usize,
+  moves: &'a Cell<usize>,
+  seekable: bool,
+}
+
+impl Entry for Counted<'_> {
+  type Key = u32;
+  type Value = u64;
+  type Version = u64;
+
+  fn key(&self) -> &Self::Key {
+    &self.data[self.idx].0
+  }
+
+  fn value(&self) -> &Self::Value {
+    &self.data[self.idx].1
+  }
+
+  fn version(&self) -> Self::Version {
+    self.data[self.idx].1
+  }
+}
+
+impl Cursor for Counted<'_> {
+  fn next(&self) -> Option<Self> {
+    self.moves.set(self.moves.get() + 1);
+    (self.idx + 1 < self.data.len()).then(|| Self {
+      idx: self.idx + 1,
+      ..self.clone()
+    })
+  }
+
+  fn skip_to_different_key<E>(&self, equivalentor: &E) -> Option<Self>
+  where
+    E: Equivalentor<Self::Key>,
+  {
+    if self.seekable {
+      self.moves.set(self.moves.get() + 1);
+      let key = self.key();
+      let idx = self.data.partition_point(|(k, _)| k <= key);
+      return (idx < self.data.len()).then(|| Self {
+        idx,
+        ..self.clone()
+      });
+    }
+
+    let mut next = self.next();
+    while let Some(ent) = next {
+      if !equivalentor.equivalent(ent.key(), self.key()) {
+        return Some(ent);
+      }
+      next = ent.next();
+    }
+    None
+  }
+}
+
+impl DoubleEndedCursor for Counted<'_> {
+  fn next_back(&self) -> Option<Self> {
+    self.moves.set(self.moves.get() + 1);
+    self.idx.checked_sub(1).map(|idx| Self {
+      idx,
+      ..self.clone()
+    })
+  }
+}
+
+struct Rewinder<'a> {
+  data: &'a [(u32, u64)],
+  moves: &'a Cell<usize>,
+  seekable: bool,
+}
+
+impl<'a> Rewindable for Rewinder<'a> {
+  type Entry = Counted<'a>;
+
+  fn first(&self) -> Option<Self::Entry> {
+    (!self.data.is_empty()).then_some(Counted {
+      data: self.data,
+      idx: 0,
+      moves: self.moves,
+      seekable: self.seekable,
+    })
+  }
+
+  fn last(&self) -> Option<Self::Entry> {
+    self.data.len().checked_sub(1).map(|idx| Counted {
+      data: self.data,
+      idx,
+      moves: self.moves,
+      seekable: self.seekable,
+    })
+  }
+}
+
+// keys are sorted ascending, versions descending for the same key.
+const DATA: &[(u32, u64)] = &[(0, 2), (0, 1), (1, 1), (2, 3), (2, 0), (3, 1)];
+
+#[test]
+fn dedup_iter_fused_forwards() {
+  let moves = Cell::new(0);
+  let mut it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> = Builder::new(Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  })
+  .iter(3);
+
+  let keys = it.by_ref().map(|ent| *ent.key()).collect::<Vec<_>>();
+  assert_eq!(keys, [0, 1, 2, 3]);
+
+  let scanned = moves.get();
+  for _ in 0..3 {
+    assert!(it.next().is_none());
+  }
+  assert_eq!(moves.get(), scanned);
+}
+
+#[test]
+fn dedup_iter_fused_backwards() {
+  let moves = Cell::new(0);
+  let mut it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> = Builder::new(Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  })
+  .iter(3);
+
+  let keys = it.by_ref().rev().map(|ent| *ent.key()).collect::<Vec<_>>();
+  assert_eq!(keys, [3, 2, 1, 0]);
+
+  let scanned = moves.get();
+  for _ in 0..3 {
+    assert!(it.next_back().is_none());
+  }
+  assert_eq!(moves.get(), scanned);
+}
+
+#[test]
+fn initializor_fn() {
+  let moves = Cell::new(0);
+  let (firsts, lasts) = (Cell::new(0), Cell::new(0));
+  let rewinder = Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  };
+  let init = InitializorFn::new(
+    || {
+      firsts.set(firsts.get() + 1);
+      rewinder.first()
+    },
+    || {
+      lasts.set(lasts.get() + 1);
+      rewinder.last()
+    },
+  );
+
+  let mut it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> = Builder::new(init).iter(3);
+  assert_eq!((firsts.get(), lasts.get()), (0, 0));
+
+  assert_eq!(it.next().map(|ent| *ent.key()), Some(0));
+  assert_eq!((firsts.get(), lasts.get()), (1, 0));
+  assert_eq!(it.next_back().map(|ent| *ent.key()), Some(3));
+  assert_eq!((firsts.get(), lasts.get()), (1, 1));
+
+  let keys = it.map(|ent| *ent.key()).collect::<Vec<_>>();
+  assert_eq!(keys, [1, 2]);
+  assert_eq!((firsts.get(), lasts.get()), (1, 1));
+
+  let it: valid::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(InitializorFn::new(|| rewinder.first(), || rewinder.last())).iter(1);
+  let all = it
+    .rev()
+    .map(|ent| (*ent.key(), ent.version()))
+    .collect::<Vec<_>>();
+  assert_eq!(all, [(3, 1), (2, 0), (1, 1), (0, 1)]);
+}
+
+#[test]
+fn valid_iter_fused() {
+  let moves = Cell::new(0);
+  let mut it: valid::Iter<_, _, Ascend, NoopValidator, NoopValidator> = Builder::new(Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  })
+  .iter(1);
+
+  assert_eq!(it.by_ref().count(), 4);
+
+  let scanned = moves.get();
+  assert!(it.next().is_none());
+  assert_eq!(moves.get(), scanned);
+}
+
+// key 0 has many versions, and its latest version is a tombstone.
+const CHURN: &[(u32, u64)] = &[
+  (0, 10),
+  (0, 9),
+  (0, 8),
+  (0, 7),
+  (0, 6),
+  (0, 5),
+  (0, 4),
+  (0, 3),
+  (0, 2),
+  (0, 1),
+  (1, 1),
+];
+
+fn churn_moves(seekable: bool) -> usize {
+  let moves = Cell::new(0);
+  let tombstone = AnyValidator(|val: &u64| *val <= 1);
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, _> = Builder::new(Rewinder {
+    data: CHURN,
+    moves: &moves,
+    seekable,
+  })
+  .with_value_validator(tombstone)
+  .iter(10);
+
+  let keys = it.map(|ent| *ent.key()).collect::<Vec<_>>();
+  assert_eq!(keys, [1]);
+  moves.get()
+}
+
+#[test]
+fn dedup_skip_to_different_key() {
+  let walked = churn_moves(false);
+  let skipped = churn_moves(true);
+  // walking visits the 9 remaining versions of key 0, skipping jumps in one move.
+  assert_eq!(walked - skipped, 9);
+}
+
+// versions of the same key are kept oldest first.
+const ASCENDING: &[(u32, u64)] = &[(0, 1), (0, 2), (0, 3), (1, 1), (1, 5), (2, 2)];
+
+#[test]
+fn valid_iter_sort_versions() {
+  let moves = Cell::new(0);
+  let rewinder = || Rewinder {
+    data: ASCENDING,
+    moves: &moves,
+    seekable: false,
+  };
+
+  let it: valid::Iter<_, _> = Builder::new(rewinder()).sort_versions().iter(4);
+  let entries = it
+    .map(|ent| (*ent.key(), ent.version()))
+    .collect::<Vec<_>>();
+  assert_eq!(entries, [(0, 3), (0, 2), (0, 1), (1, 1), (2, 2)]);
+
+  let it: valid::Iter<_, _> = Builder::new(rewinder()).sort_versions().iter(4);
+  let entries = it
+    .rev()
+    .map(|ent| (*ent.key(), ent.version()))
+    .collect::<Vec<_>>();
+  assert_eq!(entries, [(2, 2), (1, 1), (0, 1), (0, 2), (0, 3)]);
+
+  let mut it: valid::Iter<_, _> = Builder::new(rewinder()).sort_versions().iter(4);
+  assert_eq!(it.next().map(|ent| ent.version()), Some(3));
+  assert_eq!(it.next_back().map(|ent| *ent.key()), Some(2));
+  assert_eq!(it.next_back().map(|ent| *ent.key()), Some(1));
+  assert_eq!(it.next().map(|ent| ent.version()), Some(2));
+  assert_eq!(it.next().map(|ent| ent.version()), Some(1));
+  assert!(it.next().is_none());
+}
+
+#[test]
+fn valid_iter_sort_versions_noop() {
+  let moves = Cell::new(0);
+  let rewinder = || Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  };
+
+  let sorted: valid::Iter<_, _> = Builder::new(rewinder()).sort_versions().iter(3);
+  let unsorted: valid::Iter<_, _> = Builder::new(rewinder()).iter(3);
+  assert_eq!(
+    sorted
+      .map(|ent| (*ent.key(), ent.version()))
+      .collect::<Vec<_>>(),
+    unsorted
+      .map(|ent| (*ent.key(), ent.version()))
+      .collect::<Vec<_>>(),
+  );
+}
+
+/// A cursor over byte keys which can optionally expose them through `Entry::key_bytes`.
+#[derive(Clone)]
+struct ByteKeyed<'a> {
+  data: &'a [(&'a [u8], u64)],
+  idx: usize,
+  expose: bool,
+}
+
+impl Entry for ByteKeyed<'_> {
+  type Key = [u8];
+  type Value = u64;
+  type Version = u64;
+
+  fn key(&self) -> &Self::Key {
+    self.data[self.idx].0
+  }
+
+  fn value(&self) -> &Self::Value {
+    &self.data[self.idx].1
+  }
+
+  fn version(&self) -> Self::Version {
+    self.data[self.idx].1
+  }
+
+  fn key_bytes(&self) -> Option<&[u8]> {
+    self.expose.then_some(self.data[self.idx].0)
+  }
+}
+
+impl Cursor for ByteKeyed<'_> {
+  fn next(&self) -> Option<Self> {
+    (self.idx + 1 < self.data.len()).then(|| Self {
+      idx: self.idx + 1,
+      ..self.clone()
+    })
+  }
+}
+
+impl DoubleEndedCursor for ByteKeyed<'_> {
+  fn next_back(&self) -> Option<Self> {
+    self.idx.checked_sub(1).map(|idx| Self {
+      idx,
+      ..self.clone()
+    })
+  }
+}
+
+struct ByteRewinder<'a> {
+  data: &'a [(&'a [u8], u64)],
+  expose: bool,
+}
+
+impl<'a> Rewindable for ByteRewinder<'a> {
+  type Entry = ByteKeyed<'a>;
+
+  fn first(&self) -> Option<Self::Entry> {
+    (!self.data.is_empty()).then_some(ByteKeyed {
+      data: self.data,
+      idx: 0,
+      expose: self.expose,
+    })
+  }
+
+  fn last(&self) -> Option<Self::Entry> {
+    self.data.len().checked_sub(1).map(|idx| ByteKeyed {
+      data: self.data,
+      idx,
+      expose: self.expose,
+    })
+  }
+}
+
+const BYTES: &[(&[u8], u64)] = &[
+  (b"a", 2),
+  (b"a", 1),
+  (b"ab", 1),
+  (b"b", 3),
+  (b"b", 0),
+  (b"ba", 1),
+  (b"c", 2),
+];
+
+/// Alternates between the front and the back so the two ends meet and the
+/// crossing check decides when to stop.
+fn zigzag<I>(mut it: I) -> Vec<(Vec<u8>, u64)>
+where
+  I: DoubleEndedIterator,
+  I::Item: Entry<Key = [u8], Version = u64>,
+{
+  let mut out = Vec::new();
+  let mut front = true;
+  loop {
+    let ent = if front { it.next() } else { it.next_back() };
+    match ent {
+      Some(ent) => out.push((ent.key().to_vec(), ent.version())),
+      None => break,
+    }
+    front = !front;
+  }
+  out
+}
+
+#[test]
+fn key_bytes_fast_path_matches_comparator() {
+  for version in 0..4 {
+    let dedup = |expose| {
+      let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+        Builder::new(ByteRewinder {
+          data: BYTES,
+          expose,
+        })
+        .iter(version);
+      zigzag(it)
+    };
+    assert_eq!(dedup(true), dedup(false));
+
+    let valid = |expose| {
+      let it: valid::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+        Builder::new(ByteRewinder {
+          data: BYTES,
+          expose,
+        })
+        .iter(version);
+      zigzag(it)
+    };
+    assert_eq!(valid(true), valid(false));
+  }
+
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> = Builder::new(ByteRewinder {
+    data: BYTES,
+    expose: true,
+  })
+  .iter(3);
+  let keys = zigzag(it).into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+  assert_eq!(
+    keys,
+    [
+      b"a".to_vec(),
+      b"c".to_vec(),
+      b"ab".to_vec(),
+      b"ba".to_vec(),
+      b"b".to_vec()
+    ]
+  );
+}
+
+#[test]
+fn key_bytes_fast_path_needs_ascend() {
+  // descending keys, the bytes do not follow the cursor order.
+  const DESC: &[(&[u8], u64)] = &[
+    (b"c", 2),
+    (b"ba", 1),
+    (b"b", 3),
+    (b"b", 0),
+    (b"ab", 1),
+    (b"a", 2),
+    (b"a", 1),
+  ];
+
+  for version in 0..4 {
+    let dedup = |expose| {
+      let it: dedup::Iter<_, _, Descend, NoopValidator, NoopValidator> =
+        Builder::new(ByteRewinder { data: DESC, expose })
+          .with_comparator(Descend)
+          .iter(version);
+      zigzag(it)
+    };
+    assert_eq!(dedup(true), dedup(false));
+  }
+}
+
+impl<'a> Seekable<[u8]> for ByteRewinder<'a> {
+  type Entry = ByteKeyed<'a>;
+
+  fn lower_bound(&self, bound: Bound<&[u8]>) -> Option<Self::Entry> {
+    let idx = match bound {
+      Bound::Included(q) => self.data.partition_point(|(k, _)| *k < q),
+      Bound::Excluded(q) => self.data.partition_point(|(k, _)| *k <= q),
+      Bound::Unbounded => 0,
+    };
+    (idx < self.data.len()).then_some(ByteKeyed {
+      data: self.data,
+      idx,
+      expose: self.expose,
+    })
+  }
+
+  fn upper_bound(&self, bound: Bound<&[u8]>) -> Option<Self::Entry> {
+    let idx = match bound {
+      Bound::Included(q) => self.data.partition_point(|(k, _)| *k <= q),
+      Bound::Excluded(q) => self.data.partition_point(|(k, _)| *k < q),
+      Bound::Unbounded => self.data.len(),
+    };
+    idx.checked_sub(1).map(|idx| ByteKeyed {
+      data: self.data,
+      idx,
+      expose: self.expose,
+    })
+  }
+}
+
+/// An ascending comparator which counts how many times it is called.
+struct CountingAscend<'a>(&'a Cell<usize>);
+
+impl Equivalentor<[u8]> for CountingAscend<'_> {
+  fn equivalent(&self, a: &[u8], b: &[u8]) -> bool {
+    self.0.set(self.0.get() + 1);
+    a == b
+  }
+}
+
+impl Comparator<[u8]> for CountingAscend<'_> {
+  fn compare(&self, a: &[u8], b: &[u8]) -> core::cmp::Ordering {
+    self.0.set(self.0.get() + 1);
+    a.cmp(b)
+  }
+
+  fn is_ascend(&self) -> bool {
+    true
+  }
+}
+
+impl QueryEquivalentor<[u8], [u8]> for CountingAscend<'_> {
+  fn query_equivalent(&self, a: &[u8], b: &[u8]) -> bool {
+    self.equivalent(a, b)
+  }
+}
+
+impl QueryComparator<[u8], [u8]> for CountingAscend<'_> {
+  fn query_compare(&self, a: &[u8], b: &[u8]) -> core::cmp::Ordering {
+    self.compare(a, b)
+  }
+}
+
+#[test]
+fn key_bytes_fast_path_range_containment() {
+  let calls = Cell::new(0);
+  let range = |expose, forward| {
+    calls.set(0);
+    let it: dedup::Range<_, [u8], _, _, _, NoopValidator, NoopValidator> =
+      Builder::new(ByteRewinder {
+        data: BYTES,
+        expose,
+      })
+      .with_comparator(CountingAscend(&calls))
+      .range(3, (Bound::Included(&b"a"[..]), Bound::Excluded(&b"c"[..])));
+    let out = if forward {
+      it.map(|ent| (ent.key().to_vec(), ent.version()))
+        .collect::<Vec<_>>()
+    } else {
+      zigzag(it)
+    };
+    (out, calls.get())
+  };
+
+  for forward in [true, false] {
+    let (exposed, fast) = range(true, forward);
+    let (hidden, slow) = range(false, forward);
+    assert_eq!(exposed, hidden);
+    assert!(fast < slow, "{fast} >= {slow}");
+  }
+
+  let (keys, _) = range(true, false);
+  assert_eq!(
+    keys.into_iter().map(|(k, _)| k).collect::<Vec<_>>(),
+    [b"a".to_vec(), b"ba".to_vec(), b"ab".to_vec(), b"b".to_vec()]
+  );
+}
+
+#[test]
+fn nth_valid_matches_repeated_next_valid() {
+  let moves = Cell::new(0);
+  let rewinder = Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  };
+  let first = rewinder.first().unwrap();
+
+  let mut stepped = first.clone();
+  for _ in 0..3 {
+    stepped = stepped
+      .next_valid::<Ascend, _, _>(&1, &NoopValidator, &NoopValidator)
+      .unwrap();
+  }
+  let nth = first
+    .nth_valid(2, &1, &NoopValidator, &NoopValidator)
+    .unwrap();
+  assert_eq!(
+    (*nth.key(), nth.version()),
+    (*stepped.key(), stepped.version())
+  );
+  assert_eq!((*nth.key(), nth.version()), (2, 0));
+
+  let nth = first
+    .nth_valid(3, &1, &NoopValidator, &NoopValidator)
+    .unwrap();
+  assert_eq!((*nth.key(), nth.version()), (3, 1));
+  assert!(first
+    .nth_valid(4, &1, &NoopValidator, &NoopValidator)
+    .is_none());
+}
+
+#[test]
+fn next_back_valid_moves_backwards() {
+  let moves = Cell::new(0);
+  let middle = Counted {
+    data: DATA,
+    idx: 3,
+    moves: &moves,
+    seekable: false,
+  };
+  assert_eq!((*middle.key(), middle.version()), (2, 3));
+
+  let prev = middle
+    .next_back_valid::<Ascend, _, _>(&3, &NoopValidator, &NoopValidator)
+    .unwrap();
+  assert_eq!((*prev.key(), prev.version()), (1, 1));
+
+  // versions newer than the query version are skipped.
+  let prev = middle
+    .next_back_valid::<Ascend, _, _>(&1, &NoopValidator, &NoopValidator)
+    .unwrap();
+  assert_eq!((*prev.key(), prev.version()), (1, 1));
+  let prev = prev
+    .next_back_valid::<Ascend, _, _>(&1, &NoopValidator, &NoopValidator)
+    .unwrap();
+  assert_eq!((*prev.key(), prev.version()), (0, 1));
+  assert!(prev
+    .next_back_valid::<Ascend, _, _>(&1, &NoopValidator, &NoopValidator)
+    .is_none());
+}
+
+#[test]
+fn nth_dedup_matches_dedup_iter() {
+  let moves = Cell::new(0);
+  let rewinder = || Rewinder {
+    data: DATA,
+    moves: &moves,
+    seekable: false,
+  };
+  // the oldest version of key 0, so the cursor moves on to key 1 first.
+  let last_of_first_key = Counted {
+    data: DATA,
+    idx: 1,
+    moves: &moves,
+    seekable: false,
+  };
+
+  for n in 0..4 {
+    let mut it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+      Builder::new(rewinder()).iter(3);
+    let expected = it.nth(n + 1).map(|ent| (*ent.key(), ent.version()));
+    let nth = last_of_first_key
+      .nth_dedup(n, &3, &Ascend, &NoopValidator, &NoopValidator)
+      .map(|ent| (*ent.key(), ent.version()));
+    assert_eq!(nth, expected);
+  }
+}
+
+/// A [`Seekable`] over a sorted slice which counts how many times it seeks.
+struct Seeker<'a> {
+  data: &'a [(u32, u64)],
+  moves: &'a Cell<usize>,
+  lower: &'a Cell<usize>,
+  upper: &'a Cell<usize>,
+}
+
+impl<'a> Seeker<'a> {
+  fn entry(&self, idx: usize) -> Option<Counted<'a>> {
+    (idx < self.data.len()).then_some(Counted {
+      data: self.data,
+      idx,
+      moves: self.moves,
+      seekable: false,
+    })
+  }
+}
+
+impl<'a> Seekable<u32> for Seeker<'a> {
+  type Entry = Counted<'a>;
+
+  fn lower_bound(&self, bound: Bound<&u32>) -> Option<Self::Entry> {
+    self.lower.set(self.lower.get() + 1);
+    let idx = match bound {
+      Bound::Included(q) => self.data.partition_point(|(k, _)| k < q),
+      Bound::Excluded(q) => self.data.partition_point(|(k, _)| k <= q),
+      Bound::Unbounded => 0,
+    };
+    self.entry(idx)
+  }
+
+  fn upper_bound(&self, bound: Bound<&u32>) -> Option<Self::Entry> {
+    self.upper.set(self.upper.get() + 1);
+    let idx = match bound {
+      Bound::Included(q) => self.data.partition_point(|(k, _)| k <= q),
+      Bound::Excluded(q) => self.data.partition_point(|(k, _)| k < q),
+      Bound::Unbounded => self.data.len(),
+    };
+    idx.checked_sub(1).and_then(|idx| self.entry(idx))
+  }
+}
+
+#[test]
+fn range_seeks_once() {
+  let moves = Cell::new(0);
+  let lower = Cell::new(0);
+  let upper = Cell::new(0);
+  let seeker = || Seeker {
+    data: DATA,
+    moves: &moves,
+    lower: &lower,
+    upper: &upper,
+  };
+  let skip_one = |key: &u32| *key != 1;
+
+  // entries skipped by the key validator are walked over, not seeked past.
+  let mut it: dedup::Range<_, u32, _, _, Ascend, _, NoopValidator> = Builder::new(seeker())
+    .with_key_validator(AnyValidator(skip_one))
+    .range(3, ..);
+  let keys = it.by_ref().map(|ent| *ent.key()).collect::<Vec<_>>();
+  assert_eq!(keys, [0, 2, 3]);
+  assert!(it.next().is_none());
+  assert_eq!((lower.get(), upper.get()), (1, 0));
+
+  // once the two ends meet, neither end seeks again.
+  lower.set(0);
+  let mut it: dedup::Range<_, u32, _, _, Ascend, _, NoopValidator> = Builder::new(seeker())
+    .with_key_validator(AnyValidator(skip_one))
+    .range(3, ..);
+  let zigzag = [
+    it.next(),
+    it.next_back(),
+    it.next(),
+    it.next_back(),
+    it.next(),
+    it.next_back(),
+  ]
+  .map(|ent| ent.map(|ent| *ent.key()));
+  assert_eq!(zigzag, [Some(0), Some(3), Some(2), None, None, None]);
+  assert_eq!((lower.get(), upper.get()), (1, 1));
+
+  lower.set(0);
+  upper.set(0);
+  let it: valid::Range<_, u32, _, _, Ascend, _, NoopValidator> = Builder::new(seeker())
+    .with_key_validator(AnyValidator(skip_one))
+    .range(3, 1..);
+  let entries = it
+    .rev()
+    .map(|ent| (*ent.key(), ent.version()))
+    .collect::<Vec<_>>();
+  assert_eq!(entries, [(3, 1), (2, 0), (2, 3)]);
+  assert_eq!((lower.get(), upper.get()), (0, 1));
+}
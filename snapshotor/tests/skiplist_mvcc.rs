@@ -4,11 +4,11 @@ use core::{
   ops::{Bound, RangeBounds},
   sync::atomic::{AtomicU64, Ordering},
 };
-use crossbeam_skiplist::{
+use crossbeam_skiplist::{equivalent as skiplist_equivalent, SkipMap as CSkipMap};
+use dbutils::{
   equivalent::{Comparable, Equivalent},
-  SkipMap as CSkipMap,
+  state::{Active, MaybeTombstone},
 };
-use dbutils::state::{Active, MaybeTombstone};
 
 /// Errors for multiple version `SkipMap`s
 #[derive(Debug, Clone)]
@@ -247,7 +247,7 @@ mod iter {
         valid::Iter<MapEntry<'a, K, V>, Rewinder<'a, K, V>, Ascend, NoopValidator, NoopValidator>;
     }
   }
-  pub struct Rewinder<'a, K, V>(&'a SkipMap<K, V>);
+  pub struct Rewinder<'a, K, V>(pub(super) &'a SkipMap<K, V>);
   impl<'a, K, V> snapshotor::Rewindable for Rewinder<'a, K, V>
   where
     K: Ord + 'static,
@@ -392,9 +392,10 @@ mod range {
         R: RangeBounds<Q>;
     }
   }
+  #[derive(Clone, Copy)]
   pub struct Seeker<'a, K, V> {
-    map: &'a super::SkipMap<K, V>,
-    query_version: u64,
+    pub(super) map: &'a super::SkipMap<K, V>,
+    pub(super) query_version: u64,
   }
   impl<'a, K, V, Q> Seekable<Q> for Seeker<'a, K, V>
   where
@@ -563,7 +564,7 @@ impl<'a, Q: ?Sized, K: ?Sized> Query<'a, Q, K> {
     }
   }
 }
-impl<Q, K> Equivalent<Query<'_, Q, K>> for Key<K>
+impl<Q, K> skiplist_equivalent::Equivalent<Query<'_, Q, K>> for Key<K>
 where
   K: Equivalent<Q>,
   Q: ?Sized,
@@ -573,7 +574,7 @@ where
     Equivalent::equivalent(&self.key, key.query) && key.version == self.version
   }
 }
-impl<Q, K> Comparable<Query<'_, Q, K>> for Key<K>
+impl<Q, K> skiplist_equivalent::Comparable<Query<'_, Q, K>> for Key<K>
 where
   K: Comparable<Q>,
   Q: ?Sized,
@@ -1006,6 +1007,7 @@ where
   }
 }
 
+#[derive(Clone, Copy)]
 pub struct TombstoneValidator;
 
 impl<V> snapshotor::Validator<Option<V>> for TombstoneValidator {
@@ -1864,3 +1866,398 @@ fn compact() {
     assert_eq!(ent.version(), 3);
   }
 }
+
+#[test]
+fn take() {
+  use dbutils::equivalentor::Ascend;
+  use snapshotor::{dedup, Builder, NoopValidator};
+
+  let map = SkipMap::new();
+  for version in 0..10 {
+    map.insert_unchecked(version, 0, version);
+  }
+
+  for i in 1..10 {
+    map.insert_unchecked(0, i, i);
+  }
+
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, TombstoneValidator> =
+    Builder::new(iter::Rewinder(&map))
+      .with_value_validator(TombstoneValidator)
+      .take(3)
+      .iter(10);
+
+  let keys = it
+    .map(|ent| *snapshotor::Entry::key(&ent))
+    .collect::<Vec<_>>();
+  assert_eq!(keys, [0, 1, 2]);
+
+  let mut it: dedup::Iter<_, _, Ascend, NoopValidator, TombstoneValidator> =
+    Builder::new(iter::Rewinder(&map))
+      .with_value_validator(TombstoneValidator)
+      .take(3)
+      .iter(10);
+
+  assert_eq!(*snapshotor::Entry::key(&it.next().unwrap()), 0);
+  assert_eq!(*snapshotor::Entry::key(&it.next_back().unwrap()), 9);
+  assert_eq!(*snapshotor::Entry::key(&it.next().unwrap()), 1);
+  assert!(it.next().is_none());
+  assert!(it.next_back().is_none());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn arc_entry() {
+  use dbutils::equivalentor::Ascend;
+  use entry::MapEntry;
+  use snapshotor::{dedup, Builder, NoopValidator, Rewindable};
+  use std::sync::Arc;
+
+  struct ArcRewinder<'a>(&'a SkipMap<u64, u64>);
+
+  impl<'a> Rewindable for ArcRewinder<'a> {
+    type Entry = Arc<MapEntry<'a, u64, u64>>;
+
+    fn first(&self) -> Option<Self::Entry> {
+      self.0.inner.front().map(|ent| Arc::new(MapEntry(ent)))
+    }
+
+    fn last(&self) -> Option<Self::Entry> {
+      self.0.inner.back().map(|ent| Arc::new(MapEntry(ent)))
+    }
+  }
+
+  let map = SkipMap::new();
+  for key in 0..5 {
+    map.insert_unchecked(1, key, key);
+    map.insert_unchecked(2, key, key * 10);
+  }
+
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, TombstoneValidator> =
+    Builder::new(ArcRewinder(&map))
+      .with_value_validator(TombstoneValidator)
+      .iter(2);
+
+  let entries = std::thread::scope(|s| {
+    it.map(|ent| {
+      // the entry can be shared with other threads without cloning the underlying entry.
+      let shared = Arc::clone(&ent);
+      s.spawn(move || {
+        (
+          *snapshotor::Entry::key(&shared),
+          snapshotor::Entry::version(&shared),
+        )
+      })
+    })
+    .collect::<Vec<_>>()
+    .into_iter()
+    .map(|handle| handle.join().unwrap())
+    .collect::<Vec<_>>()
+  });
+  assert_eq!(entries, [(0, 2), (1, 2), (2, 2), (3, 2), (4, 2)]);
+
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, TombstoneValidator> =
+    Builder::new(ArcRewinder(&map))
+      .with_value_validator(TombstoneValidator)
+      .iter(2);
+  let keys = it
+    .rev()
+    .map(|ent| *snapshotor::Entry::key(&ent))
+    .collect::<Vec<_>>();
+  assert_eq!(keys, [4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn range_after() {
+  use dbutils::equivalentor::Ascend;
+  use snapshotor::{dedup, Builder, NoopValidator, RangeAfter};
+
+  type Page<'a> = dedup::Range<
+    RangeAfter<'a, usize, core::ops::Range<usize>>,
+    usize,
+    range::Seeker<'a, usize, usize>,
+    entry::MapEntry<'a, usize, usize>,
+    Ascend,
+    NoopValidator,
+    TombstoneValidator,
+  >;
+
+  let map = SkipMap::new();
+  for i in 0..10 {
+    map.insert_unchecked(0, i, i);
+    map.insert_unchecked(1, i, i * 10);
+  }
+
+  let page = |last_key: &usize| -> Vec<usize> {
+    let seeker = range::Seeker {
+      map: &map,
+      query_version: 1,
+    };
+    let it: Page<'_> = Builder::new(seeker)
+      .with_value_validator(TombstoneValidator)
+      .take(2)
+      .range_after(1, 2..8, last_key);
+    it.map(|ent| *snapshotor::Entry::key(&ent)).collect()
+  };
+
+  // start before the range
+  let mut keys = Vec::new();
+  let mut last_key = 0;
+  loop {
+    let page = page(&last_key);
+    if page.is_empty() {
+      break;
+    }
+    assert!(page.len() <= 2);
+    last_key = *page.last().unwrap();
+    keys.extend(page);
+  }
+  assert_eq!(keys, [2, 3, 4, 5, 6, 7]);
+
+  assert_eq!(page(&4), [5, 6]);
+  assert!(page(&7).is_empty());
+  assert!(page(&9).is_empty());
+}
+
+#[test]
+fn builder_clone_fan_out() {
+  use dbutils::equivalentor::Ascend;
+  use snapshotor::{dedup, Builder, NoopValidator};
+
+  type Scan<'a> = dedup::Range<
+    core::ops::Range<usize>,
+    usize,
+    range::Seeker<'a, usize, usize>,
+    entry::MapEntry<'a, usize, usize>,
+    Ascend,
+    NoopValidator,
+    TombstoneValidator,
+  >;
+
+  let map = SkipMap::new();
+  for i in 0..100 {
+    map.insert_unchecked(0, i, i);
+    map.insert_unchecked(1, i, i * 10);
+  }
+
+  let builder = Builder::new(range::Seeker {
+    map: &map,
+    query_version: 1,
+  })
+  .with_value_validator(TombstoneValidator);
+
+  let (left, right) = std::thread::scope(|s| {
+    let left = {
+      let builder = builder.clone();
+      s.spawn(move || {
+        let it: Scan<'_> = builder.range(1, 0..50);
+        it.map(|ent| snapshotor::Entry::value(&ent).unwrap())
+          .sum::<usize>()
+      })
+    };
+    let right = {
+      let builder = builder.clone();
+      s.spawn(move || {
+        let it: Scan<'_> = builder.range(1, 50..100);
+        it.map(|ent| snapshotor::Entry::value(&ent).unwrap())
+          .sum::<usize>()
+      })
+    };
+    (left.join().unwrap(), right.join().unwrap())
+  });
+
+  let it: Scan<'_> = builder.range(1, 0..100);
+  let total = it
+    .map(|ent| snapshotor::Entry::value(&ent).unwrap())
+    .sum::<usize>();
+  assert_eq!(left + right, total);
+  assert_eq!(total, (0..100).map(|i| i * 10).sum::<usize>());
+}
+
+#[test]
+fn dedup_version_equal_to_query() {
+  use dbutils::equivalentor::Ascend;
+  use snapshotor::{dedup, Builder, NoopValidator};
+
+  type Iter<'a> = dedup::Iter<
+    entry::MapEntry<'a, usize, usize>,
+    iter::Rewinder<'a, usize, usize>,
+    Ascend,
+    NoopValidator,
+    TombstoneValidator,
+  >;
+
+  let map = SkipMap::new();
+  // older, equal and newer versions around the query version 2
+  map.insert_unchecked(1, 0, 10);
+  map.insert_unchecked(2, 0, 20);
+  map.insert_unchecked(3, 0, 30);
+  // only the equal version
+  map.insert_unchecked(2, 1, 21);
+  // removed exactly at the query version
+  map.insert_unchecked(1, 2, 12);
+  map.remove_unchecked(2, 2);
+  // only a newer version
+  map.insert_unchecked(3, 3, 33);
+  // equal version followed by a newer removal
+  map.insert_unchecked(2, 4, 24);
+  map.remove_unchecked(3, 4);
+
+  let scan = |version: u64| -> Iter<'_> {
+    Builder::new(iter::Rewinder(&map))
+      .with_value_validator(TombstoneValidator)
+      .iter(version)
+  };
+  fn collect<'a>(
+    it: impl Iterator<Item = entry::MapEntry<'a, usize, usize>>,
+  ) -> Vec<(usize, u64, usize)> {
+    it.map(|ent| {
+      (
+        *snapshotor::Entry::key(&ent),
+        snapshotor::Entry::version(&ent),
+        snapshotor::Entry::value(&ent).unwrap(),
+      )
+    })
+    .collect()
+  }
+
+  let expected = [(0, 2, 20), (1, 2, 21), (4, 2, 24)];
+  assert_eq!(collect(scan(2)), expected);
+
+  let mut backward = collect(scan(2).rev());
+  backward.reverse();
+  assert_eq!(backward, expected);
+
+  // a single-key map, where the equal-version entry has no different-key neighbour
+  let map = SkipMap::new();
+  map.insert_unchecked(1, 0, 10);
+  map.insert_unchecked(2, 0, 20);
+  map.insert_unchecked(3, 0, 30);
+  let it: Iter<'_> = Builder::new(iter::Rewinder(&map))
+    .with_value_validator(TombstoneValidator)
+    .iter(2);
+  assert_eq!(collect(it.rev()), [(0, 2, 20)]);
+  let it: Iter<'_> = Builder::new(iter::Rewinder(&map))
+    .with_value_validator(TombstoneValidator)
+    .iter(2);
+  assert_eq!(collect(it), [(0, 2, 20)]);
+}
+
+#[test]
+fn merge_shadowing() {
+  use dbutils::equivalentor::Ascend;
+  use snapshotor::{dedup, Builder, MergeIter, NoopValidator};
+
+  type Iter<'a> = dedup::Iter<
+    entry::MapEntry<'a, usize, usize>,
+    iter::Rewinder<'a, usize, usize>,
+    Ascend,
+    NoopValidator,
+    NoopValidator,
+  >;
+
+  // the immutable table
+  let table = SkipMap::new();
+  for i in 0..5 {
+    table.insert_unchecked(1, i, i);
+  }
+
+  // the memtable, written at higher versions
+  let memtable = SkipMap::new();
+  memtable.insert_unchecked(3, 1, 100);
+  memtable.remove_unchecked(3, 2);
+  memtable.insert_unchecked(2, 7, 700);
+
+  let merged = |version: u64| -> Vec<(usize, usize)> {
+    // keep tombstones in the sources, so they can shadow the older table
+    let mem: Iter<'_> = Builder::new(iter::Rewinder(&memtable)).iter(version);
+    let tab: Iter<'_> = Builder::new(iter::Rewinder(&table)).iter(version);
+    MergeIter::new(mem, tab)
+      .with_value_validator(TombstoneValidator)
+      .map(|ent| {
+        (
+          *snapshotor::Entry::key(&ent),
+          snapshotor::Entry::value(&ent).unwrap(),
+        )
+      })
+      .collect()
+  };
+
+  assert_eq!(merged(1), [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+  assert_eq!(
+    merged(2),
+    [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (7, 700)]
+  );
+  assert_eq!(merged(3), [(0, 0), (1, 100), (3, 3), (4, 4), (7, 700)]);
+}
+
+#[test]
+fn seek_bounds() {
+  use dbutils::equivalent::{VersionedKey, VersionedQuery};
+  use snapshotor::{seek_lower, seek_upper};
+
+  // the skiplist compares by its own equivalent traits, so seek with the stored key type.
+  fn key<'a>(bound: Bound<VersionedQuery<'_, &'a str>>) -> Bound<VersionedKey<&'a str>> {
+    bound.map(|q| VersionedKey::new(*q.query(), q.version()))
+  }
+
+  let map = CSkipMap::new();
+  map.insert(VersionedKey::new("a", 1), "a1");
+  map.insert(VersionedKey::new("a", 3), "a2");
+  map.insert(VersionedKey::new("c", 1), "c1");
+  map.insert(VersionedKey::new("c", 3), "c2");
+  map.insert(VersionedKey::new("c", 5), "c3");
+
+  // `a..` lands on the newest version of `a` visible at version 4
+  let ent = map
+    .lower_bound(key(seek_lower(Bound::Included(&"a"), 4)).as_ref())
+    .unwrap();
+  assert_eq!(*ent.value(), "a2");
+  let ent = map
+    .lower_bound(key(seek_lower(Bound::Included(&"c"), 4)).as_ref())
+    .unwrap();
+  assert_eq!(*ent.value(), "c2");
+
+  // `(Excluded(a), ..)` skips every version of `a`, not just the ones newer than 4
+  let ent = map
+    .lower_bound(key(seek_lower(Bound::Excluded(&"a"), 4)).as_ref())
+    .unwrap();
+  assert_eq!(*ent.value(), "c3");
+  assert!(map
+    .lower_bound(key(seek_lower(Bound::Excluded(&"c"), 4)).as_ref())
+    .is_none());
+
+  // `..=a` lands on the oldest version of `a`
+  let ent = map
+    .upper_bound(key(seek_upper(Bound::Included(&"a"))).as_ref())
+    .unwrap();
+  assert_eq!(*ent.value(), "a1");
+  let ent = map
+    .upper_bound(key(seek_upper(Bound::Included(&"c"))).as_ref())
+    .unwrap();
+  assert_eq!(*ent.value(), "c1");
+
+  // `..c` skips every version of `c`
+  let ent = map
+    .upper_bound(key(seek_upper(Bound::Excluded(&"c"))).as_ref())
+    .unwrap();
+  assert_eq!(*ent.value(), "a1");
+  assert!(map
+    .upper_bound(key(seek_upper(Bound::Excluded(&"a"))).as_ref())
+    .is_none());
+
+  assert_eq!(
+    *map
+      .lower_bound(key(seek_lower(Bound::<&&str>::Unbounded, 4)).as_ref())
+      .unwrap()
+      .value(),
+    "a2"
+  );
+  assert_eq!(
+    *map
+      .upper_bound(key(seek_upper(Bound::<&&str>::Unbounded)).as_ref())
+      .unwrap()
+      .value(),
+    "c1"
+  );
+}
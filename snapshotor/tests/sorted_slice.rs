@@ -0,0 +1,805 @@
+use core::ops::{Bound, RangeBounds};
+
+use snapshotor::{
+  dedup, equivalentor::Ascend, raw, valid, Builder, Cursor, CursorExt, Descending,
+  DoubleEndedCursor, DoubleEndedCursorExt, Entry, EntrySlice, HeapEntry, NoopValidator, OwnedRange,
+  RangeRev, Rewindable, RewindableExt, Seekable, SliceEntry, SortedSlice, Validator, Versioned,
+  VersionedValidator,
+};
+
+type Slice = SortedSlice<'static, &'static str, &'static str>;
+type Ent = SliceEntry<'static, &'static str, &'static str>;
+
+// keys are sorted ascending, versions descending for the same key, `None` is a tombstone.
+const DATA: &[(&str, u64, Option<&str>)] = &[
+  ("a", 3, Some("a2")),
+  ("a", 1, Some("a1")),
+  ("b", 2, None),
+  ("b", 1, Some("b1")),
+  ("c", 5, Some("c3")),
+  ("c", 3, Some("c2")),
+  ("c", 1, Some("c1")),
+  ("d", 2, Some("d1")),
+];
+
+#[derive(Clone, Copy)]
+struct Live;
+
+impl<V> Validator<Option<V>> for Live {
+  #[inline]
+  fn validate(&self, value: &Option<V>) -> bool {
+    value.is_some()
+  }
+}
+
+fn latest(version: u64) -> dedup::Iter<Ent, Slice, Ascend, NoopValidator, Live> {
+  Builder::new(SortedSlice::new(DATA))
+    .with_value_validator(Live)
+    .iter(version)
+}
+
+fn all_versions(version: u64) -> valid::Iter<Ent, Slice> {
+  Builder::new(SortedSlice::new(DATA)).iter(version)
+}
+
+fn latest_range<R>(
+  version: u64,
+  range: R,
+) -> dedup::Range<R, &'static str, Slice, Ent, Ascend, NoopValidator, Live>
+where
+  R: RangeBounds<&'static str>,
+{
+  Builder::new(SortedSlice::new(DATA))
+    .with_value_validator(Live)
+    .range(version, range)
+}
+
+fn collect<I: Iterator<Item = Ent>>(it: I) -> Vec<(&'static str, u64)> {
+  it.map(|ent| (*ent.key(), ent.version())).collect()
+}
+
+#[test]
+fn iter_latest() {
+  assert_eq!(collect(latest(5)), [("a", 3), ("c", 5), ("d", 2)]);
+  assert_eq!(collect(latest(3)), [("a", 3), ("c", 3), ("d", 2)]);
+  assert_eq!(collect(latest(1)), [("a", 1), ("b", 1), ("c", 1)]);
+  assert!(collect(latest(0)).is_empty());
+
+  for version in 0..=5 {
+    let mut backwards = collect(latest(version).rev());
+    backwards.reverse();
+    assert_eq!(backwards, collect(latest(version)));
+  }
+}
+
+#[test]
+fn iter_latest_values() {
+  let values = latest(3)
+    .map(|ent| ent.value().unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(values, ["a2", "c2", "d1"]);
+
+  // without the tombstone validator, the tombstone of `b` is the latest entry.
+  let it: dedup::Iter<Ent, Slice, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(SortedSlice::new(DATA)).iter(2);
+  let entries = it.map(|ent| (*ent.key(), *ent.value())).collect::<Vec<_>>();
+  assert_eq!(
+    entries,
+    [
+      ("a", Some("a1")),
+      ("b", None),
+      ("c", Some("c1")),
+      ("d", Some("d1"))
+    ]
+  );
+}
+
+#[test]
+fn iter_all_versions() {
+  assert_eq!(
+    collect(all_versions(2)),
+    [("a", 1), ("b", 2), ("b", 1), ("c", 1), ("d", 2)]
+  );
+  assert_eq!(collect(all_versions(5)).len(), DATA.len());
+  assert!(collect(all_versions(0)).is_empty());
+
+  let mut backwards = collect(all_versions(2).rev());
+  backwards.reverse();
+  assert_eq!(backwards, collect(all_versions(2)));
+}
+
+#[test]
+fn iter_raw() {
+  let raw =
+    |version: u64| -> raw::Iter<Ent, Slice> { Builder::new(SortedSlice::new(DATA)).iter(version) };
+
+  for version in 0..=6 {
+    let expected = DATA
+      .iter()
+      .filter(|(_, v, _)| *v <= version)
+      .map(|(k, v, _)| (*k, *v))
+      .collect::<Vec<_>>();
+    assert_eq!(collect(raw(version)), expected);
+
+    let mut backwards = collect(raw(version).rev());
+    backwards.reverse();
+    assert_eq!(backwards, expected);
+  }
+
+  // tombstones and older versions of the same key are yielded as well.
+  let values = raw(2)
+    .map(|ent| (*ent.key(), *ent.value()))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    values,
+    [
+      ("a", Some("a1")),
+      ("b", None),
+      ("b", Some("b1")),
+      ("c", Some("c1")),
+      ("d", Some("d1"))
+    ]
+  );
+
+  let mut it = raw(3);
+  assert_eq!(
+    it.next().map(|ent| (*ent.key(), ent.version())),
+    Some(("a", 3))
+  );
+  assert_eq!(
+    it.next_back().map(|ent| (*ent.key(), ent.version())),
+    Some(("d", 2))
+  );
+  assert_eq!(
+    collect(it),
+    [("a", 1), ("b", 2), ("b", 1), ("c", 3), ("c", 1)]
+  );
+}
+
+#[test]
+fn iter_mixed_directions() {
+  let mut it = latest(5);
+  assert_eq!(it.next().map(|ent| *ent.key()), Some("a"));
+  assert_eq!(it.next_back().map(|ent| *ent.key()), Some("d"));
+  assert_eq!(it.next().map(|ent| *ent.key()), Some("c"));
+  assert!(it.next_back().is_none());
+  assert!(it.next().is_none());
+}
+
+#[test]
+fn range_latest() {
+  // start bound, end bound, expected key and version pairs
+  type Case = (
+    Bound<&'static str>,
+    Bound<&'static str>,
+    &'static [(&'static str, u64)],
+  );
+
+  let cases: [Case; 8] = [
+    (Bound::Included("b"), Bound::Excluded("d"), &[("c", 5)]),
+    (
+      Bound::Included("b"),
+      Bound::Included("d"),
+      &[("c", 5), ("d", 2)],
+    ),
+    (Bound::Excluded("a"), Bound::Included("c"), &[("c", 5)]),
+    (Bound::Unbounded, Bound::Included("b"), &[("a", 3)]),
+    (
+      Bound::Included("bb"),
+      Bound::Unbounded,
+      &[("c", 5), ("d", 2)],
+    ),
+    (Bound::Unbounded, Bound::Excluded("a"), &[]),
+    (Bound::Excluded("d"), Bound::Unbounded, &[]),
+    (Bound::Excluded("a"), Bound::Excluded("c"), &[]),
+  ];
+
+  for (start, end, expected) in cases {
+    assert_eq!(collect(latest_range(5, (start, end))), expected);
+
+    let mut backwards = collect(latest_range(5, (start, end)).rev());
+    backwards.reverse();
+    assert_eq!(backwards, expected);
+  }
+
+  assert_eq!(
+    collect(latest_range(
+      1,
+      (Bound::Excluded("a"), Bound::Included("c"))
+    )),
+    [("b", 1), ("c", 1)]
+  );
+  assert_eq!(collect(latest_range(5, ..)), collect(latest(5)));
+}
+
+#[test]
+fn range_effective_bounds() {
+  let mut it = latest_range(5, ..="c");
+  assert_eq!(*it.query_version(), 5);
+  assert_eq!(
+    it.effective_bounds(),
+    (Bound::Unbounded, Bound::Included(&"c"))
+  );
+  assert!(!it.is_started());
+
+  assert_eq!(it.next().map(|ent| *ent.key()), Some("a"));
+  assert!(it.is_started());
+  assert_eq!(
+    it.effective_bounds(),
+    (Bound::Unbounded, Bound::Included(&"c"))
+  );
+
+  let mut it = latest(5);
+  assert_eq!(*it.query_version(), 5);
+  assert!(!it.is_started());
+  assert!(it.next_back().is_some());
+  assert!(it.is_started());
+}
+
+#[test]
+fn range_owned() {
+  fn owned(
+    start: Bound<&str>,
+    end: Bound<&str>,
+  ) -> dedup::Range<OwnedRange<String>, str, Slice, Ent, Ascend, NoopValidator, Live> {
+    // the bounds are built here and dropped with the range.
+    Builder::new(SortedSlice::new(DATA))
+      .with_value_validator(Live)
+      .range_owned(5, (start.map(String::from), end.map(String::from)))
+  }
+
+  let it = owned(Bound::Included("b"), Bound::Included("d"));
+  assert_eq!(
+    it.range().bounds(),
+    (
+      Bound::Included(&"b".to_string()),
+      Bound::Included(&"d".to_string())
+    )
+  );
+  assert_eq!(collect(it), [("c", 5), ("d", 2)]);
+  assert_eq!(
+    collect(owned(Bound::Excluded("a"), Bound::Excluded("d")).rev()),
+    [("c", 5)]
+  );
+  assert_eq!(
+    collect(owned(Bound::Unbounded, Bound::Unbounded)),
+    collect(latest(5))
+  );
+}
+
+#[test]
+fn range_all_versions() {
+  let it: valid::Range<_, &str, Slice, Ent, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(SortedSlice::new(DATA)).range(3, "b"..="c");
+  assert_eq!(collect(it), [("b", 2), ("b", 1), ("c", 3), ("c", 1)]);
+
+  let it: valid::Range<_, &str, Slice, Ent, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(SortedSlice::new(DATA)).range(3, "b"..="c");
+  assert_eq!(collect(it.rev()), [("c", 1), ("c", 3), ("b", 1), ("b", 2)]);
+}
+
+#[test]
+fn seek_bounds() {
+  let slice = SortedSlice::new(DATA);
+  let lower = |bound: Bound<&&'static str>| {
+    slice
+      .lower_bound(bound)
+      .map(|ent| (*ent.key(), ent.version()))
+  };
+  let upper = |bound: Bound<&&'static str>| {
+    slice
+      .upper_bound(bound)
+      .map(|ent| (*ent.key(), ent.version()))
+  };
+
+  // lower bounds land on the newest version of the first key in the bound.
+  assert_eq!(lower(Bound::Included(&"b")), Some(("b", 2)));
+  assert_eq!(lower(Bound::Included(&"bb")), Some(("c", 5)));
+  assert_eq!(lower(Bound::Included(&"0")), Some(("a", 3)));
+  assert_eq!(lower(Bound::Included(&"e")), None);
+  assert_eq!(lower(Bound::Excluded(&"b")), Some(("c", 5)));
+  assert_eq!(lower(Bound::Excluded(&"d")), None);
+  assert_eq!(lower(Bound::Unbounded), Some(("a", 3)));
+
+  // upper bounds land on the oldest version of the last key in the bound.
+  assert_eq!(upper(Bound::Included(&"b")), Some(("b", 1)));
+  assert_eq!(upper(Bound::Included(&"bb")), Some(("b", 1)));
+  assert_eq!(upper(Bound::Included(&"z")), Some(("d", 2)));
+  assert_eq!(upper(Bound::Included(&"0")), None);
+  assert_eq!(upper(Bound::Excluded(&"b")), Some(("a", 1)));
+  assert_eq!(upper(Bound::Excluded(&"a")), None);
+  assert_eq!(upper(Bound::Unbounded), Some(("d", 2)));
+}
+
+#[test]
+fn cursor() {
+  let slice = SortedSlice::new(DATA);
+
+  let mut forwards = Vec::new();
+  let mut curr = slice.first();
+  while let Some(ent) = curr {
+    forwards.push((*ent.key(), ent.version()));
+    curr = ent.next();
+  }
+
+  let mut backwards = Vec::new();
+  let mut curr = slice.last();
+  while let Some(ent) = curr {
+    backwards.push((*ent.key(), ent.version()));
+    curr = ent.next_back();
+  }
+  backwards.reverse();
+
+  assert_eq!(forwards.len(), DATA.len());
+  assert_eq!(forwards, backwards);
+
+  let c = slice.lower_bound(Bound::Included(&"c")).unwrap();
+  assert_eq!(c.index(), 4);
+  let d = c.skip_to_different_key(&Ascend).unwrap();
+  assert_eq!((*d.key(), d.version()), ("d", 2));
+  assert!(d.skip_to_different_key(&Ascend).is_none());
+}
+
+#[test]
+fn empty() {
+  let slice = SortedSlice::<&str, &str>::new(&[]);
+  assert!(slice.first().is_none());
+  assert!(slice.last().is_none());
+  assert!(slice.lower_bound(Bound::<&&str>::Unbounded).is_none());
+  assert!(slice.upper_bound(Bound::<&&str>::Unbounded).is_none());
+
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> = Builder::new(slice).iter(1);
+  assert_eq!(it.count(), 0);
+}
+
+#[test]
+fn heap_merge() {
+  use std::collections::BinaryHeap;
+
+  // a newer source overlaid on `DATA`.
+  const NEWER: &[(&str, u64, Option<&str>)] = &[
+    ("a", 6, None),
+    ("bb", 6, Some("bb1")),
+    ("c", 4, Some("c4")),
+    ("e", 6, Some("e1")),
+  ];
+
+  let mut heap = BinaryHeap::new();
+  for source in [DATA, NEWER] {
+    let mut curr = SortedSlice::new(source).first();
+    while let Some(ent) = curr {
+      heap.push(HeapEntry::new(ent));
+      curr = ent.next();
+    }
+  }
+
+  let mut merged = Vec::new();
+  while let Some(ent) = heap.pop() {
+    merged.push((*ent.entry().key(), ent.into_entry().version()));
+  }
+  assert_eq!(
+    merged,
+    [
+      ("a", 6),
+      ("a", 3),
+      ("a", 1),
+      ("b", 2),
+      ("b", 1),
+      ("bb", 6),
+      ("c", 5),
+      ("c", 4),
+      ("c", 3),
+      ("c", 1),
+      ("d", 2),
+      ("e", 6)
+    ]
+  );
+}
+
+#[test]
+fn builder_latest() {
+  const WRITES: &[(&str, u64, Option<&str>)] = &[
+    ("a", 3, Some("a3")),
+    ("a", 1, Some("a1")),
+    ("b", 1, Some("b1")),
+    ("c", 3, Some("c3")),
+  ];
+
+  let slice = SortedSlice::new(WRITES);
+  assert_eq!(slice.max_version(), Some(3));
+
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(slice).iter_latest();
+  assert_eq!(collect(it), [("a", 3), ("b", 1), ("c", 3)]);
+
+  let range: dedup::Range<_, &str, _, _, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(slice).range_latest("b"..);
+  assert_eq!(collect(range), [("b", 1), ("c", 3)]);
+
+  let empty = SortedSlice::<&str, &str>::new(&[]);
+  assert_eq!(empty.max_version(), None);
+  let it: dedup::Iter<_, _, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(empty).iter_latest();
+  assert_eq!(it.count(), 0);
+}
+
+#[test]
+fn dedup_sorted_iter_matches_cursor() {
+  use snapshotor::dedup_sorted_iter;
+
+  let entries =
+    core::iter::successors(SortedSlice::new(DATA).first(), |ent| ent.next()).collect::<Vec<Ent>>();
+
+  for version in 0..=6 {
+    let streamed = dedup_sorted_iter(
+      entries.iter().copied(),
+      version,
+      Ascend,
+      NoopValidator,
+      Live,
+    );
+    assert_eq!(
+      collect(streamed),
+      collect(latest(version)),
+      "version {version}"
+    );
+  }
+
+  // with tombstones kept, `b` yields its tombstone at version 2.
+  let streamed = dedup_sorted_iter(entries.into_iter(), 2, Ascend, NoopValidator, NoopValidator);
+  assert_eq!(collect(streamed), [("a", 1), ("b", 2), ("c", 1), ("d", 2)]);
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn dedup_stats() {
+  const VERSIONS: &[(&str, u64, Option<&str>)] = &[
+    ("a", 9, Some("a9")),
+    ("a", 8, None),
+    ("a", 7, Some("a7")),
+    ("a", 6, None),
+    ("a", 5, Some("a5")),
+    ("a", 4, None),
+    ("a", 3, Some("a3")),
+    ("b", 2, Some("b2")),
+    ("c", 9, Some("c9")),
+    ("c", 1, Some("c1")),
+  ];
+
+  let mut it: dedup::Iter<_, _, Ascend, NoopValidator, Live> =
+    Builder::new(SortedSlice::new(VERSIONS))
+      .with_value_validator(Live)
+      .iter(6);
+  assert_eq!(it.stats().visited, 0);
+  assert_eq!(collect(&mut it), [("b", 2), ("c", 1)]);
+
+  let stats = *it.stats();
+  assert_eq!(stats.yielded, 2);
+  // a9, a8, a7 and c9 are newer than the query version.
+  assert_eq!(stats.skipped_version, 4);
+  // the newest visible version of `a` is a tombstone.
+  assert_eq!(stats.skipped_invalid, 1);
+  assert!(stats.visited >= stats.yielded + stats.skipped_version + stats.skipped_invalid);
+}
+
+/// A version newtype, which borrows as the `u64` it wraps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Ts(u64);
+
+impl core::borrow::Borrow<u64> for Ts {
+  fn borrow(&self) -> &u64 {
+    &self.0
+  }
+}
+
+/// A [`SliceEntry`] whose versions are wrapped in [`Ts`].
+#[derive(Debug, Clone, Copy)]
+struct TsEntry(Ent);
+
+impl Entry for TsEntry {
+  type Key = &'static str;
+  type Value = Option<&'static str>;
+  type Version = Ts;
+
+  fn key(&self) -> &Self::Key {
+    self.0.key()
+  }
+
+  fn value(&self) -> &Self::Value {
+    self.0.value()
+  }
+
+  fn version(&self) -> Self::Version {
+    Ts(self.0.version())
+  }
+}
+
+impl Cursor for TsEntry {
+  fn next(&self) -> Option<Self> {
+    self.0.next().map(TsEntry)
+  }
+}
+
+impl DoubleEndedCursor for TsEntry {
+  fn next_back(&self) -> Option<Self> {
+    self.0.next_back().map(TsEntry)
+  }
+}
+
+#[test]
+fn borrowed_query_version() {
+  let slice = SortedSlice::new(DATA);
+  let first = slice.first().unwrap();
+  let last = slice.last().unwrap();
+  let index = |ent: Option<Ent>| ent.map(|ent| ent.index());
+
+  for version in 0..=6u64 {
+    let query: &u64 = &version;
+    let plain = index(first.next_dedup(query, &Ascend, &NoopValidator, &Live));
+    let plain_back = index(last.next_back_dedup(query, &Ascend, &NoopValidator, &Live));
+    let plain_valid = index(first.next_valid::<Ascend, _, _>(query, &NoopValidator, &Live));
+
+    // entries versioned by `Ts` are queried with a `u64`.
+    let (first, last) = (TsEntry(first), TsEntry(last));
+    let ts = |ent: Option<TsEntry>| ent.map(|ent| ent.0.index());
+    assert_eq!(
+      ts(first.next_dedup(query, &Ascend, &NoopValidator, &Live)),
+      plain
+    );
+    assert_eq!(
+      ts(last.next_back_dedup(query, &Ascend, &NoopValidator, &Live)),
+      plain_back
+    );
+    assert_eq!(
+      ts(first.next_valid::<Ascend, _, _>(query, &NoopValidator, &Live)),
+      plain_valid
+    );
+    // and with the newtype itself.
+    assert_eq!(
+      ts(first.next_dedup(&Ts(version), &Ascend, &NoopValidator, &Live)),
+      plain
+    );
+  }
+}
+
+#[test]
+fn range_rev() {
+  type Rev = Descending<
+    dedup::Range<
+      RangeRev<core::ops::RangeInclusive<&'static str>>,
+      &'static str,
+      Slice,
+      Ent,
+      Ascend,
+      NoopValidator,
+      Live,
+    >,
+  >;
+
+  for version in 0..=5 {
+    let rev: Rev = Builder::new(SortedSlice::new(DATA))
+      .with_value_validator(Live)
+      .range_rev(version, "a"..="c");
+    assert!(!rev.inner().range().is_swapped());
+    assert_eq!(
+      collect(rev),
+      collect(latest_range(version, "a"..="c").rev())
+    );
+
+    let rev: Rev = Builder::new(SortedSlice::new(DATA))
+      .with_value_validator(Live)
+      .range_rev(version, "a"..="c");
+    assert_eq!(
+      collect(rev.rev()),
+      collect(latest_range(version, "a"..="c"))
+    );
+  }
+}
+
+#[test]
+fn first_last_valid() {
+  let edge = |version: u64| {
+    let slice = SortedSlice::new(DATA);
+    let first = slice.first_valid(&version, &Ascend, &NoopValidator, &Live);
+    let last = slice.last_valid(&version, &Ascend, &NoopValidator, &Live);
+    let key_version = |ent: Ent| (*ent.key(), ent.version());
+    (first.map(key_version), last.map(key_version))
+  };
+
+  for version in 0..=6 {
+    let all = collect(latest(version));
+    assert_eq!(edge(version), (all.first().copied(), all.last().copied()));
+  }
+
+  // the newest version of `a` is a tombstone, so the first valid entry is `b`.
+  let data: &[(&str, u64, Option<&str>)] = &[
+    ("a", 2, None),
+    ("a", 1, Some("a1")),
+    ("b", 1, Some("b1")),
+    ("c", 3, None),
+  ];
+  let slice = SortedSlice::new(data);
+  let first = slice
+    .first_valid(&2, &Ascend, &NoopValidator, &Live)
+    .unwrap();
+  assert_eq!((*first.key(), first.version()), ("b", 1));
+  let first = slice
+    .first_valid(&1, &Ascend, &NoopValidator, &Live)
+    .unwrap();
+  assert_eq!((*first.key(), first.version()), ("a", 1));
+  let last = slice
+    .last_valid(&3, &Ascend, &NoopValidator, &Live)
+    .unwrap();
+  assert_eq!((*last.key(), last.version()), ("b", 1));
+}
+
+#[test]
+fn range_bounds() {
+  let dedup = latest_range(3, "b".."d");
+  assert_eq!(dedup.bounds(), &("b".."d"));
+  assert_eq!(dedup.bounds(), dedup.range());
+
+  let valid: valid::Range<_, &str, Slice, Ent, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(SortedSlice::new(DATA)).range(3, "a"..="c");
+  assert_eq!(valid.bounds(), &("a"..="c"));
+  assert_eq!(
+    collect(valid),
+    collect(all_versions(3))
+      .into_iter()
+      .filter(|(k, _)| *k <= "c")
+      .collect::<Vec<_>>()
+  );
+}
+
+#[test]
+fn tuple_entries() {
+  // `(key, version, value)` tuples are entries on their own.
+  let data: Vec<(&str, u64, Option<i32>)> = vec![
+    ("a", 3, Some(2)),
+    ("a", 1, Some(1)),
+    ("b", 2, None),
+    ("b", 1, Some(1)),
+    ("c", 5, Some(3)),
+    ("c", 3, Some(2)),
+    ("d", 2, Some(1)),
+  ];
+  let slice = EntrySlice::new(&data);
+  assert_eq!(slice.max_version(), Some(5));
+
+  let visible = |version: u64| -> dedup::Iter<_, _, Ascend, NoopValidator, Live> {
+    Builder::new(slice).with_value_validator(Live).iter(version)
+  };
+  let expected = [("a", 3, Some(2)), ("c", 3, Some(2)), ("d", 2, Some(1))];
+  assert_eq!(
+    visible(3).map(|ent| *ent.get()).collect::<Vec<_>>(),
+    expected
+  );
+
+  let mut backwards = visible(3).rev().map(|ent| *ent.get()).collect::<Vec<_>>();
+  backwards.reverse();
+  assert_eq!(backwards, expected);
+
+  let got = visible(1)
+    .map(|ent| (*ent.key(), ent.version(), *ent.value()))
+    .collect::<Vec<_>>();
+  assert_eq!(got, [("a", 1, Some(1)), ("b", 1, Some(1))]);
+
+  let d = slice.lower_bound(Bound::Included(&"d")).unwrap();
+  assert_eq!(d.index(), 6);
+  assert_eq!(*d.get(), ("d", 2, Some(1)));
+}
+
+// a value is valid only if it is live and was written at or after the boundary version.
+#[derive(Clone, Copy)]
+struct LiveSince(u64);
+
+impl<V> VersionedValidator<u64, Option<V>> for LiveSince {
+  #[inline]
+  fn validate(&self, version: &u64, value: &Option<V>) -> bool {
+    *version >= self.0 && value.is_some()
+  }
+}
+
+#[test]
+fn versioned_value_validator() {
+  let since =
+    |version: u64, boundary: u64| -> dedup::Iter<Ent, Slice, Ascend, NoopValidator, LiveSince> {
+      Builder::new(SortedSlice::new(DATA))
+        .with_versioned_value_validator(LiveSince(boundary))
+        .iter(version)
+    };
+  assert_eq!(collect(since(5, 0)), collect(latest(5)));
+  assert_eq!(collect(since(5, 2)), [("a", 3), ("c", 5), ("d", 2)]);
+  // the newest visible version of `d` is below the boundary, so `d` is skipped.
+  assert_eq!(collect(since(3, 3)), [("a", 3), ("c", 3)]);
+  assert!(collect(since(1, 2)).is_empty());
+
+  let mut backwards = collect(since(3, 3).rev());
+  backwards.reverse();
+  assert_eq!(backwards, [("a", 3), ("c", 3)]);
+
+  let all: valid::Iter<Ent, Slice, Ascend, NoopValidator, LiveSince> =
+    Builder::new(SortedSlice::new(DATA))
+      .with_versioned_value_validator(LiveSince(2))
+      .iter(5);
+  assert_eq!(collect(all), [("a", 3), ("c", 5), ("c", 3), ("d", 2)]);
+}
+
+// `b@2` is duplicated, which never happens with MVCC inputs but may with a buggy one.
+const DUPLICATED: &[(&str, u64, Option<&str>)] = &[
+  ("a", 1, Some("a1")),
+  ("b", 2, Some("b2")),
+  ("b", 2, Some("b2'")),
+  ("b", 1, Some("b1")),
+  ("c", 1, Some("c1")),
+];
+
+#[test]
+fn duplicated_key_version() {
+  let latest = || -> dedup::Iter<Ent, Slice, Ascend, NoopValidator, NoopValidator> {
+    Builder::new(SortedSlice::new(DUPLICATED)).iter(2)
+  };
+  let values = latest().map(|ent| ent.value().unwrap()).collect::<Vec<_>>();
+  assert_eq!(values, ["a1", "b2", "c1"]);
+  // the first copy is selected from the back as well.
+  let mut backwards = latest()
+    .rev()
+    .map(|ent| ent.value().unwrap())
+    .collect::<Vec<_>>();
+  backwards.reverse();
+  assert_eq!(backwards, ["a1", "b2", "c1"]);
+
+  let mut it = latest();
+  assert_eq!(it.next().unwrap().value(), &Some("a1"));
+  assert_eq!(it.next_back().unwrap().value(), &Some("c1"));
+  assert_eq!(it.next().unwrap().value(), &Some("b2"));
+  assert!(it.next_back().is_none());
+  assert!(it.next().is_none());
+
+  let all = || -> valid::Iter<Ent, Slice> { Builder::new(SortedSlice::new(DUPLICATED)).iter(2) };
+  let expected = [("a", 1), ("b", 2), ("b", 1), ("c", 1)];
+  assert_eq!(collect(all()), expected);
+  let mut backwards = collect(all().rev());
+  backwards.reverse();
+  assert_eq!(backwards, expected);
+
+  let mut it = all();
+  let mut yielded = Vec::new();
+  while let Some(ent) = it.next() {
+    yielded.push((*ent.key(), ent.version()));
+    match it.next_back() {
+      Some(ent) => yielded.push((*ent.key(), ent.version())),
+      None => break,
+    }
+  }
+  yielded.sort_by(|a, b| a.0.cmp(b.0).then_with(|| b.1.cmp(&a.1)));
+  assert_eq!(yielded, expected);
+
+  let range: valid::Range<_, &str, Slice, Ent, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(SortedSlice::new(DUPLICATED)).range(2, "b"..="b");
+  assert_eq!(collect(range), [("b", 2), ("b", 1)]);
+  let range: valid::Range<_, &str, Slice, Ent, Ascend, NoopValidator, NoopValidator> =
+    Builder::new(SortedSlice::new(DUPLICATED)).range(2, "b"..="b");
+  assert_eq!(collect(range.rev()), [("b", 1), ("b", 2)]);
+}
+
+#[test]
+fn fused_after_exhaustion() {
+  fn assert_fused<I: core::iter::FusedIterator>(mut it: I, len: usize) {
+    assert_eq!(it.by_ref().take(len + 1).count(), len);
+    for _ in 0..4 {
+      assert!(it.next().is_none());
+    }
+  }
+
+  assert_fused(latest(5), 3);
+  assert_fused(all_versions(5), DATA.len());
+  assert_fused(latest(0), 0);
+  // exhausted by the limit rather than by the underlying structure.
+  let limited: dedup::Iter<Ent, Slice, Ascend, NoopValidator, Live> =
+    Builder::new(SortedSlice::new(DATA))
+      .with_value_validator(Live)
+      .take(1)
+      .iter(5);
+  assert_fused(limited, 1);
+}
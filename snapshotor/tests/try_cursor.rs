@@ -0,0 +1,199 @@
+use core::cell::Cell;
+
+use snapshotor::{
+  equivalentor::Ascend, try_dedup, try_dedup_back, try_valid, try_valid_back, AnyValidator, Entry,
+  NoopValidator, TryCursor, TryDoubleEndedCursor,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Corrupted(usize);
+
+/// A cursor over a sorted slice of `(key, version, value)` entries, which fails to move once
+/// it has moved `fail_after` times.
+#[derive(Debug, Clone)]
+struct Fallible<'a> {
+  data: &'a [(u32, u64, Option<u32>)],
+  idx: usize,
+  steps: &'a Cell<usize>,
+  fail_after: usize,
+}
+
+impl<'a> Fallible<'a> {
+  fn new(
+    data: &'a [(u32, u64, Option<u32>)],
+    idx: usize,
+    steps: &'a Cell<usize>,
+    fail_after: usize,
+  ) -> Self {
+    Self {
+      data,
+      idx,
+      steps,
+      fail_after,
+    }
+  }
+
+  fn step(&self, idx: Option<usize>) -> Result<Option<Self>, Corrupted> {
+    let steps = self.steps.get() + 1;
+    self.steps.set(steps);
+    if steps > self.fail_after {
+      return Err(Corrupted(steps));
+    }
+
+    Ok(idx.filter(|idx| *idx < self.data.len()).map(|idx| Self {
+      idx,
+      ..self.clone()
+    }))
+  }
+}
+
+impl Entry for Fallible<'_> {
+  type Key = u32;
+  type Value = Option<u32>;
+  type Version = u64;
+
+  fn key(&self) -> &Self::Key {
+    &self.data[self.idx].0
+  }
+
+  fn value(&self) -> &Self::Value {
+    &self.data[self.idx].2
+  }
+
+  fn version(&self) -> Self::Version {
+    self.data[self.idx].1
+  }
+}
+
+impl TryCursor for Fallible<'_> {
+  type Error = Corrupted;
+
+  fn try_next(&self) -> Result<Option<Self>, Self::Error> {
+    self.step(Some(self.idx + 1))
+  }
+}
+
+impl TryDoubleEndedCursor for Fallible<'_> {
+  fn try_next_back(&self) -> Result<Option<Self>, Self::Error> {
+    self.step(self.idx.checked_sub(1))
+  }
+}
+
+// keys are sorted ascending, versions descending for the same key, `None` is a tombstone.
+const DATA: &[(u32, u64, Option<u32>)] = &[
+  (0, 2, Some(2)),
+  (0, 1, Some(1)),
+  (1, 3, None),
+  (1, 1, Some(1)),
+  (2, 2, Some(2)),
+  (3, 1, Some(1)),
+];
+
+fn live(value: &Option<u32>) -> bool {
+  value.is_some()
+}
+
+fn key_version(ent: Option<Fallible<'_>>) -> Option<(u32, u64)> {
+  ent.map(|ent| (*ent.key(), ent.version()))
+}
+
+#[test]
+fn valid_error_surfaces() {
+  let steps = Cell::new(0);
+  // entries before key 3 are rejected, so the cursor has to move 5 times.
+  let reject = AnyValidator(|key: &u32| *key >= 3);
+
+  let start = Fallible::new(DATA, 0, &steps, 2);
+  let err = try_valid(Some(start), &3, &reject, &NoopValidator).unwrap_err();
+  assert_eq!(err, Corrupted(3));
+
+  steps.set(0);
+  let start = Fallible::new(DATA, 0, &steps, usize::MAX);
+  let found = try_valid(Some(start), &3, &reject, &NoopValidator).unwrap();
+  assert_eq!(key_version(found), Some((3, 1)));
+  assert_eq!(steps.get(), 5);
+}
+
+#[test]
+fn valid() {
+  let steps = Cell::new(0);
+  let start = Fallible::new(DATA, 0, &steps, usize::MAX);
+
+  let mut found = Vec::new();
+  let mut curr = try_valid(Some(start), &1, &NoopValidator, &NoopValidator).unwrap();
+  while let Some(ent) = curr {
+    found.push((*ent.key(), ent.version()));
+    curr = try_valid(ent.try_next().unwrap(), &1, &NoopValidator, &NoopValidator).unwrap();
+  }
+  assert_eq!(found, [(0, 1), (1, 1), (3, 1)]);
+
+  let last = Fallible::new(DATA, DATA.len() - 1, &steps, usize::MAX);
+  let mut found = Vec::new();
+  let mut curr = try_valid_back(Some(last), &1, &NoopValidator, &NoopValidator).unwrap();
+  while let Some(ent) = curr {
+    found.push((*ent.key(), ent.version()));
+    curr = try_valid_back(
+      ent.try_next_back().unwrap(),
+      &1,
+      &NoopValidator,
+      &NoopValidator,
+    )
+    .unwrap();
+  }
+  assert_eq!(found, [(3, 1), (1, 1), (0, 1)]);
+}
+
+#[test]
+fn dedup() {
+  let steps = Cell::new(0);
+  let live = AnyValidator(live);
+
+  let start = Fallible::new(DATA, 0, &steps, usize::MAX);
+  let mut found = Vec::new();
+  let mut curr = try_dedup(Some(start), &3, &Ascend, &NoopValidator, &live).unwrap();
+  while let Some(ent) = curr {
+    found.push((*ent.key(), ent.version()));
+    curr = try_dedup(
+      ent.try_skip_to_different_key(&Ascend).unwrap(),
+      &3,
+      &Ascend,
+      &NoopValidator,
+      &live,
+    )
+    .unwrap();
+  }
+  // the tombstone of key 1 hides its older version.
+  assert_eq!(found, [(0, 2), (2, 2), (3, 1)]);
+
+  let last = Fallible::new(DATA, DATA.len() - 1, &steps, usize::MAX);
+  let mut found = Vec::new();
+  let mut curr = try_dedup_back(Some(last), &3, &Ascend, &NoopValidator, &live).unwrap();
+  while let Some(ent) = curr {
+    found.push((*ent.key(), ent.version()));
+    curr = try_dedup_back(
+      ent.try_next_back().unwrap(),
+      &3,
+      &Ascend,
+      &NoopValidator,
+      &live,
+    )
+    .unwrap();
+  }
+  assert_eq!(found, [(3, 1), (2, 2), (0, 2)]);
+}
+
+#[test]
+fn dedup_error_surfaces() {
+  let steps = Cell::new(0);
+  let live = AnyValidator(live);
+
+  // skipping the tombstone of key 1 moves the cursor past the failing step.
+  let start = Fallible::new(DATA, 2, &steps, 1);
+  let err = try_dedup(Some(start), &3, &Ascend, &NoopValidator, &live).unwrap_err();
+  assert_eq!(err, Corrupted(2));
+
+  steps.set(0);
+  let last = Fallible::new(DATA, 3, &steps, 2);
+  let err = try_dedup_back(Some(last), &3, &Ascend, &NoopValidator, &live).unwrap_err();
+  assert_eq!(err, Corrupted(3));
+}
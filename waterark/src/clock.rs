@@ -0,0 +1,100 @@
+use core::time::Duration;
+use std::{
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+  },
+  time::Instant,
+};
+
+/// A source of time, so time-based behavior can be driven deterministically in tests.
+///
+/// See [`Closer::wait_timeout_with`](crate::closer::sync::Closer::wait_timeout_with).
+pub trait Clock {
+  /// Returns the current instant.
+  fn now(&self) -> Instant;
+
+  /// Blocks the current thread for the given duration, as measured by this clock.
+  fn sleep(&self, dur: Duration);
+
+  /// Returns `true` if this clock follows the real time of [`Instant::now`], so a timed wait
+  /// can block until its deadline instead of sleeping on the clock in short steps.
+  #[inline]
+  fn is_real_time(&self) -> bool {
+    false
+  }
+}
+
+/// The real clock, backed by [`Instant::now`] and [`std::thread::sleep`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  #[inline]
+  fn now(&self) -> Instant {
+    Instant::now()
+  }
+
+  #[inline]
+  fn sleep(&self, dur: Duration) {
+    std::thread::sleep(dur);
+  }
+
+  #[inline]
+  fn is_real_time(&self) -> bool {
+    true
+  }
+}
+
+/// A clock which only moves when it is advanced, for deterministic tests.
+///
+/// Sleeping on a `ManualClock` advances it by the slept duration and returns immediately.
+/// Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+  origin: Instant,
+  elapsed_nanos: Arc<AtomicU64>,
+}
+
+impl Default for ManualClock {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl ManualClock {
+  /// Creates a new clock, starting at the current instant.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      origin: Instant::now(),
+      elapsed_nanos: Arc::new(AtomicU64::new(0)),
+    }
+  }
+
+  /// Moves the clock forward by the given duration.
+  #[inline]
+  pub fn advance(&self, dur: Duration) {
+    let nanos = u64::try_from(dur.as_nanos()).unwrap_or(u64::MAX);
+    self.elapsed_nanos.fetch_add(nanos, Ordering::AcqRel);
+  }
+
+  /// Returns the time elapsed since the clock was created.
+  #[inline]
+  pub fn elapsed(&self) -> Duration {
+    Duration::from_nanos(self.elapsed_nanos.load(Ordering::Acquire))
+  }
+}
+
+impl Clock for ManualClock {
+  #[inline]
+  fn now(&self) -> Instant {
+    self.origin + self.elapsed()
+  }
+
+  #[inline]
+  fn sleep(&self, dur: Duration) {
+    self.advance(dur);
+  }
+}
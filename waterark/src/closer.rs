@@ -1,3 +1,5 @@
+mod reason;
+
 /// Synchronous closer implementation.
 #[cfg(feature = "sync")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sync")))]
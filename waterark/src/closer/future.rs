@@ -7,6 +7,8 @@ use event_listener::{Event, Listener};
 
 use crate::AsyncSpawner;
 
+use super::reason::ReasonCell;
+
 #[derive(Debug)]
 struct Canceler {
   tx: Sender<()>,
@@ -46,13 +48,16 @@ impl CancelContext {
 /// AsyncCloser holds the two things we need to close a thread and wait for it to
 /// finish: a chan to tell the thread to shut down, and a WaitGroup with
 /// which to wait for it to finish shutting down.
+///
+/// The closer can optionally carry a reason of type `R` (e.g. graceful stop vs a
+/// fatal error) from [`AsyncCloser::signal_with`] to the waiters.
 #[derive(Debug)]
-pub struct AsyncCloser<S> {
-  inner: Arc<AsyncCloserInner>,
+pub struct AsyncCloser<S, R = ()> {
+  inner: Arc<AsyncCloserInner<R>>,
   _spawner: core::marker::PhantomData<S>,
 }
 
-impl<S> Clone for AsyncCloser<S> {
+impl<S, R> Clone for AsyncCloser<S, R> {
   fn clone(&self) -> Self {
     Self {
       inner: self.inner.clone(),
@@ -62,25 +67,15 @@ impl<S> Clone for AsyncCloser<S> {
 }
 
 #[derive(Debug)]
-struct AsyncCloserInner {
+struct AsyncCloserInner<R> {
   waitings: AtomicUsize,
   event: Event,
   ctx: CancelContext,
   cancel: Canceler,
+  reason: ReasonCell<R>,
 }
 
-impl AsyncCloserInner {
-  #[inline]
-  fn new() -> Self {
-    let (ctx, cancel) = CancelContext::new();
-    Self {
-      waitings: AtomicUsize::new(0),
-      event: Event::new(),
-      ctx,
-      cancel,
-    }
-  }
-
+impl<R> AsyncCloserInner<R> {
   #[inline]
   fn with(initial: usize) -> Self {
     let (ctx, cancel) = CancelContext::new();
@@ -89,16 +84,14 @@ impl AsyncCloserInner {
       event: Event::new(),
       ctx,
       cancel,
+      reason: ReasonCell::new(),
     }
   }
 }
 
 impl<S> Default for AsyncCloser<S> {
   fn default() -> Self {
-    Self {
-      inner: Arc::new(AsyncCloserInner::new()),
-      _spawner: core::marker::PhantomData,
-    }
+    Self::new(0)
   }
 }
 
@@ -106,6 +99,15 @@ impl<S> AsyncCloser<S> {
   /// Constructs a new [`AsyncCloser`], with an initial count on the closer.
   #[inline]
   pub fn new(initial: usize) -> Self {
+    Self::with_initial(initial)
+  }
+}
+
+impl<S, R> AsyncCloser<S, R> {
+  /// Constructs a new [`AsyncCloser`] carrying a shutdown reason of type `R`, with an
+  /// initial count on the closer.
+  #[inline]
+  pub fn with_initial(initial: usize) -> Self {
     Self {
       inner: Arc::new(AsyncCloserInner::with(initial)),
       _spawner: core::marker::PhantomData,
@@ -137,10 +139,22 @@ impl<S> AsyncCloser<S> {
     }
   }
 
-  /// Signals the [`AsyncCloser::listen`] signal.
+  /// Signals the [`AsyncCloser::listen`] signal without a reason.
   #[inline]
   pub fn signal(&self) {
-    self.inner.cancel.cancel();
+    if self.inner.reason.set(None) {
+      self.inner.cancel.cancel();
+    }
+  }
+
+  /// Signals the [`AsyncCloser::listen`] signal with the given reason.
+  ///
+  /// Only the first signal is recorded; later signals, with or without a reason, are ignored.
+  #[inline]
+  pub fn signal_with(&self, reason: R) {
+    if self.inner.reason.set(Some(reason)) {
+      self.inner.cancel.cancel();
+    }
   }
 
   /// Waits on the closer. (It waits for the AsyncCloser's initial value, [`AsyncCloser::add_running`], and [`AsyncCloser::done`]
@@ -171,7 +185,24 @@ impl<S> AsyncCloser<S> {
   }
 }
 
-impl<S: AsyncSpawner> AsyncCloser<S> {
+impl<S, R: Clone> AsyncCloser<S, R> {
+  /// Returns the reason passed to [`AsyncCloser::signal_with`].
+  ///
+  /// Returns `None` if the closer has not been signaled yet, or was signaled by [`AsyncCloser::signal`].
+  #[inline]
+  pub fn reason(&self) -> Option<R> {
+    self.inner.reason.get()
+  }
+
+  /// Waits until the closer is signaled, then returns the reason it was signaled with.
+  #[inline]
+  pub async fn wait_signal(&self) -> Option<R> {
+    self.listen().wait().await;
+    self.reason()
+  }
+}
+
+impl<S: AsyncSpawner, R: Send + Sync + 'static> AsyncCloser<S, R> {
   /// Waits on the closer. (It waits for the AsyncCloser's initial value, [`AsyncCloser::add_running`], and [`AsyncCloser::done`]
   /// calls to balance out.)
   #[inline]
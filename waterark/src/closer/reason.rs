@@ -0,0 +1,65 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use atomic_refcell::AtomicRefCell;
+
+const EMPTY: u8 = 0;
+const WRITING: u8 = 1;
+const READY: u8 = 2;
+
+/// A write-once slot holding the reason a closer was signaled.
+///
+/// Only the first signal records its reason, and readers only borrow the cell
+/// once the writer has published it, so the borrows never conflict.
+pub(super) struct ReasonCell<R> {
+  state: AtomicU8,
+  reason: AtomicRefCell<Option<R>>,
+}
+
+impl<R> core::fmt::Debug for ReasonCell<R> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("ReasonCell")
+      .field("signaled", &(self.state.load(Ordering::Acquire) == READY))
+      .finish()
+  }
+}
+
+impl<R> ReasonCell<R> {
+  #[inline]
+  pub(super) const fn new() -> Self {
+    Self {
+      state: AtomicU8::new(EMPTY),
+      reason: AtomicRefCell::new(None),
+    }
+  }
+
+  /// Records the reason if no signal has been recorded yet.
+  ///
+  /// Returns `true` if this call recorded the reason; only that caller should
+  /// then wake the listeners, so they never observe a half-written reason.
+  #[inline]
+  pub(super) fn set(&self, reason: Option<R>) -> bool {
+    if self
+      .state
+      .compare_exchange(EMPTY, WRITING, Ordering::AcqRel, Ordering::Acquire)
+      .is_err()
+    {
+      return false;
+    }
+
+    *self.reason.borrow_mut() = reason;
+    self.state.store(READY, Ordering::Release);
+    true
+  }
+
+  #[inline]
+  pub(super) fn get(&self) -> Option<R>
+  where
+    R: Clone,
+  {
+    if self.state.load(Ordering::Acquire) != READY {
+      return None;
+    }
+
+    self.reason.borrow().clone()
+  }
+}
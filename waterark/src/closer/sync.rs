@@ -5,8 +5,15 @@ use std::sync::{
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 pub use crossbeam_channel::{RecvError, TryRecvError};
+use event_listener::{Event, Listener};
 use wg::WaitGroup;
 
+use super::reason::ReasonCell;
+use crate::clock::{Clock, SystemClock};
+
+/// The longest a timed wait sleeps between two checks of the [`WaitGroup`].
+const WAIT_POLL_INTERVAL: core::time::Duration = core::time::Duration::from_millis(1);
+
 #[derive(Debug)]
 struct Canceler {
   tx: AtomicPtr<()>,
@@ -63,46 +70,51 @@ impl CancelContext {
 /// Closer holds the two things we need to close a thread and wait for it to
 /// finish: a chan to tell the thread to shut down, and a WaitGroup with
 /// which to wait for it to finish shutting down.
-#[derive(Debug, Clone)]
+///
+/// The closer can optionally carry a reason of type `R` (e.g. graceful stop vs a
+/// fatal error) from [`Closer::signal_with`] to the waiters.
+#[derive(Debug)]
 #[repr(transparent)]
-pub struct Closer {
-  inner: Arc<CloserInner>,
+pub struct Closer<R = ()> {
+  inner: Arc<CloserInner<R>>,
+}
+
+impl<R> Clone for Closer<R> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+    }
+  }
 }
 
 #[derive(Debug)]
-struct CloserInner {
+struct CloserInner<R> {
   wg: WaitGroup,
+  /// Notified each time the [`WaitGroup`] balances out, for timed waits.
+  idle: Event,
   ctx: CancelContext,
   cancel: Canceler,
+  reason: ReasonCell<R>,
 }
 
-impl CloserInner {
-  #[inline]
-  fn new() -> Self {
-    let (ctx, cancel) = CancelContext::new();
-    Self {
-      wg: WaitGroup::new(),
-      ctx,
-      cancel,
-    }
-  }
-
+impl<R> CloserInner<R> {
   #[inline]
   fn with(initial: usize) -> Self {
     let (ctx, cancel) = CancelContext::new();
     Self {
       wg: WaitGroup::from(initial),
+      idle: Event::new(),
       ctx,
       cancel,
+      reason: ReasonCell::new(),
     }
   }
 }
 
 impl Default for Closer {
   fn default() -> Self {
-    Self {
-      inner: Arc::new(CloserInner::new()),
-    }
+    Self::new(0)
   }
 }
 
@@ -110,6 +122,15 @@ impl Closer {
   /// Constructs a new [`Closer`], with an initial count on the [`WaitGroup`].
   #[inline]
   pub fn new(initial: usize) -> Self {
+    Self::with_initial(initial)
+  }
+}
+
+impl<R> Closer<R> {
+  /// Constructs a new [`Closer`] carrying a shutdown reason of type `R`, with an initial
+  /// count on the [`WaitGroup`].
+  #[inline]
+  pub fn with_initial(initial: usize) -> Self {
     Self {
       inner: Arc::new(CloserInner::with(initial)),
     }
@@ -124,13 +145,27 @@ impl Closer {
   /// Calls [`WaitGroup::done`] on the [`WaitGroup`].
   #[inline]
   pub fn done(&self) {
-    self.inner.wg.done();
+    if self.inner.wg.done() == 0 {
+      self.inner.idle.notify(usize::MAX);
+    }
   }
 
-  /// Signals cancel to the closer.
+  /// Signals cancel to the closer without a reason.
   #[inline]
   pub fn signal(&self) {
-    self.inner.cancel.cancel();
+    if self.inner.reason.set(None) {
+      self.inner.cancel.cancel();
+    }
+  }
+
+  /// Signals cancel to the closer with the given reason.
+  ///
+  /// Only the first signal is recorded; later signals, with or without a reason, are ignored.
+  #[inline]
+  pub fn signal_with(&self, reason: R) {
+    if self.inner.reason.set(Some(reason)) {
+      self.inner.cancel.cancel();
+    }
   }
 
   /// Waits on the [`WaitGroup`]. (It waits for the Closer's initial value, [`Closer::add_running`], and [`Closer::done`]
@@ -140,6 +175,58 @@ impl Closer {
     self.inner.wg.wait();
   }
 
+  /// Waits on the [`WaitGroup`] for at most `timeout`.
+  ///
+  /// Returns `true` if the [`WaitGroup`] balanced out before the timeout.
+  #[inline]
+  pub fn wait_timeout(&self, timeout: core::time::Duration) -> bool {
+    self.wait_timeout_with(timeout, &SystemClock)
+  }
+
+  /// Works like [`Closer::wait_timeout`], but measures the timeout with the given clock.
+  ///
+  /// A [real time](Clock::is_real_time) clock blocks until the [`WaitGroup`] balances out or
+  /// the deadline passes. Any other clock is slept on in short intervals, checking the
+  /// [`WaitGroup`] in between, so a [`ManualClock`](crate::clock::ManualClock) drives the wait
+  /// to expiry without a real delay.
+  pub fn wait_timeout_with<C: Clock>(&self, timeout: core::time::Duration, clock: &C) -> bool {
+    let deadline = clock.now() + timeout;
+    if clock.is_real_time() {
+      return self.wait_deadline(deadline);
+    }
+
+    loop {
+      if self.inner.wg.waitings() == 0 {
+        return true;
+      }
+
+      let now = clock.now();
+      if now >= deadline {
+        return false;
+      }
+
+      clock.sleep((deadline - now).min(WAIT_POLL_INTERVAL));
+    }
+  }
+
+  fn wait_deadline(&self, deadline: std::time::Instant) -> bool {
+    loop {
+      if self.inner.wg.waitings() == 0 {
+        return true;
+      }
+
+      // register before checking again, so a `done` in between is not missed.
+      let listener = self.inner.idle.listen();
+      if self.inner.wg.waitings() == 0 {
+        return true;
+      }
+
+      if listener.wait_deadline(deadline).is_none() {
+        return self.inner.wg.waitings() == 0;
+      }
+    }
+  }
+
   /// Calls [`Closer::signal`], then [`Closer::wait`].
   #[inline]
   pub fn signal_and_wait(&self) {
@@ -152,3 +239,20 @@ impl Closer {
     self.inner.ctx.done()
   }
 }
+
+impl<R: Clone> Closer<R> {
+  /// Returns the reason passed to [`Closer::signal_with`].
+  ///
+  /// Returns `None` if the closer has not been signaled yet, or was signaled by [`Closer::signal`].
+  #[inline]
+  pub fn reason(&self) -> Option<R> {
+    self.inner.reason.get()
+  }
+
+  /// Blocks until the closer is signaled, then returns the reason it was signaled with.
+  #[inline]
+  pub fn wait_signal(&self) -> Option<R> {
+    let _ = self.listen().recv();
+    self.reason()
+  }
+}
@@ -19,6 +19,11 @@ extern crate std;
 #[cfg(any(feature = "future", feature = "sync"))]
 pub mod closer;
 
+/// Clocks for time-based behavior, which can be replaced in tests.
+#[cfg(feature = "sync")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sync")))]
+pub mod clock;
+
 mod watermark;
 pub use watermark::WaterMarkError;
 
@@ -16,6 +16,8 @@ pub enum WaterMarkError {
   Canceled,
   /// The channel is closed.
   ChannelClosed,
+  /// The pending set of the watermark is full.
+  TooManyPending,
 }
 
 impl core::fmt::Display for WaterMarkError {
@@ -27,6 +29,7 @@ impl core::fmt::Display for WaterMarkError {
       ),
       Self::Canceled => write!(f, "watermark: canceled"),
       Self::ChannelClosed => write!(f, "watermark: channel closed"),
+      Self::TooManyPending => write!(f, "watermark: too many pending indices"),
     }
   }
 }
@@ -50,4 +53,9 @@ fn test_error() {
     std::format!("{}", WaterMarkError::ChannelClosed),
     "watermark: channel closed"
   );
+
+  assert_eq!(
+    std::format!("{}", WaterMarkError::TooManyPending),
+    "watermark: too many pending indices"
+  );
 }
@@ -42,12 +42,21 @@ struct Inner<S> {
   name: Cow<'static, str>,
   mark_tx: Sender<Mark>,
   mark_rx: Receiver<Mark>,
+  #[cfg(feature = "stream")]
+  advanced: event_listener::Event,
+  #[cfg(feature = "stream")]
+  closed: core::sync::atomic::AtomicBool,
   _spawner: core::marker::PhantomData<S>,
 }
 
 impl<S: AsyncSpawner> Inner<S> {
-  async fn process(&self, closer: AsyncCloser<S>) {
+  async fn process(&self, closer: AsyncCloser<S>, stop: oneshot::Receiver<()>) {
     scopeguard::defer!(closer.done(););
+    #[cfg(feature = "stream")]
+    scopeguard::defer!({
+      self.closed.store(true, Ordering::Release);
+      self.advanced.notify(usize::MAX);
+    });
 
     let mut indices: BinaryHeap<Reverse<u64>> = BinaryHeap::new();
     // pending maps raft proposal index to the number of pending mutations for this proposal.
@@ -107,8 +116,12 @@ impl<S: AsyncSpawner> Inner<S> {
             .compare_exchange(done_until, until, Ordering::SeqCst, Ordering::Acquire),
           Ok(done_until)
         );
+        #[cfg(feature = "stream")]
+        self.advanced.notify(usize::MAX);
       }
 
+      // Wake every waiter in (done_until, until], so a single jump of the mark
+      // releases all of the satisfied waiters at once.
       if until - done_until <= waiters.len() as u64 {
         // Close channel and remove from waiters.
         (done_until + 1..=until).for_each(|idx| {
@@ -121,9 +134,12 @@ impl<S: AsyncSpawner> Inner<S> {
     };
 
     let closer = closer.listen();
+    let mut stop = stop.fuse();
     loop {
       futures_util::select_biased! {
         _ = closer.wait().fuse() => return,
+        // the watermark has been dropped.
+        _ = stop => return,
         mark = self.mark_rx.recv().fuse() => match mark {
           Ok(mark) => {
             if let Some(wait_tx) = mark.waiter {
@@ -164,10 +180,17 @@ impl<S: AsyncSpawner> Inner<S> {
 ///
 /// Since `done_until` and `last_index` addresses are passed to sync/atomic packages, we ensure that they
 /// are 64-bit aligned by putting them at the beginning of the structure.
+///
+/// Dropping an initialized watermark stops its background task, without blocking. The
+/// [`AsyncCloser`] passed to [`init`](AsyncWaterMark::init) is not signaled, as it may be shared
+/// with other workers, but the task calls [`AsyncCloser::done`] on exit as usual.
 #[derive(Debug)]
 pub struct AsyncWaterMark<S: AsyncSpawner> {
   inner: Arc<Inner<S>>,
   initialized: bool,
+  stop: Option<oneshot::Sender<()>>,
+  /// Closed once the background task has exited.
+  exited: Option<Receiver<()>>,
 }
 
 impl<S: AsyncSpawner> AsyncWaterMark<S> {
@@ -184,9 +207,15 @@ impl<S: AsyncSpawner> AsyncWaterMark<S> {
         name,
         mark_tx,
         mark_rx,
+        #[cfg(feature = "stream")]
+        advanced: event_listener::Event::new(),
+        #[cfg(feature = "stream")]
+        closed: core::sync::atomic::AtomicBool::new(false),
         _spawner: core::marker::PhantomData,
       }),
       initialized: false,
+      stop: None,
+      exited: None,
     }
   }
 
@@ -206,8 +235,15 @@ impl<S: AsyncSpawner> AsyncWaterMark<S> {
     let inner = self.inner.clone();
     self.initialized = true;
 
+    // never sent to, the task stops when the sender is dropped with the watermark.
+    let (stop_tx, stop_rx) = oneshot::channel();
+    self.stop = Some(stop_tx);
+    // never sent to, the task drops the sender when it exits.
+    let (exited_tx, exited_rx) = async_channel::bounded::<()>(1);
+    self.exited = Some(exited_rx);
     S::spawn_detach(async move {
-      inner.process(closer).await;
+      let _exited = exited_tx;
+      inner.process(closer, stop_rx).await;
     });
   }
 
@@ -296,9 +332,11 @@ impl<S: AsyncSpawner> AsyncWaterMark<S> {
   /// less than or equal to it are done.
   #[inline]
   pub fn set_done_util(&self, val: u64) -> Result<()> {
-    self
-      .check()
-      .map(|_| self.inner.done_until.store(val, Ordering::SeqCst))
+    self.check().map(|_| {
+      self.inner.done_until.store(val, Ordering::SeqCst);
+      #[cfg(feature = "stream")]
+      self.inner.advanced.notify(usize::MAX);
+    })
   }
 
   /// Returns the last index for which `begin` has been called.
@@ -310,11 +348,19 @@ impl<S: AsyncSpawner> AsyncWaterMark<S> {
   }
 
   /// Waits until the given index is marked as done.
+  ///
+  /// When the mark advances past several waiting indices at once (e.g. a single `done`
+  /// completes the lowest pending index while higher ones are already done), every
+  /// waiter whose index is now satisfied is woken, not just the lowest one.
+  ///
+  /// Returns [`WaterMarkError::Canceled`] if the background task has exited, e.g. because
+  /// the [`AsyncCloser`] was signaled, before the index is done.
   #[inline]
   pub async fn wait_for_mark(&self, index: u64) -> Result<()> {
     if self.inner.done_until.load(Ordering::SeqCst) >= index {
       return Ok(());
     }
+    self.check()?;
 
     let (wait_tx, wait_rx) = oneshot::channel();
     self
@@ -327,8 +373,62 @@ impl<S: AsyncSpawner> AsyncWaterMark<S> {
       })
       .unwrap(); // we hold both rx and tx, so cannot fail?
 
-    let _ = wait_rx.await;
-    Ok(())
+    // the mark stays in the channel once the task has exited, so also wait for the exit.
+    let exited = self
+      .exited
+      .as_ref()
+      .expect("initialized watermark has a task");
+    futures_util::select_biased! {
+      _ = wait_rx.fuse() => {},
+      _ = exited.recv().fuse() => {},
+    }
+
+    if self.inner.done_until.load(Ordering::SeqCst) >= index {
+      Ok(())
+    } else {
+      Err(WaterMarkError::Canceled)
+    }
+  }
+
+  /// Waits until every index begun before this call is done, i.e. until
+  /// [`done_until`](AsyncWaterMark::done_until) reaches the [`last_index`](AsyncWaterMark::last_index)
+  /// observed when this method is called.
+  ///
+  /// Indices begun after the call are not waited for. Returns [`WaterMarkError::Canceled`]
+  /// if the watermark is closed by its [`AsyncCloser`] before all of the indices are done.
+  #[inline]
+  pub async fn wait_for_all(&self) -> Result<()> {
+    self.check()?;
+    let last_index = self.inner.last_index.load(Ordering::SeqCst);
+    self.wait_for_mark(last_index).await
+  }
+
+  /// Returns a stream which yields the new [`done_until`](AsyncWaterMark::done_until) each
+  /// time the mark advances after this call.
+  ///
+  /// Advances are coalesced: a consumer that falls behind observes only the latest mark, not
+  /// every intermediate one. The stream ends once the watermark's background task exits.
+  #[cfg(feature = "stream")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+  pub fn updates(&self) -> impl futures_util::Stream<Item = u64> {
+    let inner = self.inner.clone();
+    let last = inner.done_until.load(Ordering::SeqCst);
+    futures_util::stream::unfold((inner, last), |(inner, last)| async move {
+      loop {
+        // register before loading, so an advance between the load and the await is not lost
+        let listener = inner.advanced.listen();
+        let current = inner.done_until.load(Ordering::SeqCst);
+        if current != last {
+          return Some((current, (inner, current)));
+        }
+
+        if inner.closed.load(Ordering::Acquire) {
+          return None;
+        }
+
+        listener.await;
+      }
+    })
   }
 
   #[inline]
@@ -341,6 +441,13 @@ impl<S: AsyncSpawner> AsyncWaterMark<S> {
   }
 }
 
+impl<S: AsyncSpawner> Drop for AsyncWaterMark<S> {
+  fn drop(&mut self) {
+    // dropping the sender resolves the receiver of the background task.
+    self.stop.take();
+  }
+}
+
 #[cfg(test)]
 #[allow(clippy::needless_return)]
 mod tests {
@@ -421,6 +528,50 @@ mod tests {
     .await;
   }
 
+  #[tokio::test]
+  async fn test_wait_for_all() {
+    use core::time::Duration;
+
+    init_and_close::<crate::TokioSpawner, _, _>(|watermark| async move {
+      watermark.begin(1).unwrap();
+      watermark.begin(2).unwrap();
+      watermark.begin(3).unwrap();
+
+      let wait = watermark.wait_for_all();
+      tokio::pin!(wait);
+
+      for idx in 1..=2 {
+        watermark.done(idx).unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(50), &mut wait)
+          .await
+          .is_err());
+      }
+
+      watermark.done(3).unwrap();
+      wait.await.unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 3);
+    })
+    .await;
+  }
+
+  #[tokio::test]
+  async fn test_wait_for_all_canceled() {
+    let closer = AsyncCloser::<crate::TokioSpawner>::new(1);
+    let mut watermark = AsyncWaterMark::new("watermark".into());
+    watermark.init(closer.clone());
+    closer.signal_and_wait().await;
+
+    watermark.begin(1).unwrap();
+    assert_eq!(
+      watermark.wait_for_all().await,
+      Err(WaterMarkError::Canceled)
+    );
+    assert_eq!(
+      watermark.wait_for_mark(1).await,
+      Err(WaterMarkError::Canceled)
+    );
+  }
+
   #[tokio::test]
   async fn test_multiple_singles() {
     let closer = AsyncCloser::<crate::TokioSpawner>::default();
@@ -471,4 +622,114 @@ mod tests {
         .unwrap();
     }
   }
+
+  #[tokio::test]
+  async fn test_closer_reason() {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Reason {
+      Graceful,
+      Fatal(&'static str),
+    }
+
+    let c = AsyncCloser::<crate::TokioSpawner, Reason>::with_initial(0);
+    assert_eq!(c.reason(), None);
+
+    let handles = (0..4)
+      .map(|_| {
+        let c = c.clone();
+        tokio::spawn(async move { c.wait_signal().await })
+      })
+      .collect::<Vec<_>>();
+
+    c.signal_with(Reason::Fatal("disk full"));
+    // only the first signal is recorded
+    c.signal_with(Reason::Graceful);
+    c.signal();
+
+    for h in handles {
+      assert_eq!(h.await.unwrap(), Some(Reason::Fatal("disk full")));
+    }
+    assert_eq!(c.reason(), Some(Reason::Fatal("disk full")));
+  }
+
+  #[cfg(feature = "stream")]
+  #[tokio::test]
+  async fn test_updates() {
+    use core::time::Duration;
+    use futures_util::StreamExt;
+
+    init_and_close::<crate::TokioSpawner, _, _>(|watermark| async move {
+      let updates = watermark.updates();
+      tokio::pin!(updates);
+
+      watermark.begin_many((1..=10).collect()).unwrap();
+      for idx in 1..=10 {
+        watermark.done(idx).unwrap();
+      }
+
+      let mut last = 0;
+      while last < 10 {
+        let mark = tokio::time::timeout(Duration::from_secs(1), updates.next())
+          .await
+          .unwrap()
+          .unwrap();
+        assert!(mark > last, "mark went from {last} to {mark}");
+        last = mark;
+      }
+      assert_eq!(watermark.done_until().unwrap(), 10);
+    })
+    .await;
+  }
+
+  #[tokio::test]
+  async fn test_wait_for_mark_wakes_all_satisfied() {
+    use core::time::Duration;
+
+    init_and_close::<crate::TokioSpawner, _, _>(|watermark| async move {
+      watermark
+        .begin_many([5, 6, 7, 8].into_iter().collect())
+        .unwrap();
+      watermark
+        .done_many([6, 7, 8].into_iter().collect())
+        .unwrap();
+
+      let all = async {
+        tokio::join!(
+          watermark.wait_for_mark(5),
+          watermark.wait_for_mark(6),
+          watermark.wait_for_mark(7),
+        )
+      };
+      tokio::pin!(all);
+      // polls all three waiters, registering them before the mark moves
+      assert!(tokio::time::timeout(Duration::from_millis(50), &mut all)
+        .await
+        .is_err());
+
+      // a single done moves the mark from 0 to 8
+      watermark.done(5).unwrap();
+      let (w5, w6, w7) = all.await;
+      w5.unwrap();
+      w6.unwrap();
+      w7.unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 8);
+    })
+    .await;
+  }
+
+  #[tokio::test]
+  async fn test_drop_stops_worker() {
+    use core::time::Duration;
+
+    let closer = AsyncCloser::<crate::TokioSpawner>::new(1);
+
+    let mut watermark = AsyncWaterMark::<crate::TokioSpawner>::new("watermark".into());
+    watermark.init(closer.clone());
+    watermark.begin(1).unwrap();
+
+    drop(watermark);
+    assert!(tokio::time::timeout(Duration::from_secs(5), closer.wait())
+      .await
+      .is_ok());
+  }
 }
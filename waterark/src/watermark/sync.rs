@@ -1,13 +1,17 @@
 use crossbeam_channel::{bounded, select, Receiver, Sender};
 use crossbeam_utils::CachePadded;
+use dbutils::leb128::{
+  decode_i64_varint, decode_u64_varint, encode_i64_varint, encode_u64_varint, DecodeVarintError,
+};
 use smallvec_wrapper::MediumVec;
 use std::{
   borrow::Cow,
   cell::RefCell,
   cmp::Reverse,
   collections::{BinaryHeap, HashMap},
+  io,
   sync::{
-    atomic::{AtomicU64, Ordering},
+    atomic::{AtomicU64, AtomicUsize, Ordering},
     Arc,
   },
 };
@@ -16,10 +20,17 @@ use crate::{closer::sync::Closer, watermark::WaterMarkError};
 
 type Result<T> = std::result::Result<T, WaterMarkError>;
 
+/// Set in [`Inner::state`] while a `done` is taking the fast path.
+const BUSY: usize = 1 << (usize::BITS - 1);
+
 #[derive(Debug)]
 enum MarkIndex {
   Single(u64),
   Multiple(MediumVec<u64>),
+  /// Marks an index as never coming, see [`WaterMark::skip`].
+  Skip(u64),
+  /// Requests the done until mark and a copy of the pending set, see [`WaterMark::checkpoint`].
+  Checkpoint(Sender<(u64, Vec<(u64, i64)>)>),
 }
 
 #[derive(Debug)]
@@ -36,38 +47,114 @@ struct Inner {
   name: Cow<'static, str>,
   mark_tx: Sender<Mark>,
   mark_rx: Receiver<Mark>,
+  num_pending: AtomicUsize,
+  max_pending: Option<usize>,
+  /// The number of marks sent but not yet processed, plus [`BUSY`] while a `done` is taking
+  /// the fast path. The processor never runs concurrently with the fast path.
+  state: CachePadded<AtomicUsize>,
+  /// An index begun without sending a mark, `0` if none. The processor takes it over
+  /// before processing any mark.
+  fast_index: AtomicU64,
+  /// The number of pending indices and waiters tracked by the processor.
+  tracked: AtomicUsize,
 }
 
 impl Inner {
-  fn process(&self, closer: Closer) {
+  #[inline]
+  fn send(&self, mark: Mark) {
+    self.state.fetch_add(1, Ordering::AcqRel);
+    self.mark_tx.send(mark).unwrap() // unwrap is safe because self also holds a receiver
+  }
+
+  /// Begins `index` without sending a mark, if no other index is begun this way.
+  #[inline]
+  fn begin_fast(&self, index: u64) -> bool {
+    if index <= self.done_until.load(Ordering::SeqCst) {
+      return false;
+    }
+
+    self
+      .fast_index
+      .compare_exchange(0, index, Ordering::AcqRel, Ordering::Acquire)
+      .is_ok()
+  }
+
+  /// Finishes `index` without sending a mark.
+  ///
+  /// This only succeeds if `index` has been begun by [`begin_fast`](Inner::begin_fast) and
+  /// the processor is idle and tracks nothing, in which case `index` is the only pending
+  /// index, so the mark moves to it, exactly as the processor would do.
+  #[inline]
+  fn done_fast(&self, index: u64) -> bool {
+    if self
+      .state
+      .compare_exchange(0, BUSY, Ordering::AcqRel, Ordering::Relaxed)
+      .is_err()
+    {
+      return false;
+    }
+
+    let fast = self.tracked.load(Ordering::Acquire) == 0
+      && self
+        .fast_index
+        .compare_exchange(index, 0, Ordering::AcqRel, Ordering::Acquire)
+        .is_ok();
+    if fast {
+      self.done_until.fetch_max(index, Ordering::SeqCst);
+    }
+
+    self.state.fetch_and(!BUSY, Ordering::Release);
+    fast
+  }
+
+  fn process(&self, closer: Closer, restored: Vec<(u64, i64)>, stop: Receiver<()>) {
     scopeguard::defer!(closer.done(););
 
-    let mut indices: BinaryHeap<Reverse<u64>> = BinaryHeap::new();
+    let mut indices: BinaryHeap<Reverse<u64>> =
+      restored.iter().map(|(idx, _)| Reverse(*idx)).collect();
     // pending maps raft proposal index to the number of pending mutations for this proposal.
-    let pending: RefCell<HashMap<u64, i64>> = RefCell::new(HashMap::new());
+    let pending: RefCell<HashMap<u64, i64>> = RefCell::new(restored.into_iter().collect());
     let waiters: RefCell<HashMap<u64, MediumVec<Sender<()>>>> = RefCell::new(HashMap::new());
 
-    let mut process_one = |idx: u64, done: bool| {
+    let mut process_one = |idx: u64, done: bool, skip: bool| {
       // If not already done, then set. Otherwise, don't undo a done entry.
       let mut pending = pending.borrow_mut();
       let mut waiters = waiters.borrow_mut();
 
+      let done_until = self.done_until.load(Ordering::SeqCst);
+      if skip && idx <= done_until {
+        // The mark has already moved past the skipped index.
+        return;
+      }
+
       if !pending.contains_key(&idx) {
         indices.push(Reverse(idx));
       }
 
-      let mut delta = 1;
-      if done {
-        delta = -1;
+      if skip {
+        // A skipped index is done regardless of how many times it has been begun, so
+        // release the pending slots which will never be released by `done`.
+        let begun = pending.insert(idx, 0).unwrap_or(0);
+        if begun > 0 {
+          let _ = self
+            .num_pending
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+              Some(pending.saturating_sub(begun as usize))
+            });
+        }
+      } else {
+        let mut delta = 1;
+        if done {
+          delta = -1;
+        }
+        pending
+          .entry(idx)
+          .and_modify(|v| *v += delta)
+          .or_insert(delta);
       }
-      pending
-        .entry(idx)
-        .and_modify(|v| *v += delta)
-        .or_insert(delta);
 
       // Update mark by going through all indices in order; and checking if they have
       // been done. Stop at the first index, which isn't done.
-      let done_until = self.done_until.load(Ordering::SeqCst);
       assert!(
         done_until <= idx,
         "name: {}, done_until: {}, idx: {}",
@@ -101,6 +188,8 @@ impl Inner {
         );
       }
 
+      // Wake every waiter in (done_until, until], so a single jump of the mark
+      // releases all of the satisfied waiters at once.
       if until - done_until <= waiters.len() as u64 {
         // Close channel and remove from waiters.
         (done_until + 1..=until).for_each(|idx| {
@@ -116,8 +205,20 @@ impl Inner {
     loop {
       select! {
         recv(closer) -> _ => return,
+        // the watermark has been dropped.
+        recv(stop) -> _ => return,
         recv(self.mark_rx) -> mark => match mark {
           Ok(mark) => {
+            // Wait for a `done` taking the fast path, it never holds the flag for long.
+            while self.state.load(Ordering::Acquire) & BUSY != 0 {
+              std::hint::spin_loop();
+            }
+
+            let fast_index = self.fast_index.swap(0, Ordering::AcqRel);
+            if fast_index != 0 {
+              process_one(fast_index, false, false);
+            }
+
             if let Some(wait_tx) = mark.waiter {
               if let MarkIndex::Single(index) = mark.index {
                 let done_until = self.done_until.load(Ordering::SeqCst);
@@ -129,10 +230,18 @@ impl Inner {
               }
             } else {
               match mark.index {
-                MarkIndex::Single(idx) => process_one(idx, mark.done),
-                MarkIndex::Multiple(indices) => indices.into_iter().for_each(|idx| process_one(idx, mark.done)),
+                MarkIndex::Single(idx) => process_one(idx, mark.done, false),
+                MarkIndex::Multiple(indices) => indices.into_iter().for_each(|idx| process_one(idx, mark.done, false)),
+                MarkIndex::Skip(idx) => process_one(idx, true, true),
+                MarkIndex::Checkpoint(tx) => {
+                  let pending = pending.borrow().iter().map(|(idx, count)| (*idx, *count)).collect();
+                  let _ = tx.send((self.done_until.load(Ordering::SeqCst), pending));
+                }
               }
             }
+
+            self.tracked.store(pending.borrow().len() + waiters.borrow().len(), Ordering::Release);
+            self.state.fetch_sub(1, Ordering::AcqRel);
           },
           Err(_) => {
             // Channel closed.
@@ -156,10 +265,19 @@ impl Inner {
 ///
 /// Since `done_until` and `last_index` addresses are passed to sync/atomic packages, we ensure that they
 /// are 64-bit aligned by putting them at the beginning of the structure.
+///
+/// Dropping an initialized watermark stops its background thread and **blocks until the thread
+/// has exited**, which is quick since the thread only processes marks. The [`Closer`] passed to
+/// [`init`](WaterMark::init) is not signaled, as it may be shared with other workers, but the
+/// thread calls [`Closer::done`] on exit as usual.
 #[derive(Debug)]
 pub struct WaterMark {
   inner: Arc<Inner>,
   initialized: bool,
+  restored: Vec<(u64, i64)>,
+  worker: Option<(Sender<()>, std::thread::JoinHandle<()>)>,
+  /// Disconnected once the background thread has exited.
+  exited: Option<Receiver<()>>,
 }
 
 impl WaterMark {
@@ -168,6 +286,23 @@ impl WaterMark {
   /// **Note**: Before using the watermark, you must call `init` to start the background thread.
   #[inline]
   pub fn new(name: Cow<'static, str>) -> Self {
+    Self::new_in(name, None)
+  }
+
+  /// Create a new WaterMark with the given name, which allows at most `max_pending`
+  /// indices to be pending (begun but not yet done) at the same time.
+  ///
+  /// When the pending set is full, [`try_begin`](WaterMark::try_begin) and
+  /// [`try_begin_many`](WaterMark::try_begin_many) return [`WaterMarkError::TooManyPending`].
+  ///
+  /// **Note**: Before using the watermark, you must call `init` to start the background thread.
+  #[inline]
+  pub fn with_capacity(name: Cow<'static, str>, max_pending: usize) -> Self {
+    Self::new_in(name, Some(max_pending))
+  }
+
+  #[inline]
+  fn new_in(name: Cow<'static, str>, max_pending: Option<usize>) -> Self {
     let (mark_tx, mark_rx) = bounded(100);
     Self {
       inner: Arc::new(Inner {
@@ -176,11 +311,59 @@ impl WaterMark {
         name,
         mark_tx,
         mark_rx,
+        num_pending: AtomicUsize::new(0),
+        max_pending,
+        state: CachePadded::new(AtomicUsize::new(0)),
+        fast_index: AtomicU64::new(0),
+        tracked: AtomicUsize::new(0),
       }),
       initialized: false,
+      restored: Vec::new(),
+      worker: None,
+      exited: None,
     }
   }
 
+  /// Loads a WaterMark with the given name from a checkpoint written by
+  /// [`checkpoint`](WaterMark::checkpoint).
+  ///
+  /// The returned watermark has the same done until mark, last index and pending set as the
+  /// checkpointed one. The pending set capacity is not restored.
+  ///
+  /// **Note**: Before using the watermark, you must call `init` to start the background thread.
+  pub fn load<R: io::Read>(name: Cow<'static, str>, mut reader: R) -> io::Result<Self> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+
+    let mut offset = 0;
+    let done_until = read_varint(&buf, &mut offset, decode_u64_varint)?;
+    let last_index = read_varint(&buf, &mut offset, decode_u64_varint)?;
+    let len = read_varint(&buf, &mut offset, decode_u64_varint)?;
+
+    let mut restored = Vec::new();
+    let mut num_pending = 0usize;
+    for _ in 0..len {
+      let idx = read_varint(&buf, &mut offset, decode_u64_varint)?;
+      let count = read_varint(&buf, &mut offset, decode_i64_varint)?;
+      if idx <= done_until {
+        return Err(io::Error::new(
+          io::ErrorKind::InvalidData,
+          "watermark: pending index is not greater than done until",
+        ));
+      }
+      num_pending += count.max(0) as usize;
+      restored.push((idx, count));
+    }
+
+    let mut this = Self::new(name);
+    this.inner.done_until.store(done_until, Ordering::SeqCst);
+    this.inner.last_index.store(last_index, Ordering::SeqCst);
+    this.inner.num_pending.store(num_pending, Ordering::SeqCst);
+    this.inner.tracked.store(restored.len(), Ordering::SeqCst);
+    this.restored = restored;
+    Ok(this)
+  }
+
   /// Returns the name of the watermark.
   #[inline(always)]
   pub fn name(&self) -> &str {
@@ -196,25 +379,68 @@ impl WaterMark {
 
     self.initialized = true;
     let inner = self.inner.clone();
-    std::thread::spawn(move || {
-      inner.process(closer);
+    let restored = core::mem::take(&mut self.restored);
+    // never sent to, the worker stops when the sender is dropped with the watermark.
+    let (stop_tx, stop_rx) = bounded(0);
+    // never sent to, the thread drops the sender when it exits.
+    let (exited_tx, exited_rx) = bounded::<()>(0);
+    let handle = std::thread::spawn(move || {
+      let _exited = exited_tx;
+      inner.process(closer, restored, stop_rx);
     });
+    self.worker = Some((stop_tx, handle));
+    self.exited = Some(exited_rx);
+  }
+
+  /// Writes the done until mark, the last index and the pending set to `writer`, so the
+  /// watermark can be reconstructed by [`load`](WaterMark::load) after a restart.
+  ///
+  /// Every `begin` and `done` called before this method is reflected in the checkpoint.
+  /// All of the numbers are encoded in LEB128 format.
+  pub fn checkpoint<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.check().map_err(io::Error::other)?;
+
+    let (tx, rx) = bounded(1);
+    self.inner.send(Mark {
+      index: MarkIndex::Checkpoint(tx),
+      waiter: None,
+      done: false,
+    });
+    let (done_until, mut pending) = rx
+      .recv()
+      .map_err(|_| io::Error::other(WaterMarkError::Canceled))?;
+    pending.sort_unstable_by_key(|(idx, _)| *idx);
+    let last_index = self.inner.last_index.load(Ordering::SeqCst);
+
+    let mut buf = [0; 10];
+    for val in [done_until, last_index, pending.len() as u64] {
+      let len = encode_u64_varint(val, &mut buf).map_err(io::Error::other)?;
+      writer.write_all(&buf[..len])?;
+    }
+    for (idx, count) in pending {
+      let len = encode_u64_varint(idx, &mut buf).map_err(io::Error::other)?;
+      writer.write_all(&buf[..len])?;
+      let len = encode_i64_varint(count, &mut buf).map_err(io::Error::other)?;
+      writer.write_all(&buf[..len])?;
+    }
+    Ok(())
   }
 
   /// Sets the last index to the given value.
+  ///
+  /// The pending set capacity is not enforced, see [`try_begin`](WaterMark::try_begin).
   #[inline]
   pub fn begin(&self, index: u64) -> Result<()> {
     self.check().map(|_| {
+      self.inner.num_pending.fetch_add(1, Ordering::AcqRel);
       self.inner.last_index.store(index, Ordering::SeqCst);
-      self
-        .inner
-        .mark_tx
-        .send(Mark {
+      if !self.inner.begin_fast(index) {
+        self.inner.send(Mark {
           index: MarkIndex::Single(index),
           waiter: None,
           done: false,
         })
-        .unwrap()
+      }
     })
   }
 
@@ -226,33 +452,73 @@ impl WaterMark {
     }
 
     self.check().map(|_| {
-      let last_index = *indices.last().unwrap();
-      self.inner.last_index.store(last_index, Ordering::SeqCst);
       self
         .inner
-        .mark_tx
-        .send(Mark {
-          index: MarkIndex::Multiple(indices),
-          waiter: None,
-          done: false,
-        })
-        .unwrap()
+        .num_pending
+        .fetch_add(indices.len(), Ordering::AcqRel);
+      let last_index = *indices.last().unwrap();
+      self.inner.last_index.store(last_index, Ordering::SeqCst);
+      self.inner.send(Mark {
+        index: MarkIndex::Multiple(indices),
+        waiter: None,
+        done: false,
+      })
     })
   }
 
+  /// Works like [`begin`](WaterMark::begin), but returns [`WaterMarkError::TooManyPending`]
+  /// if the pending set is full.
+  #[inline]
+  pub fn try_begin(&self, index: u64) -> Result<()> {
+    self.check()?;
+    self.reserve_pending(1)?;
+    self.inner.last_index.store(index, Ordering::SeqCst);
+    if !self.inner.begin_fast(index) {
+      self.inner.send(Mark {
+        index: MarkIndex::Single(index),
+        waiter: None,
+        done: false,
+      });
+    }
+    Ok(())
+  }
+
+  /// Works like [`begin_many`](WaterMark::begin_many), but returns [`WaterMarkError::TooManyPending`]
+  /// if the pending set cannot hold all of the indices.
+  #[inline]
+  pub fn try_begin_many(&self, indices: MediumVec<u64>) -> Result<()> {
+    if indices.is_empty() {
+      return Ok(());
+    }
+
+    self.check()?;
+    self.reserve_pending(indices.len())?;
+    let last_index = *indices.last().unwrap();
+    self.inner.last_index.store(last_index, Ordering::SeqCst);
+    self.inner.send(Mark {
+      index: MarkIndex::Multiple(indices),
+      waiter: None,
+      done: false,
+    });
+    Ok(())
+  }
+
   /// Sets a single index as done.
+  ///
+  /// When `index` is the only pending index and nothing is waiting for the mark, e.g. when
+  /// indices are begun and done one after another, the mark is moved to `index` directly,
+  /// without going through the background thread.
   #[inline]
   pub fn done(&self, index: u64) -> Result<()> {
     self.check().map(|_| {
-      self
-        .inner
-        .mark_tx
-        .send(Mark {
+      self.release_pending(1);
+      if !self.inner.done_fast(index) {
+        self.inner.send(Mark {
           index: MarkIndex::Single(index),
           waiter: None,
           done: true,
         })
-        .unwrap() // unwrap is safe because self also holds a receiver
+      }
     })
   }
 
@@ -264,15 +530,34 @@ impl WaterMark {
     }
 
     self.check().map(|_| {
-      self
-        .inner
-        .mark_tx
-        .send(Mark {
-          index: MarkIndex::Multiple(indices),
-          waiter: None,
-          done: true,
-        })
-        .unwrap() // unwrap is safe because self also holds a receiver
+      self.release_pending(indices.len());
+      self.inner.send(Mark {
+        index: MarkIndex::Multiple(indices),
+        waiter: None,
+        done: true,
+      })
+    })
+  }
+
+  /// Marks an index as never coming, so the mark can advance past it.
+  ///
+  /// This is meant for an index which has been reserved but will never be done, e.g. the
+  /// version of an aborted transaction. The skipped index is treated as done no matter how
+  /// many times it has been begun, and the pending slots taken by those `begin`s are
+  /// released. Skipping an index which has not been begun marks it as done, so the mark does
+  /// not stop at it, like [`done`](WaterMark::done) it must not be called before the lower
+  /// indices are begun. Skipping an index the mark has already passed is a no-op.
+  ///
+  /// Do not call [`done`](WaterMark::done) for a skipped index, a later `done` is counted
+  /// as a regular one.
+  #[inline]
+  pub fn skip(&self, index: u64) -> Result<()> {
+    self.check().map(|_| {
+      self.inner.send(Mark {
+        index: MarkIndex::Skip(index),
+        waiter: None,
+        done: true,
+      })
     })
   }
 
@@ -303,26 +588,83 @@ impl WaterMark {
   }
 
   /// Waits until the given index is marked as done.
+  ///
+  /// When the mark advances past several waiting indices at once (e.g. a single `done`
+  /// completes the lowest pending index while higher ones are already done), every
+  /// waiter whose index is now satisfied is woken, not just the lowest one.
+  ///
+  /// Returns [`WaterMarkError::Canceled`] if the background thread has exited, e.g. because
+  /// the [`Closer`] was signaled, before the index is done.
   #[inline]
   pub fn wait_for_mark(&self, index: u64) -> Result<()> {
-    self.check().map(|_| {
-      if self.inner.done_until.load(Ordering::SeqCst) >= index {
-        return;
-      }
+    self.check()?;
+    if self.inner.done_until.load(Ordering::SeqCst) >= index {
+      return Ok(());
+    }
 
-      let (wait_tx, wait_rx) = bounded(1);
-      self
-        .inner
-        .mark_tx
-        .send(Mark {
-          index: MarkIndex::Single(index),
-          waiter: Some(wait_tx),
-          done: false,
-        })
-        .unwrap(); // unwrap is safe because self also holds a receiver
+    let (wait_tx, wait_rx) = bounded(1);
+    self.inner.send(Mark {
+      index: MarkIndex::Single(index),
+      waiter: Some(wait_tx),
+      done: false,
+    });
 
-      let _ = wait_rx.recv();
-    })
+    // the mark stays in the channel once the thread has exited, so also wait for the exit.
+    let exited = self
+      .exited
+      .as_ref()
+      .expect("initialized watermark has a worker");
+    select! {
+      recv(wait_rx) -> _ => {},
+      recv(exited) -> _ => {},
+    }
+
+    if self.inner.done_until.load(Ordering::SeqCst) >= index {
+      Ok(())
+    } else {
+      Err(WaterMarkError::Canceled)
+    }
+  }
+
+  /// Waits until every index begun before this call is done, i.e. until
+  /// [`done_until`](WaterMark::done_until) reaches the [`last_index`](WaterMark::last_index)
+  /// observed when this method is called.
+  ///
+  /// Indices begun after the call are not waited for. Returns [`WaterMarkError::Canceled`]
+  /// if the watermark is closed by its [`Closer`] before all of the indices are done.
+  #[inline]
+  pub fn wait_for_all(&self) -> Result<()> {
+    self.check()?;
+    let last_index = self.inner.last_index.load(Ordering::SeqCst);
+    self.wait_for_mark(last_index)
+  }
+
+  #[inline]
+  fn reserve_pending(&self, n: usize) -> Result<()> {
+    let Some(max) = self.inner.max_pending else {
+      self.inner.num_pending.fetch_add(n, Ordering::AcqRel);
+      return Ok(());
+    };
+
+    self
+      .inner
+      .num_pending
+      .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+        pending.checked_add(n).filter(|pending| *pending <= max)
+      })
+      .map(|_| ())
+      .map_err(|_| WaterMarkError::TooManyPending)
+  }
+
+  #[inline]
+  fn release_pending(&self, n: usize) {
+    // done may be called more times than begin, so never underflow.
+    let _ = self
+      .inner
+      .num_pending
+      .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+        Some(pending.saturating_sub(n))
+      });
   }
 
   #[inline]
@@ -334,6 +676,27 @@ impl WaterMark {
   }
 }
 
+#[inline]
+fn read_varint<T>(
+  buf: &[u8],
+  offset: &mut usize,
+  decode: fn(&[u8]) -> core::result::Result<(usize, T), DecodeVarintError>,
+) -> io::Result<T> {
+  let (read, val) =
+    decode(&buf[*offset..]).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+  *offset += read;
+  Ok(val)
+}
+
+impl Drop for WaterMark {
+  fn drop(&mut self) {
+    if let Some((stop, handle)) = self.worker.take() {
+      drop(stop);
+      let _ = handle.join();
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -406,6 +769,71 @@ mod tests {
     });
   }
 
+  #[test]
+  fn test_wait_for_all() {
+    use core::time::Duration;
+
+    init_and_close(|watermark| {
+      watermark.begin(1).unwrap();
+      watermark.begin(2).unwrap();
+      watermark.begin(3).unwrap();
+
+      std::thread::scope(|s| {
+        let waiter = s.spawn(|| watermark.wait_for_all());
+
+        for idx in 1..=2 {
+          watermark.done(idx).unwrap();
+          std::thread::sleep(Duration::from_millis(50));
+          assert!(!waiter.is_finished());
+        }
+
+        watermark.done(3).unwrap();
+        waiter.join().unwrap().unwrap();
+        assert_eq!(watermark.done_until().unwrap(), 3);
+      });
+    });
+  }
+
+  #[test]
+  fn test_wait_for_all_canceled() {
+    let closer = Closer::new(1);
+    let mut watermark = WaterMark::new("watermark".into());
+    watermark.init(closer.clone());
+    closer.signal_and_wait();
+
+    watermark.begin(1).unwrap();
+    assert_eq!(watermark.wait_for_all(), Err(WaterMarkError::Canceled));
+    assert_eq!(watermark.wait_for_mark(1), Err(WaterMarkError::Canceled));
+  }
+
+  #[test]
+  fn test_bounded_pending() {
+    let closer = Closer::new(1);
+    let mut watermark = WaterMark::with_capacity("watermark".into(), 3);
+    watermark.init(closer.clone());
+
+    watermark.try_begin(1).unwrap();
+    watermark
+      .try_begin_many([2, 3].into_iter().collect())
+      .unwrap();
+    assert_eq!(
+      watermark.try_begin(4).unwrap_err(),
+      WaterMarkError::TooManyPending
+    );
+    assert_eq!(
+      watermark
+        .try_begin_many([4, 5].into_iter().collect())
+        .unwrap_err(),
+      WaterMarkError::TooManyPending
+    );
+
+    watermark.done(1).unwrap();
+    watermark.try_begin(4).unwrap();
+    assert_eq!(watermark.last_index().unwrap(), 4);
+
+    closer.signal_and_wait();
+  }
+
   #[test]
   fn test_multiple_singles() {
     let closer = Closer::default();
@@ -420,6 +848,44 @@ mod tests {
     closer.signal();
   }
 
+  #[test]
+  fn test_closer_wait_timeout() {
+    use crate::clock::{Clock, ManualClock};
+    use core::time::Duration;
+
+    let clock = ManualClock::new();
+    let start = clock.now();
+    let real = std::time::Instant::now();
+
+    let closer = Closer::new(1);
+    assert!(!closer.wait_timeout_with(Duration::from_secs(60), &clock));
+    assert_eq!(clock.now() - start, Duration::from_secs(60));
+    assert!(real.elapsed() < Duration::from_secs(10));
+
+    closer.done();
+    assert!(closer.wait_timeout_with(Duration::from_secs(60), &clock));
+    assert_eq!(clock.elapsed(), Duration::from_secs(60));
+    assert!(closer.wait_timeout(Duration::from_millis(10)));
+  }
+
+  #[test]
+  fn test_closer_wait_timeout_wakes_on_done() {
+    use core::time::Duration;
+
+    let closer = Closer::new(1);
+    assert!(!closer.wait_timeout(Duration::from_millis(10)));
+
+    let start = std::time::Instant::now();
+    let tc = closer.clone();
+    let handle = std::thread::spawn(move || {
+      std::thread::sleep(Duration::from_millis(20));
+      tc.done();
+    });
+    assert!(closer.wait_timeout(Duration::from_secs(60)));
+    assert!(start.elapsed() < Duration::from_secs(10));
+    handle.join().unwrap();
+  }
+
   #[test]
   fn test_closer() {
     let closer = Closer::new(1);
@@ -455,4 +921,246 @@ mod tests {
       rx.recv_timeout(Duration::from_millis(1000)).unwrap();
     }
   }
+
+  #[test]
+  fn test_closer_reason() {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Reason {
+      Graceful,
+      Fatal(&'static str),
+    }
+
+    let c = Closer::<Reason>::with_initial(0);
+    assert_eq!(c.reason(), None);
+
+    let handles = (0..4)
+      .map(|_| {
+        let c = c.clone();
+        std::thread::spawn(move || c.wait_signal())
+      })
+      .collect::<Vec<_>>();
+
+    c.signal_with(Reason::Fatal("disk full"));
+    // only the first signal is recorded
+    c.signal_with(Reason::Graceful);
+    c.signal();
+
+    for h in handles {
+      assert_eq!(h.join().unwrap(), Some(Reason::Fatal("disk full")));
+    }
+    assert_eq!(c.reason(), Some(Reason::Fatal("disk full")));
+
+    let c = Closer::default();
+    c.signal();
+    assert_eq!(c.wait_signal(), None);
+  }
+
+  #[test]
+  fn test_wait_for_mark_wakes_all_satisfied() {
+    use core::time::Duration;
+
+    init_and_close(|watermark| {
+      watermark
+        .begin_many([5, 6, 7, 8].into_iter().collect())
+        .unwrap();
+      watermark
+        .done_many([6, 7, 8].into_iter().collect())
+        .unwrap();
+
+      std::thread::scope(|s| {
+        let waiters = [5, 6, 7].map(|idx| s.spawn(move || watermark.wait_for_mark(idx)));
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(waiters.iter().all(|w| !w.is_finished()));
+
+        // a single done moves the mark from 0 to 8
+        watermark.done(5).unwrap();
+        for w in waiters {
+          w.join().unwrap().unwrap();
+        }
+      });
+      assert_eq!(watermark.done_until().unwrap(), 8);
+    });
+  }
+
+  #[test]
+  fn test_done_fast_path() {
+    init_and_close(|watermark| {
+      // begun and done one after another, the mark moves without the background thread.
+      for idx in 1..=100 {
+        watermark.begin(idx).unwrap();
+        watermark.done(idx).unwrap();
+        assert_eq!(watermark.done_until().unwrap(), idx);
+      }
+
+      // 101 is still pending, so 102 takes the full path.
+      watermark.begin(101).unwrap();
+      watermark.begin(102).unwrap();
+      watermark.done(102).unwrap();
+      watermark.done(101).unwrap();
+      watermark.wait_for_mark(102).unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 102);
+
+      watermark.begin(103).unwrap();
+      watermark.done(103).unwrap();
+      watermark.wait_for_mark(103).unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 103);
+    });
+  }
+
+  #[test]
+  fn test_concurrent_begin_done() {
+    use std::sync::Mutex;
+
+    const THREADS: u64 = 8;
+    const ROUNDS: u64 = 1000;
+
+    init_and_close(|watermark| {
+      // indices are begun in order, like the versions handed out by a transaction oracle.
+      let next = Mutex::new(0u64);
+
+      std::thread::scope(|s| {
+        for _ in 0..THREADS {
+          s.spawn(|| {
+            let mut last = 0;
+            for _ in 0..ROUNDS {
+              let idx = {
+                let mut next = next.lock().unwrap();
+                *next += 1;
+                watermark.begin(*next).unwrap();
+                *next
+              };
+
+              let done_until = watermark.done_until().unwrap();
+              assert!(done_until < idx);
+              assert!(done_until >= last);
+              last = done_until;
+
+              watermark.done(idx).unwrap();
+            }
+          });
+        }
+      });
+
+      let max = THREADS * ROUNDS;
+      watermark.wait_for_all().unwrap();
+      assert_eq!(watermark.done_until().unwrap(), max);
+      assert_eq!(watermark.last_index().unwrap(), max);
+    });
+  }
+
+  #[test]
+  fn test_skip() {
+    init_and_close(|watermark| {
+      watermark.begin(1).unwrap();
+      watermark.begin(3).unwrap();
+      watermark.done(1).unwrap();
+      watermark.done(3).unwrap();
+      watermark.skip(2).unwrap();
+      watermark.wait_for_mark(3).unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 3);
+    });
+
+    // 5 is reserved by an aborted transaction and never done.
+    init_and_close(|watermark| {
+      watermark
+        .begin_many([4, 5, 6].into_iter().collect())
+        .unwrap();
+      watermark.done_many([4, 6].into_iter().collect()).unwrap();
+      watermark.wait_for_mark(4).unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 4);
+
+      watermark.skip(5).unwrap();
+      watermark.wait_for_mark(6).unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 6);
+    });
+
+    // 8 is reserved but never begun.
+    init_and_close(|watermark| {
+      watermark.begin(7).unwrap();
+      watermark.skip(8).unwrap();
+      watermark.begin(9).unwrap();
+      watermark.done(7).unwrap();
+      watermark.done(9).unwrap();
+      watermark.wait_for_mark(9).unwrap();
+      assert_eq!(watermark.done_until().unwrap(), 9);
+    });
+  }
+
+  #[test]
+  fn test_skip_releases_pending() {
+    let closer = Closer::new(1);
+    let mut watermark = WaterMark::with_capacity("watermark".into(), 2);
+    watermark.init(closer.clone());
+
+    watermark
+      .try_begin_many([1, 2].into_iter().collect())
+      .unwrap();
+    assert_eq!(
+      watermark.try_begin(3).unwrap_err(),
+      WaterMarkError::TooManyPending
+    );
+
+    watermark.skip(1).unwrap();
+    watermark.wait_for_mark(1).unwrap();
+    watermark.try_begin(3).unwrap();
+
+    closer.signal_and_wait();
+  }
+
+  #[test]
+  fn test_checkpoint_load() {
+    let closer = Closer::new(1);
+    let mut watermark = WaterMark::new("watermark".into());
+    watermark.init(closer.clone());
+
+    watermark
+      .begin_many([1, 2, 3, 4].into_iter().collect())
+      .unwrap();
+    watermark.done_many([1, 3].into_iter().collect()).unwrap();
+    watermark.wait_for_mark(1).unwrap();
+
+    let mut checkpoint = Vec::new();
+    watermark.checkpoint(&mut checkpoint).unwrap();
+
+    let mut loaded = WaterMark::load("loaded".into(), checkpoint.as_slice()).unwrap();
+    loaded.init(closer.clone());
+    assert_eq!(loaded.done_until().unwrap(), 1);
+    assert_eq!(loaded.last_index().unwrap(), 4);
+
+    let mut reloaded = Vec::new();
+    loaded.checkpoint(&mut reloaded).unwrap();
+    assert_eq!(reloaded, checkpoint);
+
+    // 3 is already done, so finishing 2 moves the mark over it.
+    loaded.done(2).unwrap();
+    loaded.wait_for_mark(3).unwrap();
+    assert_eq!(loaded.done_until().unwrap(), 3);
+    loaded.done(4).unwrap();
+    loaded.wait_for_all().unwrap();
+
+    let mut corrupted = checkpoint.clone();
+    corrupted.truncate(corrupted.len() - 1);
+    assert_eq!(
+      WaterMark::load("corrupted".into(), corrupted.as_slice())
+        .unwrap_err()
+        .kind(),
+      std::io::ErrorKind::InvalidData
+    );
+
+    closer.signal_and_wait();
+  }
+
+  #[test]
+  fn test_drop_stops_worker() {
+    let closer = Closer::new(1);
+
+    let mut watermark = WaterMark::new("watermark".into());
+    watermark.init(closer.clone());
+    watermark.begin(1).unwrap();
+
+    // the worker is joined on drop, so it has called `done` without any signal.
+    drop(watermark);
+    assert!(closer.wait_timeout(core::time::Duration::ZERO));
+  }
 }